@@ -0,0 +1,129 @@
+//! Benchmarks for decoding, encoding, formatting, parsing and comparing
+//! Decimal128.
+//!
+//! Each benchmark runs over three corpora, so a regression in one kind of
+//! value doesn't hide behind the others.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use decimal128::*;
+
+// Values with a handful of digits and small exponents: 1, -5, 5.00, 123.45,
+// -0.001, 1E+3 and 0.
+const SMALL: [u128; 7] = [
+    0x3040_0000_0000_0000_0000_0000_0000_0001,
+    0xb040_0000_0000_0000_0000_0000_0000_0005,
+    0x303c_0000_0000_0000_0000_0000_0000_01f4,
+    0x303c_0000_0000_0000_0000_0000_0000_3039,
+    0xb03a_0000_0000_0000_0000_0000_0000_0001,
+    0x3046_0000_0000_0000_0000_0000_0000_0001,
+    0x3040_0000_0000_0000_0000_0000_0000_0000,
+];
+
+// Values with long coefficients, in both positional and scientific notation:
+// 0.1234567890123456789012345678901234,
+// 1.000000000000000000000000000000000E+6144, 1E-6176, 0.00123400000 and
+// 9.999999999999999999999999999999999E-6143.
+const LONG: [u128; 5] = [
+    0x2ffc_3cde_6fff_9732_de82_5cd0_7e96_aff2,
+    0x5ffe_314d_c644_8d93_38c1_5b0a_0000_0000,
+    0x0000_0000_0000_0000_0000_0000_0000_0001,
+    0x302a_0000_0000_0000_0000_0000_075a_ef40,
+    0x0001_ed09_bead_87c0_378d_8e63_ffff_ffff,
+];
+
+// NaN, Infinity and -Infinity.
+const SPECIAL: [u128; 3] = [
+    0x7c00_0000_0000_0000_0000_0000_0000_0000,
+    0x7800_0000_0000_0000_0000_0000_0000_0000,
+    0xf800_0000_0000_0000_0000_0000_0000_0000,
+];
+
+fn corpora() -> Vec<(&'static str, Vec<[u8; 16]>)> {
+    let bytes = |values: &[u128]| values.iter().map(|v| v.to_be_bytes()).collect();
+    vec![
+        ("small", bytes(&SMALL)),
+        ("long", bytes(&LONG)),
+        ("special", bytes(&SPECIAL)),
+    ]
+}
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_raw_bytes");
+    for (name, corpus) in corpora() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &corpus, |b, corpus| {
+            b.iter(|| {
+                for bytes in corpus {
+                    black_box(Decimal128::from_raw_bytes(black_box(*bytes)));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_raw_bytes");
+    for (name, corpus) in corpora() {
+        let values: Vec<Decimal128> = corpus.into_iter().map(Decimal128::from_raw_bytes).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &values, |b, values| {
+            b.iter(|| {
+                for dec128 in values {
+                    black_box(black_box(dec128).to_raw_bytes());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_string");
+    for (name, corpus) in corpora() {
+        let values: Vec<Decimal128> = corpus.into_iter().map(Decimal128::from_raw_bytes).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &values, |b, values| {
+            b.iter(|| {
+                for dec128 in values {
+                    black_box(black_box(dec128).to_string());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_ascii");
+    for (name, corpus) in corpora() {
+        let strings: Vec<String> = corpus
+            .into_iter()
+            .map(|bytes| Decimal128::from_raw_bytes(bytes).to_string())
+            .collect();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &strings, |b, strings| {
+            b.iter(|| {
+                for string in strings {
+                    black_box(Decimal128::parse_ascii(black_box(string.as_bytes())).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn compare(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compare");
+    for (name, corpus) in corpora() {
+        let values: Vec<Decimal128> = corpus.into_iter().map(Decimal128::from_raw_bytes).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(name), &values, |b, values| {
+            b.iter(|| {
+                for a in values {
+                    for other in values {
+                        black_box(black_box(a).total_cmp(black_box(other)));
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decode, encode, format, parse, compare);
+criterion_main!(benches);
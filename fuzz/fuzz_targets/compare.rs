@@ -0,0 +1,15 @@
+//! Comparing two values the other way round gives the opposite result, for
+//! every pair of encodings.
+#![no_main]
+use decimal128::Decimal128;
+use libfuzzer_sys::fuzz_target;
+use std::cmp::Ordering;
+
+fuzz_target!(|values: (Decimal128, Decimal128)| {
+    let (a, b) = values;
+    assert_eq!(a.total_cmp(&b), b.total_cmp(&a).reverse());
+    assert_eq!(a.compare_total(&b), b.compare_total(&a).reverse());
+    assert_eq!(a.partial_cmp(&b), b.partial_cmp(&a).map(|o| o.reverse()));
+    assert_eq!(a.total_cmp(&a), Ordering::Equal);
+    assert_eq!(a.compare_total(&a), Ordering::Equal);
+});
@@ -0,0 +1,17 @@
+//! Decoding any 16 bytes must not panic, and neither must anything done with
+//! the value it decodes to.
+#![no_main]
+use decimal128::Decimal128;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: [u8; 16]| {
+    let dec128 = Decimal128::from_raw_bytes(bytes);
+    assert_eq!(bytes, dec128.to_raw_bytes());
+    let _ = dec128.to_string();
+    let _ = dec128.to_engineering_string();
+    let _ = dec128.to_plain_string();
+    let _ = dec128.to_canonical_string();
+    let _ = dec128.digit_count();
+    let _ = dec128.integer_decode();
+    let _ = dec128.to_sortable_bytes();
+});
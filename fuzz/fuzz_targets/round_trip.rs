@@ -0,0 +1,30 @@
+//! Formatting then parsing gives back the same bytes for every canonical
+//! encoding, and the canonical string gives them back for every encoding.
+#![no_main]
+use decimal128::Decimal128;
+use libfuzzer_sys::fuzz_target;
+
+// The largest coefficient a canonical finite encoding has, 10^34 - 1.
+const MAX_COEFFICIENT: u128 = 9_999_999_999_999_999_999_999_999_999_999_999;
+
+// Whether `bits` is a finite encoding with a coefficient of at most 34
+// digits. Encodings using the `11` combination field have larger implicit
+// coefficients, so they never are.
+fn is_canonical_finite(bits: u128) -> bool {
+    let combination_11 = (bits >> 125) & 0b11 == 0b11;
+    !combination_11 && bits & ((1 << 113) - 1) <= MAX_COEFFICIENT
+}
+
+fuzz_target!(|bytes: [u8; 16]| {
+    let dec128 = Decimal128::from_raw_bytes(bytes);
+
+    if is_canonical_finite(u128::from_be_bytes(bytes)) {
+        let string = dec128.to_string();
+        let parsed: Decimal128 = string.parse().expect("to_string output parses");
+        assert_eq!(bytes, parsed.to_raw_bytes(), "{}", string);
+    }
+
+    let canonical = dec128.to_canonical_string();
+    let parsed = Decimal128::from_canonical_string(&canonical).expect("canonical strings parse");
+    assert_eq!(bytes, parsed.to_raw_bytes(), "{}", canonical);
+});
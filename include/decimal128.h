@@ -0,0 +1,51 @@
+/*
+ * C bindings for the decimal128 crate, built with the `ffi` feature.
+ *
+ * A decimal128_t holds a Decimal128 encoding, most significant byte first.
+ */
+#ifndef DECIMAL128_H
+#define DECIMAL128_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct {
+    uint8_t bytes[16];
+} decimal128_t;
+
+/* Longest string decimal128_to_string writes, including the NUL. */
+#define DECIMAL128_STRING_SIZE 44
+
+/* Return codes of decimal128_from_string. */
+#define DECIMAL128_OK 0
+#define DECIMAL128_ERROR_EMPTY 1
+#define DECIMAL128_ERROR_INVALID_CHARACTER 2
+#define DECIMAL128_ERROR_EXPONENT_OVERFLOW 3
+#define DECIMAL128_ERROR_TOO_MANY_DIGITS 4
+#define DECIMAL128_ERROR_MALFORMED_EXPONENT 5
+#define DECIMAL128_ERROR_NOT_CANONICAL 6
+
+decimal128_t decimal128_from_bytes(const uint8_t *bytes);
+void decimal128_to_bytes(decimal128_t value, uint8_t *out);
+
+int decimal128_from_string(const char *s, decimal128_t *out);
+size_t decimal128_to_string(decimal128_t value, char *buf, size_t len);
+
+/* decimal128_compare returns -1, 0 or 1, or this if either value is NaN. */
+#define DECIMAL128_UNORDERED 2
+
+int decimal128_compare(decimal128_t a, decimal128_t b);
+bool decimal128_is_nan(decimal128_t value);
+bool decimal128_is_negative(decimal128_t value);
+bool decimal128_is_zero(decimal128_t value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* DECIMAL128_H */
@@ -0,0 +1,64 @@
+//! Splitting an amount into parts by weight, without losing or making up
+//! any units of its last digit.
+use crate::{quiet_nan, special, Decimal128, NAN};
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Decimal128 {
+    /// Splits the value into one part per weight, in proportion to the
+    /// weights, so that the parts add up to exactly the value. Every part
+    /// has the value's exponent, so splitting `100.00` gives parts in cents.
+    ///
+    /// Parts are worked out with the largest remainder method: each part
+    /// gets its share rounded down, and the units left over go one each to
+    /// the parts whose shares had the largest fractions, the earlier part
+    /// first when two fractions are equal. Negative values split into
+    /// negative parts the same way.
+    ///
+    /// Every part is NaN if the value is NaN or Infinity or if the weights
+    /// are all zero, and no weights give no parts.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let bill: Decimal128 = "100.00".parse().unwrap();
+    /// let parts: Vec<String> = bill.allocate(&[1, 1, 1]).iter().map(|p| p.to_string()).collect();
+    /// assert_eq!(vec!["33.34", "33.33", "33.33"], parts);
+    /// let parts: Vec<String> = bill.allocate(&[70, 30]).iter().map(|p| p.to_string()).collect();
+    /// assert_eq!(vec!["70.00", "30.00"], parts);
+    /// ```
+    pub fn allocate(&self, weights: &[u32]) -> Vec<Decimal128> {
+        let total: u128 = weights.iter().map(|w| *w as u128).sum();
+        if self.nan {
+            return vec![quiet_nan(self); weights.len()];
+        }
+        if self.inf || total == 0 {
+            return vec![special(false, NAN, 0); weights.len()];
+        }
+
+        // c * w / total, split into a whole number of units and the
+        // numerator of the fraction left over, without overflowing: c is
+        // below 2^113 and w below 2^32
+        let coefficient = self.significand.to_num();
+        let (quotient, remainder) = (coefficient / total, coefficient % total);
+        let mut shares: Vec<(u128, u128)> = weights
+            .iter()
+            .map(|w| {
+                let w = *w as u128;
+                (quotient * w + remainder * w / total, remainder * w % total)
+            })
+            .collect();
+
+        let allocated: u128 = shares.iter().map(|(units, _)| units).sum();
+        let mut order: Vec<usize> = (0..shares.len()).collect();
+        order.sort_by(|a, b| shares[*b].1.cmp(&shares[*a].1));
+        for i in order.into_iter().take((coefficient - allocated) as usize) {
+            shares[i].0 += 1;
+        }
+
+        let exponent = self.exponent.to_adjusted();
+        shares
+            .into_iter()
+            .map(|(units, _)| Decimal128::from_parts(self.sign, exponent, units))
+            .collect()
+    }
+}
@@ -0,0 +1,15 @@
+//! `arbitrary::Arbitrary` support for fuzzing.
+use crate::Decimal128;
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Every 16 byte input is a Decimal128 encoding, so fuzzers reach all of them:
+/// canonical and non-canonical finite values, Infinity and NaN.
+impl<'a> Arbitrary<'a> for Decimal128 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Decimal128::from_raw_bytes(u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u8; 16] as Arbitrary>::size_hint(depth)
+    }
+}
@@ -0,0 +1,744 @@
+//! Addition, subtraction, multiplication and division: the operators, and
+//! variants of them and of the other rounding and dividing operations that
+//! report what happened along the way instead of only returning a result.
+//!
+//! The additions and multiplications here are the ones `Sum` and `Product`
+//! use, and like them division rounds its result half-even to 34 digits.
+use crate::iter::{exact_product, exact_sum, multiply, multiply_digits, sum, to_digits};
+use crate::parse::from_digits_rounded;
+use crate::{
+    pow10, quiet_nan, special, ArithmeticError, ConversionError, Decimal128, RoundingMode,
+    INFINITY, MAX_DIGITS, MAX_EXPONENT, MIN_EXPONENT, NAN,
+};
+use alloc::vec::Vec;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// The exceptional conditions from the [decimal arithmetic
+/// specification](http://speleotrove.com/decimal/daexcep.html) that an
+/// operation raised.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Flags {
+    /// The result was rounded and isn't exactly the mathematical result.
+    pub inexact: bool,
+    /// The result was too large for a finite Decimal128 and became Infinity.
+    pub overflow: bool,
+    /// The result was rounded to a subnormal number (or zero), smaller
+    /// than the smallest normal Decimal128.
+    pub underflow: bool,
+    /// A non-zero finite value was divided by zero.
+    pub division_by_zero: bool,
+    /// The operation has no defined result, like Infinity minus Infinity
+    /// or 0 divided by 0, and the result is NaN.
+    pub invalid_operation: bool,
+}
+
+impl Flags {
+    /// Whether no condition was raised, i.e. the result is exact.
+    pub fn is_empty(&self) -> bool {
+        *self == Flags::default()
+    }
+}
+
+impl Decimal128 {
+    /// Adds `other` and also returns the conditions the addition raised.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let big: Decimal128 = "1E+34".parse().unwrap();
+    /// let (sum, flags) = big.add_with_flags(&"1".parse().unwrap());
+    /// assert_eq!("1.000000000000000000000000000000000E+34", sum.to_string());
+    /// assert!(flags.inexact);
+    /// ```
+    pub fn add_with_flags(&self, other: &Decimal128) -> (Decimal128, Flags) {
+        flags(self, other, add(self, other), false)
+    }
+
+    /// Subtracts `other` and also returns the conditions the subtraction
+    /// raised.
+    pub fn sub_with_flags(&self, other: &Decimal128) -> (Decimal128, Flags) {
+        flags(self, other, add(self, &negate(other)), false)
+    }
+
+    /// Multiplies by `other` and also returns the conditions the
+    /// multiplication raised.
+    pub fn mul_with_flags(&self, other: &Decimal128) -> (Decimal128, Flags) {
+        flags(self, other, multiply(self, other), false)
+    }
+
+    /// Divides by `other` and also returns the conditions the division
+    /// raised. The quotient is the one described for `overflowing_div`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let one: Decimal128 = "1".parse().unwrap();
+    /// let (quotient, flags) = one.div_with_flags(&"0".parse().unwrap());
+    /// assert_eq!("Infinity", quotient.to_string());
+    /// assert!(flags.division_by_zero);
+    /// ```
+    pub fn div_with_flags(&self, other: &Decimal128) -> (Decimal128, Flags) {
+        flags(self, other, divide(self, other), true)
+    }
+
+    /// Divides like `divide_integer` and also returns the conditions the
+    /// division raised. The quotient is exact, so the only ones it can raise
+    /// are `division_by_zero` and `invalid_operation`, which includes the
+    /// spec's division impossible, a quotient of more than 34 digits.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let big: Decimal128 = "1E+34".parse().unwrap();
+    /// let (quotient, flags) = big.divide_integer_with_flags(&"1".parse().unwrap());
+    /// assert!(quotient.is_nan());
+    /// assert!(flags.invalid_operation);
+    /// ```
+    pub fn divide_integer_with_flags(&self, other: &Decimal128) -> (Decimal128, Flags) {
+        flags(self, other, (self.divide_integer(other), false), true)
+    }
+
+    /// Divides like `div_rem` and also returns the conditions that working
+    /// out either the quotient or the remainder raised. Dividing a non-zero
+    /// value by zero raises `division_by_zero` for the quotient and
+    /// `invalid_operation` for the remainder, which is NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let total: Decimal128 = "100.00".parse().unwrap();
+    /// let (_, remainder, flags) = total.div_rem_with_flags(&"0".parse().unwrap());
+    /// assert!(remainder.is_nan());
+    /// assert!(flags.division_by_zero && flags.invalid_operation);
+    /// ```
+    pub fn div_rem_with_flags(&self, other: &Decimal128) -> (Decimal128, Decimal128, Flags) {
+        let (quotient, remainder) = self.div_rem(other);
+        let (quotient, flags) = flags(self, other, (quotient, false), true);
+        let flags = Flags {
+            invalid_operation: flags.invalid_operation
+                || (remainder.nan && !self.nan && !other.nan),
+            ..flags
+        };
+        (quotient, remainder, flags)
+    }
+
+    /// Takes the reciprocal like `recip` and also returns the conditions
+    /// dividing 1 by the value raised.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let (reciprocal, flags) = Decimal128::from(0).recip_with_flags();
+    /// assert_eq!("Infinity", reciprocal.to_string());
+    /// assert!(flags.division_by_zero);
+    /// ```
+    pub fn recip_with_flags(&self) -> (Decimal128, Flags) {
+        let one = Decimal128::from_parts(false, 0, 1);
+        flags(&one, self, divide(&one, self), true)
+    }
+
+    /// Finds the midpoint like `midpoint` and also returns the conditions
+    /// halving the sum raised. It can't overflow, but halving the smallest
+    /// values underflows.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let tiny: Decimal128 = "1E-6176".parse().unwrap();
+    /// let (midpoint, flags) = tiny.midpoint_with_flags(&"0".parse().unwrap());
+    /// assert_eq!("0E-6176", midpoint.to_string());
+    /// assert!(flags.underflow && flags.inexact);
+    /// ```
+    pub fn midpoint_with_flags(&self, other: &Decimal128) -> (Decimal128, Flags) {
+        flags(self, other, halve_sum(self, other), false)
+    }
+
+    /// Rescales like `rescale`, and instead of failing returns NaN with
+    /// `invalid_operation` raised, like the spec's quantize operation.
+    /// Rounding digits off raises `inexact`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "12.345".parse().unwrap();
+    /// let (rescaled, flags) = price.rescale_with_flags(2, RoundingMode::HalfEven);
+    /// assert_eq!("12.34", rescaled.to_string());
+    /// assert!(flags.inexact);
+    /// let (rescaled, flags) = price.rescale_with_flags(40, RoundingMode::HalfEven);
+    /// assert!(rescaled.is_nan() && flags.invalid_operation);
+    /// ```
+    pub fn rescale_with_flags(&self, scale: i32, mode: RoundingMode) -> (Decimal128, Flags) {
+        match self.rescaled(scale, mode) {
+            Ok((rescaled, inexact)) => (
+                rescaled,
+                Flags {
+                    inexact,
+                    ..Flags::default()
+                },
+            ),
+            Err(_) => invalid(),
+        }
+    }
+
+    /// Rounds like `round_sf` and also returns the conditions rounding
+    /// raised. Instead of failing, a value that rounds up past the largest
+    /// Decimal128 overflows to Infinity, and an `n` of 0 gives NaN with
+    /// `invalid_operation` raised.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let max: Decimal128 = "9.999999999999999999999999999999999E+6144".parse().unwrap();
+    /// let (rounded, flags) = max.round_sf_with_flags(2, RoundingMode::HalfEven);
+    /// assert_eq!("Infinity", rounded.to_string());
+    /// assert!(flags.overflow && flags.inexact);
+    /// ```
+    pub fn round_sf_with_flags(&self, n: u32, mode: RoundingMode) -> (Decimal128, Flags) {
+        match self.rounded_sf(n, mode) {
+            Ok((rounded, inexact)) => {
+                let flags = Flags {
+                    inexact,
+                    underflow: inexact && subnormal(&rounded),
+                    ..Flags::default()
+                };
+                (rounded, flags)
+            }
+            Err(ConversionError::Overflow) => {
+                let flags = Flags {
+                    inexact: true,
+                    overflow: true,
+                    ..Flags::default()
+                };
+                (special(self.sign, INFINITY, 0), flags)
+            }
+            Err(_) => invalid(),
+        }
+    }
+
+    /// Adds `other`, returning the largest finite value of the right sign
+    /// instead of Infinity when the sum overflows. Infinite operands still
+    /// give Infinity (or NaN, for Infinity minus Infinity), and NaN operands
+    /// give NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let big: Decimal128 = "9E+6144".parse().unwrap();
+    /// assert_eq!(
+    ///     "9.999999999999999999999999999999999E+6144",
+    ///     big.saturating_add(&big).to_string()
+    /// );
+    /// ```
+    pub fn saturating_add(&self, other: &Decimal128) -> Decimal128 {
+        saturate(self.add_with_flags(other))
+    }
+
+    /// Subtracts `other`, saturating like `saturating_add`.
+    pub fn saturating_sub(&self, other: &Decimal128) -> Decimal128 {
+        saturate(self.sub_with_flags(other))
+    }
+
+    /// Multiplies by `other`, saturating like `saturating_add`. Infinity
+    /// times zero is NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let big: Decimal128 = "-1E+6000".parse().unwrap();
+    /// assert_eq!(
+    ///     "-9.999999999999999999999999999999999E+6144",
+    ///     big.saturating_mul(&big.with_sign(false)).to_string()
+    /// );
+    /// ```
+    pub fn saturating_mul(&self, other: &Decimal128) -> Decimal128 {
+        saturate(self.mul_with_flags(other))
+    }
+
+    /// Adds `other` and also returns whether the sum overflowed, in which
+    /// case it is Infinity. Like integer `overflowing_add`, this lets a loop
+    /// branch on overflow without checking the result for Infinity itself.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let big: Decimal128 = "9E+6144".parse().unwrap();
+    /// let (sum, overflow) = big.overflowing_add(&big);
+    /// assert_eq!("Infinity", sum.to_string());
+    /// assert!(overflow);
+    /// ```
+    pub fn overflowing_add(&self, other: &Decimal128) -> (Decimal128, bool) {
+        let (sum, flags) = self.add_with_flags(other);
+        (sum, flags.overflow)
+    }
+
+    /// Subtracts `other` and also returns whether the difference overflowed,
+    /// like `overflowing_add`.
+    pub fn overflowing_sub(&self, other: &Decimal128) -> (Decimal128, bool) {
+        let (difference, flags) = self.sub_with_flags(other);
+        (difference, flags.overflow)
+    }
+
+    /// Multiplies by `other` and also returns whether the product
+    /// overflowed, like `overflowing_add`.
+    pub fn overflowing_mul(&self, other: &Decimal128) -> (Decimal128, bool) {
+        let (product, flags) = self.mul_with_flags(other);
+        (product, flags.overflow)
+    }
+
+    /// Divides by `other`, rounding the quotient half-even to 34 digits, and
+    /// also returns whether it overflowed, like `overflowing_add`.
+    ///
+    /// An exact quotient keeps the exponent of `self` minus that of `other`
+    /// where it can, so `1.00 / 4` is `0.25` and `2.40 / 2` is `1.20`.
+    /// Dividing a non-zero value by zero gives Infinity, which isn't counted
+    /// as overflow; 0 divided by 0 and Infinity divided by Infinity are NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let total: Decimal128 = "10".parse().unwrap();
+    /// let (third, overflow) = total.overflowing_div(&"3".parse().unwrap());
+    /// assert_eq!("3.333333333333333333333333333333333", third.to_string());
+    /// assert!(!overflow);
+    /// ```
+    pub fn overflowing_div(&self, other: &Decimal128) -> (Decimal128, bool) {
+        let (quotient, flags) = self.div_with_flags(other);
+        (quotient, flags.overflow)
+    }
+
+    /// Adds `other`, returning an error instead of NaN when the sum has no
+    /// defined result, like Infinity minus Infinity. NaN operands aren't
+    /// an error and give NaN, and an overflowing sum is Infinity as usual.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let one: Decimal128 = "1".parse().unwrap();
+    /// assert_eq!("2", one.checked_add(&one).unwrap().to_string());
+    /// let infinity: Decimal128 = "Infinity".parse().unwrap();
+    /// assert_eq!(
+    ///     Err(ArithmeticError::InvalidOperation),
+    ///     infinity.checked_add(&infinity.with_sign(true))
+    /// );
+    /// ```
+    pub fn checked_add(&self, other: &Decimal128) -> Result<Decimal128, ArithmeticError> {
+        checked(self.add_with_flags(other))
+    }
+
+    /// Subtracts `other`, returning an error instead of NaN when the
+    /// difference has no defined result, like `checked_add`.
+    pub fn checked_sub(&self, other: &Decimal128) -> Result<Decimal128, ArithmeticError> {
+        checked(self.sub_with_flags(other))
+    }
+
+    /// Multiplies by `other`, returning an error instead of NaN when the
+    /// product has no defined result, like 0 times Infinity.
+    pub fn checked_mul(&self, other: &Decimal128) -> Result<Decimal128, ArithmeticError> {
+        checked(self.mul_with_flags(other))
+    }
+
+    /// Divides by `other`, returning `DivisionByZero` instead of Infinity
+    /// when a non-zero value is divided by zero, and `InvalidOperation`
+    /// instead of NaN for 0 divided by 0 and Infinity divided by Infinity.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let total: Decimal128 = "100.00".parse().unwrap();
+    /// let zero: Decimal128 = "0".parse().unwrap();
+    /// assert_eq!("25.00", total.checked_div(&"4".parse().unwrap()).unwrap().to_string());
+    /// assert_eq!(Err(ArithmeticError::DivisionByZero), total.checked_div(&zero));
+    /// assert_eq!(Err(ArithmeticError::InvalidOperation), zero.checked_div(&zero));
+    /// ```
+    pub fn checked_div(&self, other: &Decimal128) -> Result<Decimal128, ArithmeticError> {
+        checked(self.div_with_flags(other))
+    }
+
+    /// Returns the remainder `div_rem` gives, or `InvalidOperation` instead
+    /// of NaN when there is none: for a divisor of zero, an infinite
+    /// dividend, or a quotient with more than 34 digits.
+    pub fn checked_rem(&self, other: &Decimal128) -> Result<Decimal128, ArithmeticError> {
+        let (_, remainder, flags) = self.div_rem_with_flags(other);
+        checked((remainder, flags))
+    }
+
+    /// Returns the reciprocal, 1 divided by the value, rounded half-even to
+    /// 34 digits once. This is the quotient `overflowing_div` gives for a
+    /// dividend of 1.
+    ///
+    /// An exact reciprocal keeps the negated exponent where it can, so the
+    /// reciprocal of `0.001` is `1E+3`, and of `4` is `0.25`.
+    /// Zero gives Infinity of the same sign, Infinity gives zero, and NaN
+    /// gives NaN. The reciprocals of the smallest subnormal values overflow
+    /// to Infinity.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let rate: Decimal128 = "1.25".parse().unwrap();
+    /// assert_eq!("0.8", rate.recip().to_string());
+    /// let third: Decimal128 = "3".parse().unwrap();
+    /// assert_eq!("0.3333333333333333333333333333333333", third.recip().to_string());
+    /// ```
+    pub fn recip(&self) -> Decimal128 {
+        divide(&Decimal128::from_parts(false, 0, 1), self).0
+    }
+
+    /// Returns the value halfway between `self` and `other`, rounded
+    /// half-even to 34 digits. The sum is worked out exactly before it is
+    /// halved, so this can't overflow even for values near the largest
+    /// Decimal128, and only rounds once. When the sum is even, the result
+    /// has the smaller of the two exponents, so the midpoint of `1` and `3`
+    /// is `2`, not `2.0`.
+    ///
+    /// NaN operands give NaN, and so do Infinity and -Infinity; Infinity
+    /// with anything else gives that Infinity.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let max: Decimal128 = "9.999999999999999999999999999999999E+6144".parse().unwrap();
+    /// assert_eq!(max.to_string(), max.midpoint(&max).to_string());
+    /// let low: Decimal128 = "1.25".parse().unwrap();
+    /// assert_eq!("1.625", low.midpoint(&"2".parse().unwrap()).to_string());
+    /// ```
+    pub fn midpoint(&self, other: &Decimal128) -> Decimal128 {
+        halve_sum(self, other).0
+    }
+
+    /// Adds `other` and returns the sum rounded toward -Infinity and toward
+    /// Infinity, the tightest pair of Decimal128 values around the exact
+    /// sum. The two are equal when the sum is exact. The sum is only worked
+    /// out once, so tracking error bounds through a calculation costs less
+    /// than running it in both rounding directions.
+    ///
+    /// Sums too large for a finite Decimal128 are bounded by the largest
+    /// finite value on one side and Infinity on the other. A zero sum of
+    /// values with different signs is -0 rounded down and 0 rounded up, as
+    /// in IEEE 754. NaN and Infinity results are the same on both sides.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let big: Decimal128 = "1E+34".parse().unwrap();
+    /// let (low, high) = big.add_bounds(&"1".parse().unwrap());
+    /// assert_eq!("1.000000000000000000000000000000000E+34", low.to_string());
+    /// assert_eq!("1.000000000000000000000000000000001E+34", high.to_string());
+    /// ```
+    pub fn add_bounds(&self, other: &Decimal128) -> (Decimal128, Decimal128) {
+        sum_bounds(self, other)
+    }
+
+    /// Subtracts `other` and returns the difference rounded toward
+    /// -Infinity and toward Infinity, like `add_bounds`.
+    pub fn sub_bounds(&self, other: &Decimal128) -> (Decimal128, Decimal128) {
+        sum_bounds(self, &negate(other))
+    }
+
+    /// Multiplies by `other` and returns the product rounded toward
+    /// -Infinity and toward Infinity, like `add_bounds`.
+    pub fn mul_bounds(&self, other: &Decimal128) -> (Decimal128, Decimal128) {
+        bounds(exact_product(self, other))
+    }
+
+    /// Divides by `other` and returns the quotient rounded toward -Infinity
+    /// and toward Infinity, like `add_bounds`. Exact quotients keep the
+    /// exponent `overflowing_div` gives them, and division by zero gives
+    /// the same Infinity or NaN on both sides.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let one: Decimal128 = "1".parse().unwrap();
+    /// let (low, high) = one.div_bounds(&"-3".parse().unwrap());
+    /// assert_eq!("-0.3333333333333333333333333333333334", low.to_string());
+    /// assert_eq!("-0.3333333333333333333333333333333333", high.to_string());
+    /// ```
+    pub fn div_bounds(&self, other: &Decimal128) -> (Decimal128, Decimal128) {
+        bounds(quotient_digits(self, other))
+    }
+}
+
+/// Adds like `add_with_flags`, rounding the sum half-even to 34 digits.
+/// Sums too large for a finite Decimal128 are Infinity.
+/// ```
+/// use decimal128::*;
+///
+/// let price: Decimal128 = "1.50".parse().unwrap();
+/// let tax: Decimal128 = "0.12".parse().unwrap();
+/// assert_eq!("1.62", (&price + &tax).to_string());
+/// ```
+impl<'a> Add<&'a Decimal128> for &'a Decimal128 {
+    type Output = Decimal128;
+
+    fn add(self, other: &Decimal128) -> Decimal128 {
+        self.add_with_flags(other).0
+    }
+}
+
+/// Adds like the implementation for references does.
+impl Add for Decimal128 {
+    type Output = Decimal128;
+
+    fn add(self, other: Decimal128) -> Decimal128 {
+        &self + &other
+    }
+}
+
+/// Subtracts like `sub_with_flags`, rounding like `+` does.
+impl<'a> Sub<&'a Decimal128> for &'a Decimal128 {
+    type Output = Decimal128;
+
+    fn sub(self, other: &Decimal128) -> Decimal128 {
+        self.sub_with_flags(other).0
+    }
+}
+
+/// Subtracts like the implementation for references does.
+impl Sub for Decimal128 {
+    type Output = Decimal128;
+
+    fn sub(self, other: Decimal128) -> Decimal128 {
+        &self - &other
+    }
+}
+
+/// Multiplies like `mul_with_flags`, rounding like `+` does.
+impl<'a> Mul<&'a Decimal128> for &'a Decimal128 {
+    type Output = Decimal128;
+
+    fn mul(self, other: &Decimal128) -> Decimal128 {
+        self.mul_with_flags(other).0
+    }
+}
+
+/// Multiplies like the implementation for references does.
+impl Mul for Decimal128 {
+    type Output = Decimal128;
+
+    fn mul(self, other: Decimal128) -> Decimal128 {
+        &self * &other
+    }
+}
+
+/// Divides like `div_with_flags`, rounding like `+` does. Dividing by zero
+/// doesn't panic: it gives Infinity, or NaN for 0 divided by 0.
+impl<'a> Div<&'a Decimal128> for &'a Decimal128 {
+    type Output = Decimal128;
+
+    fn div(self, other: &Decimal128) -> Decimal128 {
+        self.div_with_flags(other).0
+    }
+}
+
+/// Divides like the implementation for references does.
+impl Div for Decimal128 {
+    type Output = Decimal128;
+
+    fn div(self, other: Decimal128) -> Decimal128 {
+        &self / &other
+    }
+}
+
+/// The remainder `div_rem` returns, which like the integer `%` has the
+/// sign of `self`. It's NaN when `div_rem` can't divide, including for a
+/// divisor of zero.
+/// ```
+/// use decimal128::*;
+///
+/// let total: Decimal128 = "100.00".parse().unwrap();
+/// let price: Decimal128 = "-7.50".parse().unwrap();
+/// assert_eq!("2.50", (&total % &price).to_string());
+/// ```
+impl<'a> Rem<&'a Decimal128> for &'a Decimal128 {
+    type Output = Decimal128;
+
+    fn rem(self, other: &Decimal128) -> Decimal128 {
+        self.div_rem(other).1
+    }
+}
+
+/// Takes the remainder like the implementation for references does.
+impl Rem for Decimal128 {
+    type Output = Decimal128;
+
+    fn rem(self, other: Decimal128) -> Decimal128 {
+        &self % &other
+    }
+}
+
+/// Flips the sign, which is exact. Like `f64`, this flips the sign of zeros
+/// and NaN too, so `-0` negates to `0`.
+impl Neg for &Decimal128 {
+    type Output = Decimal128;
+
+    fn neg(self) -> Decimal128 {
+        self.with_sign(!self.sign)
+    }
+}
+
+/// Flips the sign like the implementation for references does.
+impl Neg for Decimal128 {
+    type Output = Decimal128;
+
+    fn neg(self) -> Decimal128 {
+        -&self
+    }
+}
+
+// `a + b`, and whether rounding changed it.
+fn add(a: &Decimal128, b: &Decimal128) -> (Decimal128, bool) {
+    sum(&[a.clone(), b.clone()])
+}
+
+// `a / b`, and whether rounding changed it.
+fn divide(a: &Decimal128, b: &Decimal128) -> (Decimal128, bool) {
+    match quotient_digits(a, b) {
+        Ok((sign, digits, exponent)) => {
+            from_digits_rounded(sign, &digits, exponent, RoundingMode::HalfEven)
+        }
+        Err(special) => (special, false),
+    }
+}
+
+// `(a + b) / 2`, with the sum worked out exactly, and whether rounding
+// changed it.
+fn halve_sum(a: &Decimal128, b: &Decimal128) -> (Decimal128, bool) {
+    match exact_sum(&[a.clone(), b.clone()]) {
+        // half of the sum is five times it, divided by ten; when the sum is
+        // even, that leaves a zero to take back off
+        Ok((sign, digits, exponent)) => {
+            let mut digits = multiply_digits(&digits, &[5]);
+            let mut exponent = exponent - 1;
+            if digits.last() == Some(&0) {
+                digits.pop();
+                exponent += 1;
+            }
+            from_digits_rounded(sign, &digits, exponent, RoundingMode::HalfEven)
+        }
+        Err(special) => (special, false),
+    }
+}
+
+// The digits of `a / b` as a sign, digits and exponent, or the result when
+// it is NaN or Infinity. Past the 34 digits a Decimal128 holds, there is
+// only a digit to round with and a non-zero digit after it if the quotient
+// didn't come out even, which is all rounding needs.
+fn quotient_digits(a: &Decimal128, b: &Decimal128) -> Result<(bool, Vec<u8>, i64), Decimal128> {
+    if a.nan || b.nan {
+        return Err(quiet_nan(if a.nan { a } else { b }));
+    }
+    let sign = a.sign != b.sign;
+    if a.inf {
+        if b.inf {
+            return Err(special(false, NAN, 0));
+        }
+        return Err(special(sign, INFINITY, 0));
+    }
+    if b.inf {
+        // zero, with the exponent clamped to the smallest there is
+        return Ok((sign, Vec::new(), i64::MIN));
+    }
+
+    let (dividend, divisor) = (a.significand.to_num(), b.significand.to_num());
+    let exponent = a.exponent.to_adjusted() as i64 - b.exponent.to_adjusted() as i64;
+    if divisor == 0 {
+        if dividend == 0 {
+            return Err(special(false, NAN, 0));
+        }
+        return Err(special(sign, INFINITY, 0));
+    }
+
+    // long division, one digit at a time, until it comes out even or there
+    // are 35 digits: 34 to keep and one to round with
+    let (mut quotient, mut remainder, mut exponent) =
+        (dividend / divisor, dividend % divisor, exponent);
+    while remainder != 0 && quotient < pow10(MAX_DIGITS) {
+        remainder *= 10;
+        quotient = quotient * 10 + remainder / divisor;
+        remainder %= divisor;
+        exponent -= 1;
+    }
+    let mut digits = to_digits(quotient, 0);
+    if remainder != 0 {
+        // a non-zero digit past the rounding digit tells rounding that the
+        // rest isn't exactly zero
+        digits.push(1);
+        exponent -= 1;
+    }
+    Ok((sign, digits, exponent))
+}
+
+// An exact result, or the digits rounding needs, rounded toward -Infinity and
+// toward Infinity.
+fn bounds(exact: Result<(bool, Vec<u8>, i64), Decimal128>) -> (Decimal128, Decimal128) {
+    match exact {
+        Ok((sign, digits, exponent)) => (
+            from_digits_rounded(sign, &digits, exponent, RoundingMode::Floor).0,
+            from_digits_rounded(sign, &digits, exponent, RoundingMode::Ceiling).0,
+        ),
+        Err(special) => (special.clone(), special),
+    }
+}
+
+// The bounds of `a + b`. Rounding toward -Infinity makes a zero sum -0
+// unless both values are 0; an exact sum can't be a non-zero value that
+// rounds to zero, so a zero bound is always an exact zero.
+fn sum_bounds(a: &Decimal128, b: &Decimal128) -> (Decimal128, Decimal128) {
+    let (low, high) = bounds(exact_sum(&[a.clone(), b.clone()]));
+    if low.is_zero() && (a.sign || b.sign) {
+        return (low.with_sign(true), high);
+    }
+    (low, high)
+}
+
+// `-v`, leaving NaN alone so a NaN operand keeps its sign.
+fn negate(v: &Decimal128) -> Decimal128 {
+    if v.nan {
+        v.clone()
+    } else {
+        v.with_sign(!v.sign)
+    }
+}
+
+// The conditions raised by an operation on `a` and `b` that gave `result`.
+fn flags(
+    a: &Decimal128,
+    b: &Decimal128,
+    (result, inexact): (Decimal128, bool),
+    dividing: bool,
+) -> (Decimal128, Flags) {
+    let finite = |v: &Decimal128| !v.nan && !v.inf;
+    let division_by_zero = dividing
+        && finite(a)
+        && a.significand.to_num() != 0
+        && finite(b)
+        && b.significand.to_num() == 0;
+    let overflow = result.inf && !a.inf && !b.inf && !division_by_zero;
+    let flags = Flags {
+        inexact: inexact || overflow,
+        overflow,
+        underflow: inexact && subnormal(&result),
+        division_by_zero,
+        invalid_operation: result.nan && !a.nan && !b.nan,
+    };
+    (result, flags)
+}
+
+// Whether a finite value has an adjusted exponent below the smallest normal
+// one, -6143.
+pub(crate) fn subnormal(v: &Decimal128) -> bool {
+    let adjusted = v.exponent.to_adjusted() as i32 + v.digit_count() as i32 - 1;
+    !v.nan && !v.inf && adjusted < MIN_EXPONENT as i32 + MAX_DIGITS as i32 - 1
+}
+
+// The NaN an operation with no defined result gives, with
+// `invalid_operation` raised.
+fn invalid() -> (Decimal128, Flags) {
+    let flags = Flags {
+        invalid_operation: true,
+        ..Flags::default()
+    };
+    (special(false, NAN, 0), flags)
+}
+
+// The result, or the error for the first condition `checked_*` report.
+fn checked((result, flags): (Decimal128, Flags)) -> Result<Decimal128, ArithmeticError> {
+    if flags.invalid_operation {
+        Err(ArithmeticError::InvalidOperation)
+    } else if flags.division_by_zero {
+        Err(ArithmeticError::DivisionByZero)
+    } else {
+        Ok(result)
+    }
+}
+
+fn saturate((result, flags): (Decimal128, Flags)) -> Decimal128 {
+    if flags.overflow {
+        Decimal128::from_parts(result.sign, MAX_EXPONENT, pow10(MAX_DIGITS) - 1)
+    } else {
+        result
+    }
+}
@@ -0,0 +1,38 @@
+//! A `Decimal128` scalar for async-graphql.
+//!
+//! GraphQL's Float is a double, so values go out as strings, which keep every
+//! digit and the exponent. Inputs can be strings, or Int and Float literals
+//! for clients that send numbers.
+use crate::Decimal128;
+use ::async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+/// Parses strings the way `str::parse` does, and converts Int inputs
+/// exactly and Float inputs with `from_f64_shortest`. Outputs the string
+/// `to_string` returns.
+#[Scalar(name = "Decimal128")]
+impl ScalarType for Decimal128 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map_err(InputValueError::custom),
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Ok(Decimal128::from_minor_units(i as i128, 0)?)
+                } else if let Some(u) = n.as_u64() {
+                    Ok(Decimal128::from_minor_units(u as i128, 0)?)
+                } else {
+                    // a JSON number is always one of the three
+                    Ok(Decimal128::from_f64_shortest(n.as_f64().unwrap()))
+                }
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_) | Value::Number(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -0,0 +1,50 @@
+//! Conversions between Decimal128 coefficients and `num_bigint` integers, for
+//! exact rational math on the value `coefficient * 10^exponent`.
+use crate::{ConversionError, Decimal128, RoundingMode};
+use num_bigint::{BigInt, BigUint, Sign};
+
+impl Decimal128 {
+    /// Create a Decimal128 from a signed coefficient and an exponent, i.e. the
+    /// value `coefficient * 10^exponent`.
+    ///
+    /// Coefficients with more than 34 digits are rounded with `mode`. Returns
+    /// `ConversionError::Overflow` if the value is too large for a
+    /// Decimal128, and `ConversionError::Inexact` if it's too small to be
+    /// represented.
+    /// ```
+    /// use decimal128::*;
+    /// use num_bigint::BigInt;
+    ///
+    /// let coefficient = BigInt::from(-12345);
+    /// let dec128 = Decimal128::from_bigint(&coefficient, -2, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("-123.45", dec128.to_string());
+    /// ```
+    pub fn from_bigint(
+        coefficient: &BigInt,
+        exponent: i32,
+        mode: RoundingMode,
+    ) -> Result<Self, ConversionError> {
+        let digits = coefficient.magnitude().to_radix_be(10);
+        Decimal128::from_digits(coefficient.sign() == Sign::Minus, &digits, exponent, mode)
+    }
+
+    /// Returns the coefficient with this value's sign applied. Together with
+    /// the exponent this is the value `coefficient * 10^exponent`.
+    ///
+    /// Returns `ConversionError::NotFinite` for NaN and Infinity.
+    pub fn coefficient_bigint(&self) -> Result<BigInt, ConversionError> {
+        let magnitude = self.coefficient_biguint()?;
+        let sign = if self.sign { Sign::Minus } else { Sign::Plus };
+        Ok(BigInt::from_biguint(sign, magnitude))
+    }
+
+    /// Returns the unsigned coefficient.
+    ///
+    /// Returns `ConversionError::NotFinite` for NaN and Infinity.
+    pub fn coefficient_biguint(&self) -> Result<BigUint, ConversionError> {
+        if self.nan || self.inf {
+            return Err(ConversionError::NotFinite);
+        }
+        Ok(BigUint::from(self.significand.to_num()))
+    }
+}
@@ -0,0 +1,133 @@
+//! Inspects Decimal128 encodings: converts between hex bytes and decimal
+//! strings, and breaks encodings down into their fields. Built with the `cli`
+//! feature.
+//!
+//! ```text
+//! $ decimal128-cli -12.50
+//! decimal:     -12.50
+//! hex:         b03c 0000 0000 0000 0000 0000 0000 04e2
+//! bson hex:    e204 0000 0000 0000 0000 0000 0000 3cb0
+//! kind:        finite
+//! sign:        -
+//! exponent:    -2
+//! coefficient: 1250
+//! canonical:   yes
+//! ```
+use decimal128::Decimal128;
+use std::env;
+use std::process;
+
+const USAGE: &str = "\
+Usage: decimal128-cli [--le] <VALUE>...
+
+Prints how each VALUE is encoded as a Decimal128. A VALUE is a decimal
+string, like -12.50, 1E+400 or NaN, or the 16 bytes of an encoding as 0x and
+32 hex digits, which may be split up by spaces or underscores.
+
+Options:
+  --le        Read hex bytes least significant first, the way BSON stores
+              them, instead of most significant first
+  -h, --help  Print this message";
+
+fn main() {
+    let mut little_endian = false;
+    let mut values = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--le" => little_endian = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => values.push(arg),
+        }
+    }
+    if values.is_empty() {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+
+    let mut failed = false;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match read(value, little_endian) {
+            Ok(dec128) => print(&dec128),
+            Err(e) => {
+                eprintln!("{}: {}", value, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+// Read a value as hex bytes if it starts with `0x`, and as a decimal string
+// otherwise.
+fn read(value: &str, little_endian: bool) -> Result<Decimal128, String> {
+    let hex = match value.strip_prefix("0x") {
+        Some(hex) => hex,
+        None => return value.parse().map_err(|e| format!("{}", e)),
+    };
+    let digits: String = hex
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect();
+    if digits.len() != 32 {
+        return Err(format!("expected 32 hex digits, found {}", digits.len()));
+    }
+    let bits = u128::from_str_radix(&digits, 16).map_err(|e| format!("{}", e))?;
+    let mut bytes = bits.to_be_bytes();
+    if little_endian {
+        bytes.reverse();
+    }
+    Ok(Decimal128::from_raw_bytes(bytes))
+}
+
+fn print(dec128: &Decimal128) {
+    let bytes = dec128.to_raw_bytes();
+    let mut bson_bytes = bytes;
+    bson_bytes.reverse();
+    let (coefficient, exponent, sign) = dec128.integer_decode();
+
+    // the canonical string keeps the sign and kind of NaNs, which
+    // `to_string` leaves out
+    let canonical = dec128.to_canonical_string();
+    let is_canonical = !canonical.starts_with("0x");
+    if is_canonical {
+        println!("decimal:     {}", canonical);
+    } else {
+        println!("decimal:     {}", dec128);
+    }
+    println!("hex:         {}", dec128.to_grouped_hex(2));
+    println!(
+        "bson hex:    {}",
+        Decimal128::from_raw_bytes(bson_bytes).to_grouped_hex(2)
+    );
+    let kind = kind(bytes[0]);
+    println!("kind:        {}", kind);
+    println!("sign:        {}", if sign < 0 { "-" } else { "+" });
+    match kind {
+        "finite" => {
+            println!("exponent:    {}", exponent);
+            println!("coefficient: {}", coefficient);
+        }
+        "infinite" => {}
+        _ => println!("payload:     {}", coefficient),
+    }
+    println!("canonical:   {}", if is_canonical { "yes" } else { "no" });
+}
+
+// The kind of value the combination field, the five bits after the sign,
+// encodes, with the bit after them that makes NaN signaling.
+fn kind(first_byte: u8) -> &'static str {
+    match (first_byte >> 1) & 0x3f {
+        0x3f => "signaling NaN",
+        0x3e => "quiet NaN",
+        0x3c | 0x3d => "infinite",
+        _ => "finite",
+    }
+}
@@ -0,0 +1,23 @@
+//! `borsh` serialization.
+//!
+//! Borsh has no decimal type, so Decimal128 is written the way borsh writes
+//! a u128 holding its encoding: 16 bytes, least significant first. That's
+//! the reverse of `to_raw_bytes`, and the same byte order BSON uses.
+use crate::Decimal128;
+use ::borsh::io::{Read, Result, Write};
+use ::borsh::{BorshDeserialize, BorshSerialize};
+
+impl BorshSerialize for Decimal128 {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        u128::from_be_bytes(self.to_raw_bytes()).serialize(writer)
+    }
+}
+
+/// Every 16 bytes are a Decimal128 encoding, so this only fails when the
+/// reader runs out of bytes.
+impl BorshDeserialize for Decimal128 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bits = u128::deserialize_reader(reader)?;
+        Ok(Decimal128::from_raw_bytes(bits.to_be_bytes()))
+    }
+}
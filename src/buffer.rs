@@ -0,0 +1,74 @@
+//! Formatting Decimal128 into a reusable buffer on the stack, like
+//! `ryu::Buffer` does for floats, or into a buffer of the caller's.
+use crate::{BufferTooSmall, Decimal128, StrBuf, POSITIONAL_EXPONENTS};
+
+/// A buffer that Decimal128 values are formatted into without allocating,
+/// for no_std targets and for hot loops like logging, where `to_string`
+/// would allocate a String for every value.
+///
+/// It's big enough for the longest string a Decimal128 formats to, and can
+/// be reused: each call to `format` overwrites the last string.
+/// ```
+/// use decimal128::*;
+///
+/// let mut buffer = Buffer::new();
+/// let price: Decimal128 = "-12.50".parse().unwrap();
+/// assert_eq!("-12.50", buffer.format(&price));
+/// let big: Decimal128 = "1E+400".parse().unwrap();
+/// assert_eq!("1E+400", buffer.format(&big));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Buffer {
+    buf: StrBuf,
+}
+
+impl Buffer {
+    /// Returns an empty buffer. This is cheap, so there's no need to keep
+    /// buffers around between uses unless that's convenient.
+    pub fn new() -> Self {
+        Buffer { buf: StrBuf::new() }
+    }
+
+    /// Formats `d` into the buffer and returns the string, which is the
+    /// same one `to_string` and `Display` write.
+    pub fn format(&mut self, d: &Decimal128) -> &str {
+        self.buf = StrBuf::new();
+        d.write_signed_string(&mut self.buf, false, Some(POSITIONAL_EXPONENTS))
+            .expect("Decimal128 strings fit in Buffer");
+        self.buf.as_str()
+    }
+}
+
+impl Default for Buffer {
+    fn default() -> Self {
+        Buffer::new()
+    }
+}
+
+impl Decimal128 {
+    /// Writes the string `to_string` returns into the start of `buf`, and
+    /// returns the part of `buf` written to, so serializers can format
+    /// straight into their output without allocating.
+    ///
+    /// Fails with `BufferTooSmall`, leaving `buf` unchanged, if the string
+    /// doesn't fit. A buffer of `MAX_STRING_LEN` bytes is always big enough.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "-12.50".parse().unwrap();
+    /// let mut buf = [0u8; MAX_STRING_LEN];
+    /// assert_eq!("-12.50", price.format_into(&mut buf).unwrap());
+    /// assert_eq!(6, price.format_into(&mut buf[..4]).unwrap_err().needed());
+    /// ```
+    pub fn format_into<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, BufferTooSmall> {
+        let mut buffer = Buffer::new();
+        let string = buffer.format(self);
+        let needed = string.len();
+        if needed > buf.len() {
+            return Err(BufferTooSmall { needed });
+        }
+        buf[..needed].copy_from_slice(string.as_bytes());
+        // the bytes were copied from a str
+        Ok(core::str::from_utf8(&buf[..needed]).unwrap())
+    }
+}
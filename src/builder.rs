@@ -0,0 +1,131 @@
+//! Building finite Decimal128 values from their sign, coefficient and
+//! exponent, e.g. from fields decoded out of a wire protocol.
+use crate::{digit_count, BuildError, Decimal128, MAX_DIGITS, MAX_EXPONENT, MIN_EXPONENT};
+
+/// Builds a finite Decimal128 with value `(-1)^sign * coefficient *
+/// 10^exponent`. Nothing is rounded or normalized: `build` fails unless the
+/// coefficient and exponent can be encoded as they are, so the result has
+/// exactly the requested quantum.
+///
+/// Every field starts out as zero (and the sign as positive).
+/// ```
+/// use decimal128::*;
+///
+/// let price = Decimal128Builder::new()
+///     .sign(true)
+///     .coefficient(1250)
+///     .exponent(-2)
+///     .build()
+///     .unwrap();
+/// assert_eq!("-12.50", price.to_string());
+///
+/// let err = Decimal128Builder::new().exponent(6112).build().unwrap_err();
+/// assert_eq!(BuildError::ExponentOutOfRange { exponent: 6112 }, err);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Decimal128Builder {
+    sign: bool,
+    coefficient: u128,
+    exponent: i32,
+}
+
+impl Decimal128Builder {
+    pub fn new() -> Self {
+        Decimal128Builder::default()
+    }
+
+    /// Sets the sign; `true` is negative.
+    pub fn sign(mut self, sign: bool) -> Self {
+        self.sign = sign;
+        self
+    }
+
+    /// Sets the coefficient, which can have at most 34 digits.
+    pub fn coefficient(mut self, coefficient: u128) -> Self {
+        self.coefficient = coefficient;
+        self
+    }
+
+    /// Sets the unbiased exponent, which has to be within -6176..=6111.
+    pub fn exponent(mut self, exponent: i32) -> Self {
+        self.exponent = exponent;
+        self
+    }
+
+    /// Encodes the Decimal128, checking the coefficient first and then the
+    /// exponent.
+    pub fn build(&self) -> Result<Decimal128, BuildError> {
+        let digits = digit_count(self.coefficient);
+        if digits > MAX_DIGITS {
+            return Err(BuildError::TooManyDigits { digits });
+        }
+        if self.exponent < MIN_EXPONENT as i32 || self.exponent > MAX_EXPONENT as i32 {
+            return Err(BuildError::ExponentOutOfRange {
+                exponent: self.exponent,
+            });
+        }
+        Ok(Decimal128::from_parts(
+            self.sign,
+            self.exponent as i16,
+            self.coefficient,
+        ))
+    }
+}
+
+impl Decimal128 {
+    /// A copy of the value with the sign replaced; `true` is negative. This
+    /// works for NaN and Infinity too.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let value: Decimal128 = "1.50".parse().unwrap();
+    /// assert_eq!("-1.50", value.with_sign(true).to_string());
+    /// ```
+    pub fn with_sign(&self, sign: bool) -> Decimal128 {
+        let mut bytes = self.to_raw_bytes();
+        bytes[0] = (bytes[0] & 0x7f) | ((sign as u8) << 7);
+        Decimal128::from_raw_bytes(bytes)
+    }
+
+    /// A copy of the value with the exponent replaced and the coefficient
+    /// left alone, so `1.50` with exponent -3 becomes `0.150`. Fails like
+    /// `Decimal128Builder::build` if the exponent is out of range. NaN and
+    /// Infinity have no exponent and are returned unchanged.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let value: Decimal128 = "1.50".parse().unwrap();
+    /// assert_eq!("1.50E+3", value.with_exponent(1).unwrap().to_string());
+    /// assert!(value.with_exponent(6112).is_err());
+    /// ```
+    pub fn with_exponent(&self, exponent: i32) -> Result<Decimal128, BuildError> {
+        if self.nan || self.inf {
+            return Ok(self.clone());
+        }
+        self.to_builder().exponent(exponent).build()
+    }
+
+    /// A copy of the value with the coefficient replaced and the exponent
+    /// left alone. Fails like `Decimal128Builder::build` if the coefficient
+    /// has more than 34 digits. NaN and Infinity have no coefficient and are
+    /// returned unchanged.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let value: Decimal128 = "-1.50".parse().unwrap();
+    /// assert_eq!("-0.99", value.with_coefficient(99).unwrap().to_string());
+    /// ```
+    pub fn with_coefficient(&self, coefficient: u128) -> Result<Decimal128, BuildError> {
+        if self.nan || self.inf {
+            return Ok(self.clone());
+        }
+        self.to_builder().coefficient(coefficient).build()
+    }
+
+    fn to_builder(&self) -> Decimal128Builder {
+        Decimal128Builder::new()
+            .sign(self.sign)
+            .coefficient(self.significand.to_num())
+            .exponent(self.exponent.to_adjusted() as i32)
+    }
+}
@@ -0,0 +1,105 @@
+//! A textual form of Decimal128 that maps one-to-one onto its encodings, for
+//! replication logs and checksums that need text to reproduce the exact
+//! bytes it was written from.
+//!
+//! Every encoding that `to_string` and `parse` round-trip, which is every
+//! canonical finite value, keeps its numeric string. NaNs are written with
+//! their sign, whether they signal and their payload, as in `-sNaN12`. The
+//! encodings left over, non-canonical coefficients, NaN payloads of 34 digits
+//! or more and infinities or NaNs with stray bits set, are written as `0x`
+//! and the 32 hex digits of their bytes.
+use crate::{Decimal128, ParseDecimal128Error};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+// Marks the hex form, which is followed by exactly this many digits.
+const HEX_PREFIX: &str = "0x";
+const HEX_DIGITS: usize = 32;
+
+impl Decimal128 {
+    /// Returns the canonical string of this encoding. Parsing it with
+    /// `from_canonical_string` always gives back the same 16 bytes, and no
+    /// two encodings have the same canonical string, even when they have the
+    /// same value.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "12.50".parse().unwrap();
+    /// assert_eq!("12.50", price.to_canonical_string());
+    /// let nan: Decimal128 = "-sNaN12".parse().unwrap();
+    /// assert_eq!("-sNaN12", nan.to_canonical_string());
+    ///
+    /// let mut bytes = [0xffu8; 16];
+    /// bytes[0] = 0x6f;
+    /// let non_canonical = Decimal128::from_raw_bytes(bytes);
+    /// assert_eq!("0E+2015", non_canonical.to_string());
+    /// assert_eq!("0x6fffffffffffffffffffffffffffffff", non_canonical.to_canonical_string());
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        let text = if self.nan {
+            let sign = if self.sign { "-" } else { "" };
+            let signaling = if self.bytes[0] & 0x02 != 0 { "s" } else { "" };
+            let payload = self.significand.to_num();
+            if payload == 0 {
+                format!("{}{}NaN", sign, signaling)
+            } else {
+                format!("{}{}NaN{}", sign, signaling, payload)
+            }
+        } else {
+            self.to_string()
+        };
+        match text.parse::<Decimal128>() {
+            Ok(parsed) if parsed.bytes == self.bytes => text,
+            _ => format!("{:#034x}", self),
+        }
+    }
+
+    /// Parses a string written by `to_canonical_string`, giving back the
+    /// exact encoding it was written from.
+    ///
+    /// Strings that parse to a value but aren't its canonical string, like
+    /// `+1`, `1e3` or `0x` followed by an encoding that has a numeric
+    /// string, fail with `ParseDecimal128Error::NotCanonical`, so that
+    /// equal strings always mean equal bytes.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_canonical_string("-1.50E+400").unwrap();
+    /// assert_eq!("-1.50E+400", dec128.to_canonical_string());
+    /// assert_eq!(
+    ///     Err(ParseDecimal128Error::NotCanonical),
+    ///     Decimal128::from_canonical_string("1.50e400")
+    /// );
+    /// ```
+    pub fn from_canonical_string(s: &str) -> Result<Decimal128, ParseDecimal128Error> {
+        let value = match s.strip_prefix(HEX_PREFIX) {
+            Some(hex) => from_hex(hex)?,
+            None => s.parse()?,
+        };
+        if value.to_canonical_string() != s {
+            return Err(ParseDecimal128Error::NotCanonical);
+        }
+        Ok(value)
+    }
+}
+
+// Decode the 32 lowercase hex digits after the `0x` prefix.
+fn from_hex(hex: &str) -> Result<Decimal128, ParseDecimal128Error> {
+    if hex.len() != HEX_DIGITS {
+        return Err(ParseDecimal128Error::NotCanonical);
+    }
+    let mut bits: u128 = 0;
+    for (i, b) in hex.bytes().enumerate() {
+        let digit = match b {
+            b'0'..=b'9' => b - b'0',
+            b'a'..=b'f' => b - b'a' + 10,
+            _ => {
+                return Err(ParseDecimal128Error::InvalidCharacter {
+                    index: HEX_PREFIX.len() + i,
+                })
+            }
+        };
+        bits = bits << 4 | digit as u128;
+    }
+    Ok(Decimal128::from_raw_bytes(bits.to_be_bytes()))
+}
@@ -0,0 +1,153 @@
+//! The decimal arithmetic specification's operations on the digits of the
+//! coefficient: shifting and rotating them, and treating them as bits.
+//!
+//! These work on the full 34-digit coefficient, with zeros in front of the
+//! significant digits, and never change the sign or the exponent.
+use crate::{pow10, ConversionError, Decimal128, MAX_DIGITS};
+
+impl Decimal128 {
+    /// The `shift` operation: moves the coefficient's digits `n` places to
+    /// the left (if `n` is positive) or right (if negative). Digits moved
+    /// past the 34th place or below the first are dropped, and zeros fill
+    /// in behind. This multiplies or divides the coefficient by a power of
+    /// ten without any rounding.
+    ///
+    /// `n` has to be within -34..=34, or this fails with
+    /// `ConversionError::Invalid`. NaN and Infinity are returned unchanged.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let value: Decimal128 = "12.34".parse().unwrap();
+    /// assert_eq!("1234.00", value.shift(2).unwrap().to_string());
+    /// assert_eq!("0.12", value.shift(-2).unwrap().to_string());
+    /// ```
+    pub fn shift(&self, n: i32) -> Result<Decimal128, ConversionError> {
+        let places = places(n)?;
+        if self.nan || self.inf {
+            return Ok(self.clone());
+        }
+        let coefficient = self.significand.to_num();
+        let coefficient = if n >= 0 {
+            coefficient % pow10(MAX_DIGITS - places) * pow10(places)
+        } else {
+            coefficient / pow10(places)
+        };
+        Ok(self.with_digits(coefficient))
+    }
+
+    /// The `rotate` operation: like `shift`, but the digits moved past one
+    /// end of the 34-digit coefficient come back in at the other end, so no
+    /// digits are lost.
+    ///
+    /// `n` has to be within -34..=34, or this fails with
+    /// `ConversionError::Invalid`. NaN and Infinity are returned unchanged.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let value: Decimal128 = "1234567890123456789012345678901234".parse().unwrap();
+    /// assert_eq!(
+    ///     "3456789012345678901234567890123412",
+    ///     value.rotate(2).unwrap().to_string()
+    /// );
+    /// ```
+    pub fn rotate(&self, n: i32) -> Result<Decimal128, ConversionError> {
+        let places = places(n)?;
+        if self.nan || self.inf {
+            return Ok(self.clone());
+        }
+        // rotating right is rotating left by the rest of the digits
+        let left = if n >= 0 { places } else { MAX_DIGITS - places };
+        let divisor = pow10(MAX_DIGITS - left);
+        let coefficient = self.significand.to_num();
+        let coefficient = coefficient % divisor * pow10(left) + coefficient / divisor;
+        Ok(self.with_digits(coefficient))
+    }
+
+    /// The `and` operation: the digit-wise AND of two logical operands. A
+    /// logical operand is a positive finite value with exponent 0 and only
+    /// 0s and 1s in its coefficient, e.g. `1101`; anything else fails with
+    /// `ConversionError::Invalid`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let a: Decimal128 = "1100".parse().unwrap();
+    /// let b: Decimal128 = "1010".parse().unwrap();
+    /// assert_eq!("1000", a.logical_and(&b).unwrap().to_string());
+    /// assert_eq!("1110", a.logical_or(&b).unwrap().to_string());
+    /// assert_eq!("110", a.logical_xor(&b).unwrap().to_string());
+    /// ```
+    pub fn logical_and(&self, other: &Decimal128) -> Result<Decimal128, ConversionError> {
+        Ok(from_bits(to_bits(self)? & to_bits(other)?))
+    }
+
+    /// The `or` operation: the digit-wise OR of two logical operands, as
+    /// described for `logical_and`.
+    pub fn logical_or(&self, other: &Decimal128) -> Result<Decimal128, ConversionError> {
+        Ok(from_bits(to_bits(self)? | to_bits(other)?))
+    }
+
+    /// The `xor` operation: the digit-wise exclusive OR of two logical
+    /// operands, as described for `logical_and`.
+    pub fn logical_xor(&self, other: &Decimal128) -> Result<Decimal128, ConversionError> {
+        Ok(from_bits(to_bits(self)? ^ to_bits(other)?))
+    }
+
+    /// The `invert` operation: flips every one of the 34 digits of a logical
+    /// operand, as described for `logical_and`, so `0` becomes 34 ones.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let value: Decimal128 = "1111111111111111111111111111111110".parse().unwrap();
+    /// assert_eq!("1", value.logical_invert().unwrap().to_string());
+    /// ```
+    pub fn logical_invert(&self) -> Result<Decimal128, ConversionError> {
+        Ok(from_bits(!to_bits(self)? & ((1 << MAX_DIGITS) - 1)))
+    }
+
+    // Replace the coefficient of a finite value, keeping its sign and
+    // exponent.
+    fn with_digits(&self, coefficient: u128) -> Decimal128 {
+        Decimal128::from_parts(self.sign, self.exponent.to_adjusted(), coefficient)
+    }
+}
+
+// The number of places to shift or rotate by, which the spec limits to the
+// number of digits in a coefficient.
+fn places(n: i32) -> Result<u32, ConversionError> {
+    if n.unsigned_abs() > MAX_DIGITS {
+        return Err(ConversionError::Invalid);
+    }
+    Ok(n.unsigned_abs())
+}
+
+// The digits of a logical operand as bits, least significant digit in the
+// lowest bit.
+fn to_bits(value: &Decimal128) -> Result<u64, ConversionError> {
+    if value.nan || value.inf || value.sign || value.exponent.to_adjusted() != 0 {
+        return Err(ConversionError::Invalid);
+    }
+    let mut coefficient = value.significand.to_num();
+    let mut bits = 0;
+    let mut bit = 1;
+    while coefficient > 0 {
+        match coefficient % 10 {
+            0 => {}
+            1 => bits |= bit,
+            _ => return Err(ConversionError::Invalid),
+        }
+        coefficient /= 10;
+        bit <<= 1;
+    }
+    Ok(bits)
+}
+
+fn from_bits(mut bits: u64) -> Decimal128 {
+    let mut coefficient = 0;
+    let mut digit = 1;
+    while bits > 0 {
+        coefficient += (bits & 1) as u128 * digit;
+        bits >>= 1;
+        digit *= 10;
+    }
+    Decimal128::from_parts(false, 0, coefficient)
+}
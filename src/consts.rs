@@ -0,0 +1,26 @@
+//! Mathematical constants, correctly rounded to 34 significant digits, the
+//! most a Decimal128 holds.
+//!
+//! ```
+//! use decimal128::consts;
+//!
+//! assert_eq!("3.141592653589793238462643383279503", consts::PI.to_string());
+//! ```
+use crate::Decimal128;
+
+/// Archimedes' constant, π.
+pub const PI: Decimal128 = Decimal128::from_parts(false, -33, 3141592653589793238462643383279503);
+
+/// Euler's number, e.
+pub const E: Decimal128 = Decimal128::from_parts(false, -33, 2718281828459045235360287471352662);
+
+/// ln(10)
+pub const LN_10: Decimal128 =
+    Decimal128::from_parts(false, -33, 2302585092994045684017991454684364);
+
+/// ln(2)
+pub const LN_2: Decimal128 = Decimal128::from_parts(false, -34, 6931471805599453094172321214581766);
+
+/// √2
+pub const SQRT_2: Decimal128 =
+    Decimal128::from_parts(false, -33, 1414213562373095048801688724209698);
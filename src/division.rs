@@ -0,0 +1,122 @@
+//! Integer division: the decimal arithmetic specification's divideInteger
+//! and remainder operations.
+//!
+//! Both are exact, so unlike the other arithmetic they never round. A
+//! quotient that needs more than 34 digits can't be represented at all, and
+//! the spec makes it NaN ("division impossible").
+use crate::{pow10, quiet_nan, special, Decimal128, INFINITY, MAX_DIGITS, NAN};
+
+impl Decimal128 {
+    /// The `divideInteger` operation: divides by `other` and drops the
+    /// fractional part of the quotient, which has exponent 0.
+    ///
+    /// The result is NaN if either value is NaN, for Infinity divided by
+    /// Infinity, for 0 divided by 0, and when the quotient has more than 34
+    /// digits. Any other value divided by 0, and Infinity divided by a
+    /// finite value, is Infinity; a finite value divided by Infinity is 0.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let total: Decimal128 = "100.00".parse().unwrap();
+    /// let price: Decimal128 = "7.50".parse().unwrap();
+    /// assert_eq!("13", total.divide_integer(&price).to_string());
+    /// ```
+    pub fn divide_integer(&self, other: &Decimal128) -> Decimal128 {
+        self.div_rem(other).0
+    }
+
+    /// Returns both the integer quotient, exactly as `divide_integer`
+    /// computes it, and the remainder `self - other * quotient`. The
+    /// remainder has the sign of `self` and the smaller of the two
+    /// exponents, so it's exact too.
+    ///
+    /// The remainder is NaN whenever the quotient is, and also for
+    /// Infinity divided by anything and for anything divided by 0. A finite
+    /// value divided by Infinity leaves the value itself as the remainder.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let total: Decimal128 = "100.00".parse().unwrap();
+    /// let price: Decimal128 = "7.50".parse().unwrap();
+    /// let (count, change) = total.div_rem(&price);
+    /// assert_eq!("13", count.to_string());
+    /// assert_eq!("2.50", change.to_string());
+    /// ```
+    pub fn div_rem(&self, other: &Decimal128) -> (Decimal128, Decimal128) {
+        let nan = || special(false, NAN, 0);
+        if self.nan || other.nan {
+            let nan = quiet_nan(if self.nan { self } else { other });
+            return (nan.clone(), nan);
+        }
+        let sign = self.sign != other.sign;
+        if self.inf {
+            if other.inf {
+                return (nan(), nan());
+            }
+            return (special(sign, INFINITY, 0), nan());
+        }
+        if other.inf {
+            return (Decimal128::from_parts(sign, 0, 0), self.clone());
+        }
+
+        let (dividend, divisor) = (self.significand.to_num(), other.significand.to_num());
+        if divisor == 0 {
+            if dividend == 0 {
+                return (nan(), nan());
+            }
+            return (special(sign, INFINITY, 0), nan());
+        }
+        let (exponent, other_exponent) =
+            (self.exponent.to_adjusted(), other.exponent.to_adjusted());
+        let (quotient, remainder, remainder_exponent) = if exponent >= other_exponent {
+            // both in units of 10^other_exponent: the dividend is followed
+            // by `zeros` zeros, so divide it out one digit at a time
+            let zeros = (exponent - other_exponent) as u32;
+            match long_divide(dividend, zeros, divisor) {
+                Some((quotient, remainder)) => (quotient, remainder, other_exponent),
+                None => return (nan(), nan()),
+            }
+        } else {
+            // both in units of 10^exponent: the divisor is followed by
+            // `zeros` zeros, and if it is longer than 34 digits it's larger
+            // than the dividend
+            let zeros = (other_exponent - exponent) as u32;
+            let quotient = if zeros >= MAX_DIGITS {
+                0
+            } else {
+                dividend / pow10(zeros) / divisor
+            };
+            let remainder = if quotient == 0 {
+                dividend
+            } else {
+                dividend - quotient * divisor * pow10(zeros)
+            };
+            (quotient, remainder, exponent)
+        };
+        (
+            Decimal128::from_parts(sign, 0, quotient),
+            Decimal128::from_parts(self.sign, remainder_exponent, remainder),
+        )
+    }
+}
+
+// Divide `dividend` followed by `zeros` zeros by `divisor`, returning the
+// quotient and remainder, or None if the quotient has more than 34 digits.
+fn long_divide(dividend: u128, zeros: u32, divisor: u128) -> Option<(u128, u128)> {
+    let max_quotient = pow10(MAX_DIGITS);
+    let mut quotient = dividend / divisor;
+    let mut remainder = dividend % divisor;
+    // the remainder is below 10^34, so appending a digit can't overflow
+    for _ in 0..zeros {
+        if quotient == 0 && remainder == 0 {
+            break;
+        }
+        remainder *= 10;
+        quotient = quotient * 10 + remainder / divisor;
+        remainder %= divisor;
+        if quotient >= max_quotient {
+            return None;
+        }
+    }
+    Some((quotient, remainder))
+}
@@ -0,0 +1,166 @@
+//! The errors returned by conversions, parsing, building, formatting and
+//! checked arithmetic.
+//!
+//! Every error is `Display` and `core::error::Error` (which is
+//! `std::error::Error` on std), so they box into `Box<dyn Error>`, work with
+//! `?` in functions returning `anyhow::Result`, and can be wrapped by
+//! `#[from]` in thiserror enums. The enums are `#[non_exhaustive]`, so
+//! matches on them need a wildcard arm, and new kinds of failure can be
+//! added without a breaking release.
+//!
+//! They're all re-exported from the crate root too.
+use crate::{MAX_EXPONENT, MIN_EXPONENT};
+use core::fmt;
+
+/// Error returned when converting between Decimal128 and another numeric
+/// representation would lose information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// The value is NaN or Infinity and has no representation on the other
+    /// side of the conversion.
+    NotFinite,
+    /// The value does not fit in the target representation.
+    Overflow,
+    /// The value can only be represented by rounding it.
+    Inexact,
+    /// The input is malformed, or the requested target is not valid.
+    Invalid,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::NotFinite => write!(fmt, "value is not finite"),
+            ConversionError::Overflow => write!(fmt, "value is out of range"),
+            ConversionError::Inexact => write!(fmt, "value cannot be represented exactly"),
+            ConversionError::Invalid => write!(fmt, "invalid input"),
+        }
+    }
+}
+
+impl core::error::Error for ConversionError {}
+
+/// Error returned by the `checked_*` operations when they raise a condition
+/// that would otherwise quietly give NaN or Infinity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArithmeticError {
+    /// The operation has no defined result, like Infinity minus Infinity,
+    /// 0 divided by 0 or a remainder after dividing by 0.
+    InvalidOperation,
+    /// A non-zero finite value was divided by zero.
+    DivisionByZero,
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArithmeticError::InvalidOperation => write!(fmt, "invalid operation"),
+            ArithmeticError::DivisionByZero => write!(fmt, "division by zero"),
+        }
+    }
+}
+
+impl core::error::Error for ArithmeticError {}
+
+/// Error returned by `Decimal128Builder::build` when the fields don't make up
+/// a valid Decimal128.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// The coefficient has `digits` digits, more than the 34 a Decimal128
+    /// can hold.
+    TooManyDigits { digits: u32 },
+    /// The exponent is outside -6176..=6111.
+    ExponentOutOfRange { exponent: i32 },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::TooManyDigits { digits } => {
+                write!(fmt, "coefficient has {} digits, more than 34", digits)
+            }
+            BuildError::ExponentOutOfRange { exponent } => write!(
+                fmt,
+                "exponent {} is outside {}..={}",
+                exponent, MIN_EXPONENT, MAX_EXPONENT
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
+
+/// Error returned by `Decimal128::format_into` when the string doesn't fit
+/// in the buffer it was given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    pub(crate) needed: usize,
+}
+
+impl BufferTooSmall {
+    /// Returns the length the buffer needed to be, which is never more than
+    /// `MAX_STRING_LEN`.
+    pub fn needed(&self) -> usize {
+        self.needed
+    }
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "buffer is too small, {} bytes are needed", self.needed)
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+/// Error returned when parsing a Decimal128 from a string fails.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseDecimal128Error {
+    /// The string has no digits.
+    Empty,
+    /// The byte at `index` can't appear where it does.
+    InvalidCharacter { index: usize },
+    /// The exponent is outside the range a Decimal128 can hold, even after
+    /// moving zeros between the coefficient and the exponent.
+    ExponentOverflow,
+    /// The number has more than 34 significant digits.
+    TooManyDigits,
+    /// The exponent marker is not followed by any digits.
+    MalformedExponent,
+    /// The string is a valid number, but not the canonical string of its
+    /// encoding, which `Decimal128::from_canonical_string` requires.
+    NotCanonical,
+    /// The string was to be read in a radix other than 10, which
+    /// `num_traits::Num::from_str_radix` allows but Decimal128 strings
+    /// never use.
+    InvalidRadix,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ParseDecimal128Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseDecimal128Error::Empty => write!(fmt, "cannot parse decimal from empty string"),
+            ParseDecimal128Error::InvalidCharacter { index } => {
+                write!(fmt, "invalid character at index {}", index)
+            }
+            ParseDecimal128Error::ExponentOverflow => write!(fmt, "exponent is out of range"),
+            ParseDecimal128Error::TooManyDigits => {
+                write!(fmt, "number has more than 34 significant digits")
+            }
+            ParseDecimal128Error::MalformedExponent => write!(fmt, "exponent has no digits"),
+            ParseDecimal128Error::NotCanonical => write!(fmt, "string is not in canonical form"),
+            ParseDecimal128Error::InvalidRadix => {
+                write!(fmt, "decimals can only be read in base 10")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for ParseDecimal128Error {}
@@ -0,0 +1,139 @@
+//! C bindings for Decimal128, declared in `include/decimal128.h`.
+//!
+//! Values cross the boundary as `decimal128_t`, a struct holding the 16-byte
+//! encoding most significant byte first, so C code can copy them around
+//! freely. To get a library C can link against, build the crate as a static
+//! or dynamic library:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type staticlib
+//! ```
+use crate::{Decimal128, StrBuf, MAX_STRING_LEN};
+use core::fmt::Write;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::slice;
+
+/// Longest string `decimal128_to_string` writes, including the terminating
+/// NUL.
+pub const DECIMAL128_STRING_SIZE: usize = MAX_STRING_LEN + 1;
+
+/// What `decimal128_compare` returns when either value is NaN.
+pub const DECIMAL128_UNORDERED: c_int = 2;
+
+/// `decimal128_t`: a Decimal128 encoding, most significant byte first.
+pub use crate::RawDecimal128;
+
+/// Creates a `decimal128_t` from 16 bytes, most significant first.
+///
+/// # Safety
+///
+/// `bytes` must point to 16 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn decimal128_from_bytes(bytes: *const u8) -> RawDecimal128 {
+    let mut raw = RawDecimal128 { bytes: [0; 16] };
+    raw.bytes.copy_from_slice(slice::from_raw_parts(bytes, 16));
+    raw
+}
+
+/// Writes the 16 bytes of `value`, most significant first, to `out`.
+///
+/// # Safety
+///
+/// `out` must point to 16 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn decimal128_to_bytes(value: RawDecimal128, out: *mut u8) {
+    ptr::copy_nonoverlapping(value.bytes.as_ptr(), out, 16);
+}
+
+/// Parses a NUL-terminated numeric string into `out`, exactly like `FromStr`.
+/// Returns 0 on success, or one of the `DECIMAL128_ERROR_*` codes, in which
+/// case `out` is left alone.
+///
+/// # Safety
+///
+/// `s` must be a NUL-terminated string, and `out` must point to a writable
+/// `decimal128_t`.
+#[no_mangle]
+pub unsafe extern "C" fn decimal128_from_string(
+    s: *const c_char,
+    out: *mut RawDecimal128,
+) -> c_int {
+    match Decimal128::parse_ascii(CStr::from_ptr(s).to_bytes()) {
+        Ok(dec128) => {
+            *out = dec128.into();
+            0
+        }
+        Err(e) => error_code(e),
+    }
+}
+
+/// Writes `value` as a NUL-terminated string to `buf`, the way `to_string`
+/// formats it, and returns its length without the NUL. If the string and
+/// its NUL don't fit in `len` bytes, nothing is written and the return value
+/// is the length that would have been needed; `DECIMAL128_STRING_SIZE`
+/// bytes are always enough.
+///
+/// # Safety
+///
+/// `buf` must point to `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn decimal128_to_string(
+    value: RawDecimal128,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    let mut string = StrBuf::new();
+    write!(string, "{}", Decimal128::from(value)).expect("Decimal128 strings fit in StrBuf");
+    let string = string.as_str().as_bytes();
+    if string.len() < len {
+        ptr::copy_nonoverlapping(string.as_ptr() as *const c_char, buf, string.len());
+        *buf.add(string.len()) = 0;
+    }
+    string.len()
+}
+
+/// Compares two values numerically like `Decimal128::compare_decimal`:
+/// returns -1, 0 or 1, or `DECIMAL128_UNORDERED` if either is NaN. `-0`
+/// equals `0`.
+#[no_mangle]
+pub extern "C" fn decimal128_compare(a: RawDecimal128, b: RawDecimal128) -> c_int {
+    match Decimal128::from(a).partial_cmp(&Decimal128::from(b)) {
+        Some(ordering) => ordering as c_int,
+        None => DECIMAL128_UNORDERED,
+    }
+}
+
+/// Whether `value` is NaN.
+#[no_mangle]
+pub extern "C" fn decimal128_is_nan(value: RawDecimal128) -> bool {
+    Decimal128::from(value).is_nan()
+}
+
+/// Whether the sign bit of `value` is set.
+#[no_mangle]
+pub extern "C" fn decimal128_is_negative(value: RawDecimal128) -> bool {
+    Decimal128::from(value).is_negative()
+}
+
+/// Whether `value` is zero, with any sign and exponent.
+#[no_mangle]
+pub extern "C" fn decimal128_is_zero(value: RawDecimal128) -> bool {
+    Decimal128::from(value).is_zero()
+}
+
+// The `DECIMAL128_ERROR_*` code for a parse error.
+fn error_code(e: crate::ParseDecimal128Error) -> c_int {
+    use crate::ParseDecimal128Error::*;
+    match e {
+        Empty => 1,
+        InvalidCharacter { .. } => 2,
+        ExponentOverflow => 3,
+        TooManyDigits => 4,
+        MalformedExponent => 5,
+        NotCanonical => 6,
+        // only `from_str_radix` reads in other radixes
+        InvalidRadix => unreachable!("C strings are always parsed in base 10"),
+    }
+}
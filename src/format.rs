@@ -0,0 +1,168 @@
+//! Human-facing formatting: digit grouping and locale-specific separators.
+use crate::Decimal128;
+use alloc::string::String;
+
+/// Separator inserted between groups of three digits in the integer part of a
+/// formatted Decimal128.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// No separator: `1234567.89`.
+    None,
+    /// `1,234,567.89`
+    Comma,
+    /// `1 234 567.89`
+    Space,
+    /// `1_234_567.89`, like Rust integer literals.
+    Underscore,
+    /// `1'234'567.89`
+    Apostrophe,
+}
+
+impl Grouping {
+    fn separator(self) -> Option<char> {
+        match self {
+            Grouping::None => None,
+            Grouping::Comma => Some(','),
+            Grouping::Space => Some(' '),
+            Grouping::Underscore => Some('_'),
+            Grouping::Apostrophe => Some('\''),
+        }
+    }
+}
+
+/// Separators and markers used by `Decimal128::format_with`. The default
+/// options give the same string as `to_string`.
+/// ```
+/// use decimal128::*;
+///
+/// let german = FormatOptions {
+///     decimal_separator: ',',
+///     grouping_separator: Some('.'),
+///     ..FormatOptions::default()
+/// };
+/// let dec128 = Decimal128::from_mysql_decimal("1234567.89", RoundingMode::HalfEven).unwrap();
+/// assert_eq!("1.234.567,89", dec128.format_with(&german));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Written between the integer and fractional digits.
+    pub decimal_separator: char,
+    /// Written between groups of integer digits, or nothing if `None`.
+    pub grouping_separator: Option<char>,
+    /// Number of integer digits in each group, counted from the decimal
+    /// separator. A group size of 0 turns grouping off.
+    pub group_size: usize,
+    /// Written before the exponent in scientific notation.
+    pub exponent_marker: char,
+    /// The smallest adjusted exponent, the exponent in scientific notation,
+    /// of a number written without an exponent. It's -6 by default, as in
+    /// `to_string`; lowering it keeps smaller numbers like `0.00000012`
+    /// positional.
+    pub min_positional_exponent: i32,
+    /// The largest adjusted exponent of a number with a positive exponent,
+    /// like `1.5E+3`, written without an exponent, as `1500`. It's 0 by
+    /// default, which leaves all of them in scientific notation like
+    /// `to_string` does. The positional string reads back as an equal value
+    /// with an exponent of 0, not the one it was written from. Zeros keep
+    /// their exponent either way, as in `0E+3`.
+    pub max_positional_exponent: i32,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            decimal_separator: '.',
+            grouping_separator: None,
+            group_size: 3,
+            exponent_marker: 'E',
+            min_positional_exponent: -6,
+            max_positional_exponent: 0,
+        }
+    }
+}
+
+impl Decimal128 {
+    /// Converts Decimal128 to a string like `to_string` does, with the digits
+    /// before the decimal point split into groups of three. Numbers in
+    /// scientific notation only have one digit there, so they are unchanged.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_mysql_decimal("-1234567.89", RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("-1,234,567.89", dec128.to_formatted_string(&Grouping::Comma));
+    /// ```
+    pub fn to_formatted_string(&self, grouping: &Grouping) -> String {
+        self.format_with(&FormatOptions {
+            grouping_separator: grouping.separator(),
+            ..FormatOptions::default()
+        })
+    }
+
+    /// Converts Decimal128 to a string like `to_string` does, using the
+    /// separators, exponent marker and range of positional exponents in
+    /// `options`. NaN and Infinity are written as they are by `to_string`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let options = FormatOptions {
+    ///     decimal_separator: ',',
+    ///     exponent_marker: 'e',
+    ///     ..FormatOptions::default()
+    /// };
+    /// let dec128 = Decimal128::from_mysql_decimal("-0.00000012", RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("-1,2e-7", dec128.format_with(&options));
+    ///
+    /// let wide = FormatOptions {
+    ///     min_positional_exponent: -10,
+    ///     max_positional_exponent: 20,
+    ///     ..FormatOptions::default()
+    /// };
+    /// assert_eq!("-0.00000012", dec128.format_with(&wide));
+    /// let dec128: Decimal128 = "1.5E+9".parse().unwrap();
+    /// assert_eq!("1500000000", dec128.format_with(&wide));
+    /// ```
+    pub fn format_with(&self, options: &FormatOptions) -> String {
+        let mut string = String::new();
+        let positional = (
+            options.min_positional_exponent,
+            options.max_positional_exponent,
+        );
+        self.write_signed_string(&mut string, false, Some(positional))
+            .expect("writing to a String can't fail");
+        if self.nan || self.inf {
+            return string;
+        }
+
+        let unsigned = string.trim_start_matches('-');
+        let sign = &string[..string.len() - unsigned.len()];
+        let (mantissa, exponent) = match unsigned.find('E') {
+            Some(i) => (&unsigned[..i], Some(&unsigned[i + 1..])),
+            None => (unsigned, None),
+        };
+        let (integer, fraction) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], Some(&mantissa[i + 1..])),
+            None => (mantissa, None),
+        };
+
+        let mut formatted = String::with_capacity(string.len() + integer.len());
+        formatted.push_str(sign);
+        for (i, digit) in integer.chars().enumerate() {
+            if let Some(separator) = options.grouping_separator {
+                let remaining = integer.len() - i;
+                if i > 0 && options.group_size > 0 && remaining.is_multiple_of(options.group_size) {
+                    formatted.push(separator);
+                }
+            }
+            formatted.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            formatted.push(options.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        if let Some(exponent) = exponent {
+            formatted.push(options.exponent_marker);
+            formatted.push_str(exponent);
+        }
+        formatted
+    }
+}
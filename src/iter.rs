@@ -0,0 +1,264 @@
+//! `Sum` and `Product` for Decimal128, and exact summation of slices.
+//!
+//! The exact additions and multiplications here, and the digit arithmetic
+//! under them, are also what the operators in `arithmetic` are built on.
+//! Each one works out the exact result and then rounds it half-even to 34
+//! digits, like IEEE 754 addition and multiplication do.
+use crate::parse::{from_digits_rounded, from_digits_with_flags};
+use crate::{quiet_nan, special, Decimal128, Digits, Flags, RoundingMode, INFINITY, NAN};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::{Product, Sum};
+
+/// Adds the values up from left to right. Every partial sum is rounded
+/// half-even to 34 digits, so the result is what adding them one at a time
+/// would give; its exponent is the smallest of the exponents when no
+/// rounding happens, so `1.50` and `2` add up to `3.50`.
+///
+/// A NaN makes the sum NaN, keeping the first NaN's payload. Infinities of
+/// the same sign sum to Infinity, but Infinity and -Infinity sum to NaN. An
+/// empty iterator sums to 0.
+/// ```
+/// use decimal128::*;
+///
+/// let prices: Vec<Decimal128> = ["1.50", "2", "-0.25"].iter().map(|p| p.parse().unwrap()).collect();
+/// assert_eq!("3.25", prices.iter().sum::<Decimal128>().to_string());
+/// ```
+impl Sum for Decimal128 {
+    fn sum<I: Iterator<Item = Decimal128>>(iter: I) -> Self {
+        iter.reduce(|a, b| sum(&[a, b]).0)
+            .unwrap_or_else(|| Decimal128::from(0))
+    }
+}
+
+impl<'a> Sum<&'a Decimal128> for Decimal128 {
+    fn sum<I: Iterator<Item = &'a Decimal128>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// Multiplies the values from left to right. Every partial product is
+/// rounded half-even to 34 digits, and products too large or too small for a
+/// Decimal128 become Infinity or (subnormal) zero.
+///
+/// A NaN makes the product NaN, keeping the first NaN's payload, and so does
+/// Infinity times zero. An empty iterator multiplies to 1.
+/// ```
+/// use decimal128::*;
+///
+/// let factors: Vec<Decimal128> = ["1.5", "-2", "0.10"].iter().map(|p| p.parse().unwrap()).collect();
+/// assert_eq!("-0.300", factors.iter().product::<Decimal128>().to_string());
+/// ```
+impl Product for Decimal128 {
+    fn product<I: Iterator<Item = Decimal128>>(iter: I) -> Self {
+        iter.reduce(|a, b| multiply(&a, &b).0)
+            .unwrap_or_else(|| Decimal128::from(1))
+    }
+}
+
+impl<'a> Product<&'a Decimal128> for Decimal128 {
+    fn product<I: Iterator<Item = &'a Decimal128>>(iter: I) -> Self {
+        iter.cloned().product()
+    }
+}
+
+impl Decimal128 {
+    /// Adds up `values` exactly and rounds the total half-even to 34 digits
+    /// only once, at the end, so long ledgers don't pick up a rounding error
+    /// per entry like summing them with `Sum` can. Also returns the
+    /// conditions that final rounding raised, so a total that overflowed to
+    /// Infinity is told apart from one that only lost digits.
+    ///
+    /// Without rounding, the exponent of the total is the smallest of the
+    /// exponents. Special values are handled like `Sum` handles them, and an
+    /// empty slice sums to 0. Infinity and -Infinity sum to NaN with
+    /// `invalid_operation` raised.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let big: Decimal128 = "1E+33".parse().unwrap();
+    /// let half: Decimal128 = "0.5".parse().unwrap();
+    /// let ledger = [big, half.clone(), half.clone(), half.clone(), half];
+    ///
+    /// // each 0.5 is rounded away on its own when summing one at a time
+    /// let sum: Decimal128 = ledger.iter().sum();
+    /// assert_eq!("1000000000000000000000000000000000", sum.to_string());
+    /// let (total, flags) = Decimal128::sum_exact(&ledger);
+    /// assert_eq!("1000000000000000000000000000000002", total.to_string());
+    /// assert!(flags.is_empty());
+    /// ```
+    pub fn sum_exact(values: &[Decimal128]) -> (Decimal128, Flags) {
+        match exact_sum(values) {
+            Ok((sign, digits, exponent)) => {
+                from_digits_with_flags(sign, &digits, exponent, RoundingMode::HalfEven)
+            }
+            Err(special) => {
+                let flags = Flags {
+                    invalid_operation: special.nan && !values.iter().any(|v| v.nan),
+                    ..Flags::default()
+                };
+                (special, flags)
+            }
+        }
+    }
+}
+
+// The sum of `values` rounded half-even to 34 digits once, and whether
+// rounding changed it.
+pub(crate) fn sum(values: &[Decimal128]) -> (Decimal128, bool) {
+    match exact_sum(values) {
+        Ok((sign, digits, exponent)) => {
+            from_digits_rounded(sign, &digits, exponent, RoundingMode::HalfEven)
+        }
+        Err(special) => (special, false),
+    }
+}
+
+// The exact sum of `values` as a sign, digits and exponent, or the result
+// when it is NaN or Infinity (or when there are no values).
+pub(crate) fn exact_sum(values: &[Decimal128]) -> Result<(bool, Vec<u8>, i64), Decimal128> {
+    if let Some(nan) = values.iter().find(|v| v.nan) {
+        return Err(quiet_nan(nan));
+    }
+    let positive_infinity = values.iter().any(|v| v.inf && !v.sign);
+    let negative_infinity = values.iter().any(|v| v.inf && v.sign);
+    match (positive_infinity, negative_infinity) {
+        (true, true) => return Err(special(false, NAN, 0)),
+        (true, false) => return Err(special(false, INFINITY, 0)),
+        (false, true) => return Err(special(true, INFINITY, 0)),
+        (false, false) => {}
+    }
+    let exponent = match values.iter().map(|v| v.exponent.to_adjusted()).min() {
+        Some(exponent) => exponent,
+        None => return Err(Decimal128::from(0)),
+    };
+
+    // add the positive and negative values up separately, with the
+    // coefficients lined up on the smallest exponent
+    let (mut positive, mut negative) = (Vec::new(), Vec::new());
+    for v in values {
+        let shift = (v.exponent.to_adjusted() - exponent) as usize;
+        let digits = to_digits(v.significand.to_num(), shift);
+        let total = if v.sign { &mut negative } else { &mut positive };
+        *total = add_digits(total, &digits);
+    }
+
+    let (sign, digits) = match compare_digits(&positive, &negative) {
+        Ordering::Greater => (false, subtract_digits(&positive, &negative)),
+        Ordering::Less => (true, subtract_digits(&negative, &positive)),
+        // an exact zero is -0 only if every value was -0
+        Ordering::Equal => (values.iter().all(|v| v.sign), Vec::new()),
+    };
+    Ok((sign, digits, exponent as i64))
+}
+
+// `a * b`, and whether rounding changed it.
+pub(crate) fn multiply(a: &Decimal128, b: &Decimal128) -> (Decimal128, bool) {
+    match exact_product(a, b) {
+        Ok((sign, digits, exponent)) => {
+            from_digits_rounded(sign, &digits, exponent, RoundingMode::HalfEven)
+        }
+        Err(special) => (special, false),
+    }
+}
+
+// The exact product of `a` and `b` as a sign, digits and exponent, or the
+// result when it is NaN or Infinity.
+pub(crate) fn exact_product(
+    a: &Decimal128,
+    b: &Decimal128,
+) -> Result<(bool, Vec<u8>, i64), Decimal128> {
+    if a.nan || b.nan {
+        return Err(quiet_nan(if a.nan { a } else { b }));
+    }
+    let sign = a.sign != b.sign;
+    if a.inf || b.inf {
+        let zero = |v: &Decimal128| !v.inf && v.significand.to_num() == 0;
+        if zero(a) || zero(b) {
+            return Err(special(false, NAN, 0));
+        }
+        return Err(special(sign, INFINITY, 0));
+    }
+
+    let digits = multiply_digits(
+        &to_digits(a.significand.to_num(), 0),
+        &to_digits(b.significand.to_num(), 0),
+    );
+    let exponent = a.exponent.to_adjusted() as i64 + b.exponent.to_adjusted() as i64;
+    Ok((sign, digits, exponent))
+}
+
+// The decimal digits of a coefficient, most significant first, followed by
+// `zeros` zeros.
+pub(crate) fn to_digits(coefficient: u128, zeros: usize) -> Vec<u8> {
+    let mut digits: Vec<u8> = Digits::new(coefficient).collect();
+    digits.resize(digits.len() + zeros, 0);
+    digits
+}
+
+pub(crate) fn compare_digits(a: &[u8], b: &[u8]) -> Ordering {
+    let strip = |digits: &[u8]| -> usize { digits.iter().take_while(|d| **d == 0).count() };
+    let (a, b) = (&a[strip(a)..], &b[strip(b)..]);
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+// `a + b`, without leading zeros.
+pub(crate) fn add_digits(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let len = a.len().max(b.len()) + 1;
+    let mut sum = vec![0u8; len];
+    let mut carry = 0;
+    for i in 0..len {
+        let digit = |digits: &[u8]| -> u8 {
+            if i < digits.len() {
+                digits[digits.len() - 1 - i]
+            } else {
+                0
+            }
+        };
+        let total = digit(a) + digit(b) + carry;
+        sum[len - 1 - i] = total % 10;
+        carry = total / 10;
+    }
+    let leading_zeros = sum.iter().take_while(|d| **d == 0).count();
+    sum.split_off(leading_zeros)
+}
+
+// `a - b`, where `a` is at least `b`.
+pub(crate) fn subtract_digits(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut difference = a.to_vec();
+    let mut borrow = 0;
+    for i in 0..a.len() {
+        let subtrahend = if i < b.len() { b[b.len() - 1 - i] } else { 0 } + borrow;
+        let digit = &mut difference[a.len() - 1 - i];
+        if *digit >= subtrahend {
+            *digit -= subtrahend;
+            borrow = 0;
+        } else {
+            *digit += 10 - subtrahend;
+            borrow = 1;
+        }
+    }
+    difference
+}
+
+pub(crate) fn multiply_digits(a: &[u8], b: &[u8]) -> Vec<u8> {
+    // column sums, least significant first; 34 * 34 * 81 fits easily
+    let mut columns = vec![0u32; a.len() + b.len()];
+    for (i, x) in a.iter().rev().enumerate() {
+        for (j, y) in b.iter().rev().enumerate() {
+            columns[i + j] += (*x as u32) * (*y as u32);
+        }
+    }
+    let mut carry = 0;
+    let mut product: Vec<u8> = columns
+        .iter()
+        .map(|column| {
+            let total = column + carry;
+            carry = total / 10;
+            (total % 10) as u8
+        })
+        .collect();
+    product.reverse();
+    product
+}
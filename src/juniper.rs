@@ -0,0 +1,30 @@
+//! A `Decimal128` scalar for juniper.
+//!
+//! Like the async-graphql scalar, values go out as strings so no digits are
+//! lost to GraphQL's double-precision Float, and inputs can be strings or
+//! Int and Float literals.
+use crate::Decimal128;
+use ::juniper::{graphql_scalar, InputValue, ScalarValue, Value};
+
+/// Parses strings the way `str::parse` does, and converts Int inputs
+/// exactly and Float inputs with `from_f64_shortest`. Outputs the string
+/// `to_string` returns.
+#[graphql_scalar(name = "Decimal128", with = self, parse_token(String, i32, f64))]
+type Scalar = Decimal128;
+
+fn to_output<S: ScalarValue>(v: &Scalar) -> Value<S> {
+    Value::scalar(v.to_string())
+}
+
+fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Scalar, String> {
+    if let Some(s) = v.as_string_value() {
+        s.parse()
+            .map_err(|e| format!("Failed to parse `Decimal128`: {}", e))
+    } else if let Some(i) = v.as_int_value() {
+        Ok(Decimal128::from(i))
+    } else if let Some(f) = v.as_float_value() {
+        Ok(Decimal128::from_f64_shortest(f))
+    } else {
+        Err(format!("Expected `String`, `Int` or `Float`, found: {}", v))
+    }
+}
@@ -2,20 +2,124 @@
 //! [1bits]  [   14bits   ]  [   113 bits   ]
 //!  sign       exponent        significand
 //!              field  
-use bitvec::{bitvec, BigEndian, BitVec};
-use byteorder::*;
-use std::cmp::Ordering;
-use std::fmt;
-use std::io::Cursor;
-use std::str::FromStr;
-
-#[derive(Clone, PartialEq, PartialOrd)]
+//!
+//! The crate is `no_std` when the default `std` feature is turned off.
+//! Decoding, encoding, comparison and formatting into a `fmt::Write` work
+//! without an allocator; the `alloc` feature adds the methods that return a
+//! String or Vec, and parsing.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::{self, Write};
+use core::hash::{Hash, Hasher};
+
+#[cfg(feature = "alloc")]
+mod allocate;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "alloc")]
+mod arithmetic;
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+#[cfg(feature = "num-bigint")]
+mod bigint;
+#[cfg(feature = "borsh")]
+mod borsh;
+mod buffer;
+mod builder;
+#[cfg(feature = "alloc")]
+mod canonical;
+mod coefficient;
+pub mod consts;
+mod division;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "alloc")]
+mod format;
+#[cfg(feature = "alloc")]
+mod iter;
+#[cfg(feature = "juniper")]
+mod juniper;
+mod literal;
+#[cfg(feature = "alloc")]
+mod math;
+#[cfg(feature = "alloc")]
+mod mysql;
+mod non_zero;
+mod not_nan;
+#[cfg(feature = "num-traits")]
+mod num_traits;
+mod parquet;
+#[cfg(feature = "alloc")]
+mod parse;
+#[cfg(feature = "alloc")]
+mod percent;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rand")]
+mod random;
+mod ratio;
+mod raw;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+#[cfg(feature = "serde")]
+mod serde;
+mod sortable;
+#[cfg(feature = "math-extra")]
+mod trig;
+#[cfg(feature = "utoipa")]
+mod utoipa;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "serde")]
+pub use self::serde::strict as serde_strict;
+#[cfg(feature = "alloc")]
+pub use arithmetic::Flags;
+pub use buffer::Buffer;
+pub use builder::Decimal128Builder;
+#[cfg(feature = "alloc")]
+pub use error::ParseDecimal128Error;
+pub use error::{ArithmeticError, BufferTooSmall, BuildError, ConversionError};
+#[cfg(feature = "alloc")]
+pub use format::{FormatOptions, Grouping};
+pub use non_zero::NonZeroDecimal128;
+pub use not_nan::NotNan;
+#[cfg(feature = "rand")]
+pub use random::UniformDecimal128;
+pub use raw::RawDecimal128;
+
+// Exponent bias as defined by IEEE 754-2008 for decimal128.
+const EXPONENT_BIAS: i16 = 6176;
+// Smallest and largest unbiased exponents a finite Decimal128 can have.
+const MIN_EXPONENT: i16 = -6176;
+const MAX_EXPONENT: i16 = 6111;
+// Number of decimal digits a coefficient can hold.
+const MAX_DIGITS: u32 = 34;
+// The combination field of Infinity and NaN.
+const INFINITY: u128 = 0b11110 << 122;
+const NAN: u128 = 0b11111 << 122;
+
+#[derive(Clone, Default, PartialEq, PartialOrd)]
 pub struct Exponent {
-    vec: BitVec<BigEndian>,
+    num: u16,
 }
-#[derive(Clone, PartialEq, PartialOrd)]
+#[derive(Clone, Default, PartialEq, PartialOrd)]
 pub struct Significand {
-    vec: BitVec<BigEndian>,
+    num: u128,
 }
 
 #[derive(Clone)]
@@ -34,22 +138,82 @@ pub enum NumberType {
     Finite,
 }
 
-impl From<i32> for Decimal128 {
-    fn from(_v: i32) -> Self {
-        unimplemented!("Creating Decimal128 from i32 is not yet implemented.")
+/// Rounding modes from the [decimal arithmetic
+/// specification](http://speleotrove.com/decimal/damodel.html), used whenever a
+/// value has more digits than its destination can hold.
+///
+/// The enum is `#[non_exhaustive]`, so matches on it need a wildcard arm:
+/// `Stochastic` only exists with the `rand` feature, and turning that on
+/// anywhere in a build mustn't break matches elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum RoundingMode {
+    /// Round to the nearest value; ties go to the even neighbour. This is the
+    /// IEEE 754 default.
+    #[default]
+    HalfEven,
+    /// Round to the nearest value; ties go away from zero.
+    HalfUp,
+    /// Round to the nearest value; ties go toward zero.
+    HalfDown,
+    /// Round away from zero.
+    Up,
+    /// Round toward zero, i.e. truncate.
+    Down,
+    /// Round toward positive infinity.
+    Ceiling,
+    /// Round toward negative infinity.
+    Floor,
+    /// Round up or down at random, up with a probability equal to the
+    /// fraction of a unit in the last place that is discarded, so `1.25`
+    /// rounded to one decimal place is `1.3` half the time and `1.27` is
+    /// `1.3` 70% of the time. The rounding error averages out to zero, even
+    /// for values that don't straddle ties evenly, which simulations and
+    /// quantized models accumulating many small values rely on. Values too
+    /// large for a Decimal128 round to Infinity. Draws from
+    /// `rand::thread_rng()` (`rand` feature).
+    #[cfg(feature = "rand")]
+    Stochastic,
+}
+
+impl RoundingMode {
+    // Whether a coefficient that had digits discarded should be incremented.
+    // `digit` is the first discarded digit, and `sticky` is whether any of the
+    // discarded digits after it were non-zero. Only stochastic rounding needs
+    // those digits themselves, `rest`, so the iterator is never run otherwise.
+    #[cfg_attr(not(feature = "rand"), allow(unused_variables))]
+    fn rounds_up(
+        self,
+        sign: bool,
+        odd: bool,
+        digit: u8,
+        sticky: bool,
+        rest: impl Iterator<Item = u8>,
+    ) -> bool {
+        let discarded = digit != 0 || sticky;
+        match self {
+            RoundingMode::HalfEven => digit > 5 || (digit == 5 && (sticky || odd)),
+            RoundingMode::HalfUp => digit >= 5,
+            RoundingMode::HalfDown => digit > 5 || (digit == 5 && sticky),
+            RoundingMode::Up => discarded,
+            RoundingMode::Down => false,
+            RoundingMode::Ceiling => !sign && discarded,
+            RoundingMode::Floor => sign && discarded,
+            #[cfg(feature = "rand")]
+            RoundingMode::Stochastic => random::rounds_up(core::iter::once(digit).chain(rest)),
+        }
     }
 }
 
-impl From<u32> for Decimal128 {
-    fn from(_v: u32) -> Self {
-        unimplemented!("Creating Decimal128 from u32 is not yet implemented.")
+impl From<i32> for Decimal128 {
+    fn from(v: i32) -> Self {
+        Decimal128::from_integer(v < 0, v.unsigned_abs() as u128)
     }
 }
 
-impl FromStr for Decimal128 {
-    type Err = ();
-    fn from_str(_s: &str) -> Result<Self, ()> {
-        unimplemented!("Creating Decimal128 from string is not yet implemented.")
+impl From<u32> for Decimal128 {
+    fn from(v: u32) -> Self {
+        Decimal128::from_integer(false, v as u128)
     }
 }
 
@@ -65,6 +229,42 @@ impl Into<u32> for Decimal128 {
     }
 }
 
+/// Converts integral values exactly, whatever their exponent, so `1.20E+2`
+/// and `120.00` both give 120. Fails with `ConversionError::Inexact` if
+/// there's a non-zero fractional part, `ConversionError::Overflow` if the
+/// value is out of range, and `ConversionError::NotFinite` for NaN and
+/// Infinity.
+/// ```
+/// use decimal128::*;
+/// use std::convert::TryFrom;
+///
+/// let total: Decimal128 = "-12.00".parse().unwrap();
+/// assert_eq!(Ok(-12), i128::try_from(total));
+/// let price: Decimal128 = "12.50".parse().unwrap();
+/// assert_eq!(Err(ConversionError::Inexact), i128::try_from(price));
+/// ```
+impl TryFrom<Decimal128> for i128 {
+    type Error = ConversionError;
+
+    fn try_from(v: Decimal128) -> Result<Self, ConversionError> {
+        v.to_minor_units_exact(0)
+    }
+}
+
+/// Converts integral values exactly, like the conversion to `i128`.
+/// Negative values are out of range, except for `-0`.
+impl TryFrom<Decimal128> for u128 {
+    type Error = ConversionError;
+
+    fn try_from(v: Decimal128) -> Result<Self, ConversionError> {
+        let magnitude = v.unsigned_minor_units(0, None)?;
+        if v.sign && magnitude != 0 {
+            return Err(ConversionError::Overflow);
+        }
+        Ok(magnitude)
+    }
+}
+
 impl Decimal128 {
     pub fn zero() -> Self {
         Decimal128 {
@@ -80,8 +280,9 @@ impl Decimal128 {
     /// Create a Decimal128 from a [u8; 16].
     ///
     /// This method extracts out the sign, exponent and signficand, uses Binary
-    /// Integer Decimal decoding. The byte order is LittleEndian. For more
-    /// information on how extraction is done, please refer to
+    /// Integer Decimal decoding. The byte order is BigEndian, i.e. the byte
+    /// holding the sign bit comes first. For more information on how
+    /// extraction is done, please refer to
     /// [wikipedia](https://en.wikipedia.org/wiki/Decimal128_floating-point_format),
     /// or the [IEEE 754-2008](https://ieeexplore.ieee.org/document/4610935)
     /// ```
@@ -91,143 +292,147 @@ impl Decimal128 {
     /// let dec128 = Decimal128::from_raw_bytes(vec);
     /// ```
     pub fn from_raw_bytes(buffer: [u8; 16]) -> Self {
-        // decimal 128's exponent is 14bits long; we will construct a u16 and
-        // fill up the first two bits as zeros and then get its value.
-        let mut total_exp = Exponent::new();
-        // Significnad can be 113 *or* 111 bit long. Regardless of the size we
-        // will pad it with 14 0s. We will be eventually constructing a u128
-        // from this eventually.
-        let mut total_sig = Significand::new();
-
-        let byte = buffer[0];
-        let max = 0b1111_1111;
+        let bits = u128::from_be_bytes(buffer);
         // first bit is sign: negative or positive integer
-        let is_negative_bitmask = 0b0111_1111;
-        let sign = (byte | is_negative_bitmask) == max;
+        let sign = bits >> 127 == 1;
 
-        // the next 5 bits of the first byte are combination field; these include:
+        // the next 5 bits are the combination field; these include:
         // first 5 bits       Type	    Exponent MSBs	Significand MSD
         // ---------------------------------------------------------------------------
         //     a b c d e	  Finite       14bits           113bits
         //     1 1 c d e	  Finite    2 bits to right     111bits
         //     1 1 1 1 0	  Infinity	    - -	            - - - -
         //     1 1 1 1 1	  NaN           - -             - - - -
-        // the easiest bitmask to do is for NaN, i.e. five 1s
-        let res = byte | 0b1000_0011;
-        let combination_field = match res {
-            // if everything is 1s, we are looking at NaN
-            0b1111_1111 => NumberType::NaN,
-            // if the last of the five bits is a 0, we are looking at Infinity
-            0b1111_1011 => NumberType::Infinity,
-            // match for finite cases
-            _ => match byte | 0b1001_1111 {
-                0b1111_1111 => {
-                    // since the first two bits after the sign are 11, we ignore
-                    // them and gather the remainder of the first byte.
-                    let c = if (byte | 0b1110_1111) == max { 1 } else { 0 };
-                    let d = if (byte | 0b1111_0111) == max { 1 } else { 0 };
-                    let e = if (byte | 0b1111_1011) == max { 1 } else { 0 };
-                    let f = if (byte | 0b1111_1101) == max { 1 } else { 0 };
-                    let g = if (byte | 0b1111_1110) == max { 1 } else { 0 };
-                    let mut exp = bitvec![c, d, e, f, g];
-                    total_exp.append(&mut exp);
-                    // in this case second byte of the buffer can just be
-                    // straight up appended to the exponent.
-                    let byte_2 = buffer[1];
-                    let mut sb_bv: BitVec = (&[byte_2] as &[u8]).into();
-                    total_exp.append(&mut sb_bv);
-                    // out of the third byte the first bit are part of the
-                    // exponent, and the last 7 bits are part of the significand
-                    let byte_3 = buffer[1];
-                    let h = if (byte_2 | 0b0111_1111) == max { 1 } else { 0 };
-                    let mut exp_cont = bitvec![h];
-                    total_exp.append(&mut exp_cont);
-                    let i = if (byte_3 | 0b1011_1111) == max { 1 } else { 0 };
-                    let j = if (byte_3 | 0b1101_1111) == max { 1 } else { 0 };
-                    let k = if (byte_3 | 0b1110_1111) == max { 1 } else { 0 };
-                    let l = if (byte_3 | 0b1111_0111) == max { 1 } else { 0 };
-                    let m = if (byte_3 | 0b1111_1011) == max { 1 } else { 0 };
-                    let n = if (byte_3 | 0b1111_1101) == max { 1 } else { 0 };
-                    let o = if (byte_3 | 0b1111_1110) == max { 1 } else { 0 };
-                    // Start a new vec for 111bit significand. This version of
-                    // the significand is offset by two bits, so we pad it with
-                    // `100`
-                    let mut sig = bitvec![1, 0, 0, i, j, k, l, m, n, o];
-                    total_sig.append(&mut sig);
-                    NumberType::Finite
-                }
-                _ => {
-                    // if the first two bits after the sign are `00`, `01`,
-                    // `10`, we add the remainder of the first byte to exponent
-                    let a = if (byte | 0b1011_1111) == max { 1 } else { 0 };
-                    let b = if (byte | 0b1101_1111) == max { 1 } else { 0 };
-                    let c = if (byte | 0b1110_1111) == max { 1 } else { 0 };
-                    let d = if (byte | 0b1111_0111) == max { 1 } else { 0 };
-                    let e = if (byte | 0b1111_1011) == max { 1 } else { 0 };
-                    let f = if (byte | 0b1111_1101) == max { 1 } else { 0 };
-                    let g = if (byte | 0b1111_1110) == max { 1 } else { 0 };
-                    let mut exp = bitvec![a, b, c, d, e, f, g];
-                    total_exp.append(&mut exp);
-                    // out of the second byte the first 7 bits are part of the
-                    // exponent, and the last bit if part of the significand
-                    let byte_2 = buffer[1];
-                    let h = if (byte_2 | 0b0111_1111) == max { 1 } else { 0 };
-                    let i = if (byte_2 | 0b1011_1111) == max { 1 } else { 0 };
-                    let j = if (byte_2 | 0b1101_1111) == max { 1 } else { 0 };
-                    let k = if (byte_2 | 0b1110_1111) == max { 1 } else { 0 };
-                    let l = if (byte_2 | 0b1111_0111) == max { 1 } else { 0 };
-                    let m = if (byte_2 | 0b1111_1011) == max { 1 } else { 0 };
-                    let n = if (byte_2 | 0b1111_1101) == max { 1 } else { 0 };
-                    let mut exp_cont = bitvec![h, i, j, k, l, m, n];
-                    total_exp.append(&mut exp_cont);
-                    let o = if (byte_2 | 0b1111_1110) == max { 1 } else { 0 };
-                    // Start a new vec for 113bit significand. Since this
-                    // version of significand is not offset, we pad it with only
-                    // `0`
-                    let mut sig = bitvec![0, o];
-                    total_sig.append(&mut sig);
-                    // add the whole third byte to the signficand in this case
-                    let byte_3 = buffer[2];
-                    let mut tb_bv: BitVec = (&[byte_3] as &[u8]).into();
-                    total_sig.append(&mut tb_bv);
-                    NumberType::Finite
-                }
-            },
+        let combination = (bits >> 122) & 0b1_1111;
+        let (combination_field, exponent, coefficient) = match combination {
+            0b1_1111 => {
+                // the payload of a NaN is in the last 110 bits
+                (NumberType::NaN, 0, bits & ((1 << 110) - 1))
+            }
+            0b1_1110 => (NumberType::Infinity, 0, 0),
+            // if the first two bits after the sign are `11`, the exponent
+            // starts two bits to the right and the 111-bit significand has an
+            // implicit `100` in front of it. Such a significand is always
+            // larger than 34 digits.
+            _ if combination >> 3 == 0b11 => (
+                NumberType::Finite,
+                ((bits >> 111) & 0x3fff) as u16,
+                (0b100 << 111) | (bits & ((1 << 111) - 1)),
+            ),
+            // if the first two bits after the sign are `00`, `01` or `10`, the
+            // exponent is the 14 bits after the sign and the rest is the
+            // significand.
+            _ => (
+                NumberType::Finite,
+                ((bits >> 113) & 0x3fff) as u16,
+                bits & ((1 << 113) - 1),
+            ),
         };
 
-        // the rest of the bytes of the vec we are passed in.
-        for bytes in 3..buffer.len() {
-            let mut bv: BitVec = (&[buffer[bytes]] as &[u8]).into();
-            total_sig.append(&mut bv);
-        }
-
-        let dec128 = match combination_field {
-            NumberType::Finite => Decimal128 {
-                sign,
-                exponent: total_exp,
-                significand: total_sig,
-                bytes: buffer,
-                nan: false,
-                inf: false,
-            },
-            NumberType::NaN => Decimal128 {
-                sign,
-                exponent: total_exp,
-                significand: total_sig,
-                bytes: buffer,
-                nan: true,
-                inf: false,
-            },
-            NumberType::Infinity => Decimal128 {
-                sign,
-                exponent: total_exp,
-                significand: total_sig,
-                bytes: buffer,
-                nan: false,
-                inf: true,
-            },
-        };
-        dec128
+        Decimal128 {
+            sign,
+            exponent: Exponent { num: exponent },
+            significand: Significand { num: coefficient },
+            bytes: buffer,
+            nan: matches!(combination_field, NumberType::NaN),
+            inf: matches!(combination_field, NumberType::Infinity),
+        }
+    }
+
+    // Encode a finite Decimal128 from its sign, unbiased exponent and
+    // coefficient. Callers need to make sure the coefficient has at most 34
+    // digits and the exponent is within MIN_EXPONENT..=MAX_EXPONENT. This is
+    // what `from_raw_bytes` would decode the encoding to, built directly so
+    // it works in constants.
+    pub(crate) const fn from_parts(sign: bool, exponent: i16, coefficient: u128) -> Self {
+        let biased = (exponent + EXPONENT_BIAS) as u16;
+        let bits = ((sign as u128) << 127) | ((biased as u128) << 113) | coefficient;
+        Decimal128 {
+            sign,
+            exponent: Exponent { num: biased },
+            significand: Significand { num: coefficient },
+            bytes: bits.to_be_bytes(),
+            nan: false,
+            inf: false,
+        }
+    }
+
+    // Encode a finite Decimal128 without rounding. Trailing zeros are removed
+    // from the coefficient, or zeros are added to it, when that's what it
+    // takes to bring both the coefficient and the exponent into range.
+    pub(crate) const fn from_parts_exact(
+        sign: bool,
+        mut exponent: i32,
+        mut coefficient: u128,
+    ) -> Result<Self, ConversionError> {
+        let max_coefficient = pow10(MAX_DIGITS) - 1;
+        if coefficient == 0 {
+            if exponent < MIN_EXPONENT as i32 {
+                exponent = MIN_EXPONENT as i32;
+            } else if exponent > MAX_EXPONENT as i32 {
+                exponent = MAX_EXPONENT as i32;
+            }
+        }
+        while coefficient > max_coefficient || exponent < MIN_EXPONENT as i32 {
+            if !coefficient.is_multiple_of(10) {
+                return Err(ConversionError::Inexact);
+            }
+            coefficient /= 10;
+            exponent += 1;
+        }
+        while exponent > MAX_EXPONENT as i32 {
+            if coefficient * 10 > max_coefficient {
+                return Err(ConversionError::Overflow);
+            }
+            coefficient *= 10;
+            exponent -= 1;
+        }
+        Ok(Decimal128::from_parts(sign, exponent as i16, coefficient))
+    }
+
+    // Encode a finite Decimal128 from a list of decimal digits (most
+    // significant first) and an exponent, rounding the digits to 34 with
+    // `mode` if there are more.
+    pub(crate) fn from_digits(
+        sign: bool,
+        digits: &[u8],
+        mut exponent: i32,
+        mode: RoundingMode,
+    ) -> Result<Self, ConversionError> {
+        let leading_zeros = digits.iter().take_while(|d| **d == 0).count();
+        let digits = &digits[leading_zeros..];
+        let kept = digits.len().min(MAX_DIGITS as usize);
+
+        let mut coefficient = digits[..kept]
+            .iter()
+            .fold(0u128, |c, d| c * 10 + *d as u128);
+        if let Some((digit, rest)) = digits[kept..].split_first() {
+            let sticky = rest.iter().any(|d| *d != 0);
+            exponent = exponent.saturating_add((digits.len() - kept) as i32);
+            let rest = rest.iter().copied();
+            if mode.rounds_up(sign, coefficient % 2 == 1, *digit, sticky, rest) {
+                coefficient += 1;
+            }
+            if coefficient == pow10(MAX_DIGITS) {
+                coefficient /= 10;
+                exponent = exponent.saturating_add(1);
+            }
+        }
+        Decimal128::from_parts_exact(sign, exponent, coefficient)
+    }
+
+    // Create a Decimal128 from an integer, rounding it to 34 digits if needed.
+    pub(crate) fn from_integer(sign: bool, magnitude: u128) -> Self {
+        if magnitude < pow10(MAX_DIGITS) {
+            return Decimal128::from_parts(sign, 0, magnitude);
+        }
+        let mut digits = [0u8; 39];
+        let count = Digits::new(magnitude).len();
+        for (digit, d) in digits.iter_mut().zip(Digits::new(magnitude)) {
+            *digit = d;
+        }
+        // at most 39 digits, so the exponent can't overflow
+        Decimal128::from_digits(sign, &digits[..count], 0, RoundingMode::HalfEven).unwrap()
     }
 
     pub fn is_nan(&self) -> bool {
@@ -250,205 +455,1127 @@ impl Decimal128 {
         return !self.is_negative();
     }
 
+    /// Whether the value is zero, of either sign and with any exponent, so
+    /// `0`, `-0.00` and `0E+5` are all zero.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// assert!("0E+5".parse::<Decimal128>().unwrap().is_zero());
+    /// assert!(!"1E-6176".parse::<Decimal128>().unwrap().is_zero());
+    /// ```
     pub fn is_zero(&self) -> bool {
-        return !self.nan && self.exponent.is_zero() && self.significand.is_zero()
+        !self.nan && !self.inf && self.significand.is_zero()
+    }
+
+    /// Whether the value is a zero with the sign bit set, like `-0.00`.
+    pub fn is_negative_zero(&self) -> bool {
+        self.is_zero() && self.sign
+    }
+
+    /// Whether the value is a zero without the sign bit set.
+    pub fn is_positive_zero(&self) -> bool {
+        self.is_zero() && !self.sign
+    }
+
+    /// Converts Decimal128 to a string in positional notation, never using an
+    /// exponent. Every digit of the coefficient is kept, and zeros are added
+    /// between it and the decimal point as needed. NaN and Infinity format
+    /// the same as with `to_string`.
+    ///
+    /// The longest result is 6179 characters, for a negative number with an
+    /// exponent of -6176: a sign, `0.` and 6176 digits.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// // 1.234E-7
+    /// let mut vec = [0u8; 16];
+    /// vec[0] = 0x30;
+    /// vec[1] = 0x2c;
+    /// vec[14] = 0x04;
+    /// vec[15] = 0xd2;
+    /// assert_eq!("0.0000001234", Decimal128::from_raw_bytes(vec).to_plain_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_plain_string(&self) -> String {
+        let fraction_digits = (-self.exponent.to_adjusted()).max(0) as usize;
+        format!("{:.*}", fraction_digits, self)
     }
 
-    /// Converts Decimal128 to string. Uses information in
+    /// Converts Decimal128 to a string like `to_string` does, except that when
+    /// an exponent is used, it is a multiple of three and there are one to
+    /// three digits before the decimal point. This is the to-engineering-string
+    /// conversion from the
     /// [speleotrove](http://speleotrove.com/decimal/daconvs.html) decimal
     /// documentation.
-    pub fn to_string(&self) -> String {
-        // just return NaN if we are dealing with NaN. This does not come with a
-        // sign.
-        if self.nan {
-            return String::from("NaN");
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// // 1.23E-7
+    /// let mut vec = [0u8; 16];
+    /// vec[0] = 0x30;
+    /// vec[1] = 0x2e;
+    /// vec[15] = 123;
+    /// let dec128 = Decimal128::from_raw_bytes(vec);
+    /// assert_eq!("1.23E-7", dec128.to_string());
+    /// assert_eq!("123E-9", dec128.to_engineering_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_engineering_string(&self) -> String {
+        let mut buf = StrBuf::new();
+        self.write_signed_string(&mut buf, true, Some(POSITIONAL_EXPONENTS))
+            .expect("engineering strings fit in StrBuf");
+        String::from(buf.as_str())
+    }
+
+    /// Converts Decimal128 to a string in scientific notation, with one digit
+    /// before the decimal point and an exponent, e.g. `1.2345E+3` or
+    /// `5.00E+0`.
+    ///
+    /// With a `threshold`, numbers are written positionally like `to_string`
+    /// does when their adjusted exponent (the exponent in scientific notation)
+    /// is at least `-threshold`. `to_string` uses a threshold of 6. Numbers
+    /// with a positive exponent always use scientific notation, because
+    /// writing them positionally would lose their exponent.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_mysql_decimal("0.00000012", RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("1.2E-7", dec128.to_string());
+    /// assert_eq!("1.2E-7", dec128.to_scientific_string(None));
+    /// assert_eq!("0.00000012", dec128.to_scientific_string(Some(10)));
+    ///
+    /// let dec128 = Decimal128::from_mysql_decimal("12.5", RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("1.25E+1", dec128.to_scientific_string(None));
+    /// assert_eq!("12.5", dec128.to_scientific_string(Some(0)));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_scientific_string(&self, threshold: Option<u32>) -> String {
+        let positional = threshold.map(|threshold| (-(threshold.min(i32::MAX as u32) as i32), 0));
+        let mut string = String::new();
+        self.write_signed_string(&mut string, false, positional)
+            .expect("writing to a String can't fail");
+        string
+    }
+
+    /// Converts Decimal128 to a string with exactly `n` significant digits,
+    /// like JavaScript's `toPrecision`: the value is rounded half-even with
+    /// `round_sf`, and then zeros are added to the end to make up `n`
+    /// digits. It's written like `to_string`, so a value whose digits would
+    /// end before the decimal point uses scientific notation.
+    ///
+    /// A coefficient holds at most 34 digits, so larger `n` give 34, and the
+    /// largest values can have more than `n`, as `round_sf` explains. Values
+    /// that round past the largest Decimal128 become Infinity, and NaN and
+    /// Infinity are written as they are. Panics if `n` is 0.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("1.500", d("1.5").to_precision_string(4));
+    /// assert_eq!("0.00123", d("0.0012345").to_precision_string(3));
+    /// assert_eq!("1.2E+3", d("1234.5").to_precision_string(2));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_precision_string(&self, n: u32) -> String {
+        assert!(n > 0, "to_precision_string needs at least one digit");
+        let rounded = match self.round_sf(n, RoundingMode::HalfEven) {
+            Ok(rounded) => rounded,
+            Err(_) => special(self.sign, INFINITY, 0),
         };
+        if rounded.nan || rounded.inf {
+            return rounded.to_string();
+        }
+        let exponent = rounded.exponent.to_adjusted() as i32;
+        // at the largest exponents round_sf can leave more than n digits
+        let zeros = n
+            .min(MAX_DIGITS)
+            .saturating_sub(rounded.digit_count())
+            .min((exponent - MIN_EXPONENT as i32) as u32);
+        let padded = Decimal128::from_parts(
+            rounded.sign,
+            (exponent - zeros as i32) as i16,
+            rounded.significand.to_num() * pow10(zeros),
+        );
+        padded.to_string()
+    }
+
+    fn write_signed_string<W: Write>(
+        &self,
+        out: &mut W,
+        engineering: bool,
+        positional: Option<(i32, i32)>,
+    ) -> fmt::Result {
+        if self.sign && !self.nan {
+            out.write_char('-')?;
+        }
+        self.write_string(out, engineering, positional)
+    }
+
+    /// Returns the number of digits in the coefficient, without leading zeros.
+    /// A zero coefficient counts as one digit, and so does Infinity. For NaN,
+    /// this counts the digits of its payload.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// // 123.45
+    /// let mut vec = [0u8; 16];
+    /// vec[0] = 0x30;
+    /// vec[1] = 0x3c;
+    /// vec[14] = 0x30;
+    /// vec[15] = 0x39;
+    /// assert_eq!(5, Decimal128::from_raw_bytes(vec).digit_count());
+    /// ```
+    pub fn digit_count(&self) -> u32 {
+        self.significand.count_digits() as u32
+    }
+
+    /// Returns the digits of the coefficient, most significant first, as
+    /// numbers from 0 to 9, without building a String. There are as many
+    /// as `digit_count` says: a zero coefficient has a single 0, and so does
+    /// Infinity, and NaN gives the digits of its payload. The sign and the
+    /// exponent aren't included.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "-12.50".parse().unwrap();
+    /// assert_eq!(vec![1, 2, 5, 0], price.digits().collect::<Vec<u8>>());
+    /// ```
+    pub fn digits(&self) -> impl ExactSizeIterator<Item = u8> {
+        Digits::new(self.significand.to_num())
+    }
+
+    /// Returns the number of digits after the decimal point implied by the
+    /// exponent, i.e. the negated exponent: 2 for `12.50`, and -3 for
+    /// `1E+3`. NaN and Infinity have no scale.
+    ///
+    /// Together with `precision` this tells whether a value fits a SQL
+    /// column like DECIMAL(20,4) without rounding.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "12.50".parse().unwrap();
+    /// assert_eq!(Some(2), price.scale());
+    /// assert_eq!(Some(4), price.precision());
+    /// ```
+    pub fn scale(&self) -> Option<i32> {
+        if self.nan || self.inf {
+            return None;
+        }
+        Some(-(self.exponent.to_adjusted() as i32))
+    }
+
+    /// Returns the number of significant digits, which is the number of
+    /// digits in the coefficient; zero has one. NaN and Infinity have no
+    /// precision.
+    pub fn precision(&self) -> Option<u32> {
+        if self.nan || self.inf {
+            return None;
+        }
+        Some(self.digit_count())
+    }
+
+    /// The IEEE 754 radix operation: the base the value is encoded in,
+    /// which for decimal formats is always 10.
+    pub fn radix(&self) -> u32 {
+        10
+    }
+
+    /// Splits the value into its coefficient, exponent and sign, like the
+    /// old `f64::integer_decode`: the value is `sign * coefficient *
+    /// 10^exponent`, with a sign of 1 or -1. The coefficient isn't
+    /// normalized, so `12.50` decodes to 1250 and -2.
+    ///
+    /// NaN and Infinity have no exponent, and decode with exponent 0 and
+    /// their payload, which is 0 for Infinity, as the coefficient.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "-12.50".parse().unwrap();
+    /// assert_eq!((1250, -2, -1), price.integer_decode());
+    /// ```
+    pub fn integer_decode(&self) -> (u128, i16, i8) {
+        let sign = if self.sign { -1 } else { 1 };
+        if self.inf {
+            return (0, 0, sign);
+        }
+        if self.nan {
+            return (self.significand.to_num(), 0, sign);
+        }
+        (self.significand.to_num(), self.exponent.to_adjusted(), sign)
+    }
+
+    /// Changes the exponent so the value has `scale` digits after the
+    /// decimal point. Zeros are added to the coefficient when the scale
+    /// grows, and digits are rounded off with `mode` when it shrinks, so
+    /// the value stays the same whenever that's possible. NaN and Infinity
+    /// are returned unchanged.
+    ///
+    /// Fails with `ConversionError::Overflow` if the coefficient would need
+    /// more than 34 digits, and with `ConversionError::Invalid` if the
+    /// exponent `-scale` is outside -6176..=6111.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "12.345".parse().unwrap();
+    /// assert_eq!("12.34", price.rescale(2, RoundingMode::HalfEven).unwrap().to_string());
+    /// assert_eq!("12.35", price.rescale(2, RoundingMode::HalfUp).unwrap().to_string());
+    /// assert_eq!("12.3450", price.rescale(4, RoundingMode::HalfEven).unwrap().to_string());
+    /// ```
+    pub fn rescale(&self, scale: i32, mode: RoundingMode) -> Result<Decimal128, ConversionError> {
+        self.rescaled(scale, mode).map(|(rescaled, _)| rescaled)
+    }
 
-        // Everything else can have a sign. We can create a string from Infinity
-        // or a Finite number.
-        let str = if self.inf {
-            "Infinity".to_string()
+    // `rescale`, and whether rounding changed the value.
+    pub(crate) fn rescaled(
+        &self,
+        scale: i32,
+        mode: RoundingMode,
+    ) -> Result<(Decimal128, bool), ConversionError> {
+        if self.nan || self.inf {
+            return Ok((self.clone(), false));
+        }
+        let exponent = scale.checked_neg().ok_or(ConversionError::Invalid)?;
+        if exponent < MIN_EXPONENT as i32 || exponent > MAX_EXPONENT as i32 {
+            return Err(ConversionError::Invalid);
+        }
+        let current = self.exponent.to_adjusted() as i32;
+        let coefficient = self.significand.to_num();
+        let (coefficient, inexact) = if exponent < current {
+            // at most 34 digits fit, so larger shifts only work for zero
+            let shift = (current - exponent) as u32;
+            if coefficient == 0 {
+                (0, false)
+            } else if shift >= MAX_DIGITS {
+                return Err(ConversionError::Overflow);
+            } else {
+                (coefficient * pow10(shift), false)
+            }
         } else {
-            self.create_string()
+            let count = (exponent - current) as u32;
+            round_coefficient(self.sign, coefficient, count, mode)
         };
+        if coefficient >= pow10(MAX_DIGITS) {
+            return Err(ConversionError::Overflow);
+        }
+        let rescaled = Decimal128::from_parts(self.sign, exponent as i16, coefficient);
+        Ok((rescaled, inexact))
+    }
 
-        // add a sign if this is a negative number
-        return if !self.sign { str } else { format!("-{}", str) };
+    /// Rounds the value with `mode` to at most `n` significant digits,
+    /// unlike `rescale`, which rounds to a number of decimal places. Values
+    /// with `n` digits or fewer are returned unchanged, as are NaN and
+    /// Infinity. Rounding up can carry into another digit, which is then
+    /// dropped, so 9.96 to two digits is 10, not 10.0. Only the largest
+    /// values keep zeros in place of the dropped digits, because their
+    /// exponent can't go past 6111.
+    ///
+    /// Fails with `ConversionError::Invalid` if `n` is 0, and with
+    /// `ConversionError::Overflow` if rounding up takes the value past the
+    /// largest Decimal128.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let mass: Decimal128 = "0.0012345".parse().unwrap();
+    /// assert_eq!("0.00123", mass.round_sf(3, RoundingMode::HalfEven).unwrap().to_string());
+    /// let distance: Decimal128 = "149597870.7".parse().unwrap();
+    /// assert_eq!("1.50E+8", distance.round_sf(3, RoundingMode::HalfEven).unwrap().to_string());
+    /// ```
+    pub fn round_sf(&self, n: u32, mode: RoundingMode) -> Result<Decimal128, ConversionError> {
+        self.rounded_sf(n, mode).map(|(rounded, _)| rounded)
     }
 
-    /// Returns raw bytes.
-    pub fn to_raw_bytes(&self) -> [u8; 16] {
-        self.bytes
+    // `round_sf`, and whether rounding changed the value.
+    pub(crate) fn rounded_sf(
+        &self,
+        n: u32,
+        mode: RoundingMode,
+    ) -> Result<(Decimal128, bool), ConversionError> {
+        if n == 0 {
+            return Err(ConversionError::Invalid);
+        }
+        let digits = self.digit_count();
+        if self.nan || self.inf || digits <= n {
+            return Ok((self.clone(), false));
+        }
+        let count = digits - n;
+        let (mut coefficient, inexact) =
+            round_coefficient(self.sign, self.significand.to_num(), count, mode);
+        let mut exponent = self.exponent.to_adjusted() as i32 + count as i32;
+        if coefficient == pow10(n) {
+            coefficient /= 10;
+            exponent += 1;
+        }
+        if exponent > MAX_EXPONENT as i32 {
+            // the largest exponents only fit with zeros on the coefficient
+            let zeros = (exponent - MAX_EXPONENT as i32) as u32;
+            if coefficient >= pow10(MAX_DIGITS - zeros) {
+                return Err(ConversionError::Overflow);
+            }
+            coefficient *= pow10(zeros);
+            exponent = MAX_EXPONENT as i32;
+        }
+        let rounded = Decimal128::from_parts(self.sign, exponent as i16, coefficient);
+        Ok((rounded, inexact))
     }
 
-    fn create_string(&self) -> String {
-        if self.use_scientific_notation() {
-            let exp_sign = if self.exponent.to_adjusted() < 0 {
-                ""
-            } else {
-                "+"
-            };
-
-            if self.significand.as_digit_vec().len() > 1 {
-                let mut first_significand = self.significand.as_digit_vec().clone();
-                // we already used the first digit, so only stringify the
-                // remainder of the significand
-                let remainder_significand = stringify_vec(first_significand.split_off(1));
-                return format!(
-                    "{first_significand}.{remainder_significand}E{exp_sign}{scientific_exponent}",
-                    first_significand = first_significand[0],
-                    remainder_significand = remainder_significand,
-                    exp_sign = exp_sign,
-                    scientific_exponent = self.scientific_exponent()
-                );
-            } else {
-                return format!(
-                    "{significand}E{exp_sign}{scientific_exponent}",
-                    significand = self.significand.to_num(),
-                    exp_sign = exp_sign,
-                    scientific_exponent = self.scientific_exponent()
-                );
+    /// Creates the value `units` × 10^-scale, e.g. an amount of money from
+    /// a whole number of cents and a scale of 2. The result has exactly
+    /// `scale` digits after the decimal point, so `(100, 2)` is `1.00`.
+    ///
+    /// Fails with `ConversionError::Overflow` if `units` has more than 34
+    /// digits, and with `ConversionError::Invalid` if the exponent `-scale`
+    /// is outside -6176..=6111.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price = Decimal128::from_minor_units(12345, 2).unwrap();
+    /// assert_eq!("123.45", price.to_string());
+    /// assert_eq!("-0.05", Decimal128::from_minor_units(-5, 2).unwrap().to_string());
+    /// ```
+    pub fn from_minor_units(units: i128, scale: i32) -> Result<Decimal128, ConversionError> {
+        let exponent = scale.checked_neg().ok_or(ConversionError::Invalid)?;
+        if exponent < MIN_EXPONENT as i32 || exponent > MAX_EXPONENT as i32 {
+            return Err(ConversionError::Invalid);
+        }
+        if units.unsigned_abs() >= pow10(MAX_DIGITS) {
+            return Err(ConversionError::Overflow);
+        }
+        Ok(Decimal128::from_parts(
+            units < 0,
+            exponent as i16,
+            units.unsigned_abs(),
+        ))
+    }
+
+    /// The inverse of `from_minor_units`: returns the value as a whole
+    /// number of 10^-scale units, e.g. cents for a scale of 2, rounding any
+    /// digits past the scale with `mode`.
+    ///
+    /// Returns `ConversionError::Overflow` if the units don't fit in an
+    /// i128, and `ConversionError::NotFinite` for NaN and Infinity.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "19.999".parse().unwrap();
+    /// assert_eq!(Ok(2000), price.to_minor_units(2, RoundingMode::HalfEven));
+    /// assert_eq!(Ok(1999), price.to_minor_units(2, RoundingMode::Down));
+    /// ```
+    pub fn to_minor_units(&self, scale: i32, mode: RoundingMode) -> Result<i128, ConversionError> {
+        self.minor_units(scale, Some(mode))
+    }
+
+    /// Like `to_minor_units`, but without rounding: returns
+    /// `ConversionError::Inexact` if the value has more digits after the
+    /// decimal point than `scale` allows, unless they're all zeros.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "12.50".parse().unwrap();
+    /// assert_eq!(Ok(1250), price.to_minor_units_exact(2));
+    /// assert_eq!(Ok(125), price.to_minor_units_exact(1));
+    /// assert_eq!(Err(ConversionError::Inexact), price.to_minor_units_exact(0));
+    /// ```
+    pub fn to_minor_units_exact(&self, scale: i32) -> Result<i128, ConversionError> {
+        self.minor_units(scale, None)
+    }
+
+    // Convert to 10^-scale units, rounding with `mode` or failing with
+    // `Inexact` if there isn't one.
+    fn minor_units(&self, scale: i32, mode: Option<RoundingMode>) -> Result<i128, ConversionError> {
+        let units = self.unsigned_minor_units(scale, mode)?;
+        if self.sign {
+            if units > 1 << 127 {
+                return Err(ConversionError::Overflow);
             }
-        } else if self.exponent.to_adjusted() < 0 {
-            if self.significand.count_digits() > self.exponent.to_adjusted().abs() {
-                let dec_point = self.get_decimal_point_index() as usize;
-                let mut significand_vec = self.significand.as_digit_vec().clone();
-                let remainder_significand = stringify_vec(significand_vec.split_off(dec_point - 1));
-                return format!(
-                    "{first_significand}.{remainder_significand}",
-                    first_significand = significand_vec[0],
-                    remainder_significand = remainder_significand
-                );
-            } else {
-                let zero_pad = self.get_zero_padding();
-                return format!(
-                    "0.{zero_pad}{significand}",
-                    zero_pad = zero_pad,
-                    significand = self.significand.to_num()
-                );
+            Ok((units as i128).wrapping_neg())
+        } else {
+            if units > i128::MAX as u128 {
+                return Err(ConversionError::Overflow);
             }
+            Ok(units as i128)
         }
-        format!("{}", self.significand.to_num())
     }
 
-    fn use_scientific_notation(&self) -> bool {
-        (self.exponent.to_adjusted() as i16) > 0 || (self.scientific_exponent() as i16) < -6
+    // The magnitude of `minor_units`, which can use all 128 bits.
+    fn unsigned_minor_units(
+        &self,
+        scale: i32,
+        mode: Option<RoundingMode>,
+    ) -> Result<u128, ConversionError> {
+        if self.nan || self.inf {
+            return Err(ConversionError::NotFinite);
+        }
+        let coefficient = self.significand.to_num();
+        let shift = self.exponent.to_adjusted() as i64 + scale as i64;
+        let units = if coefficient == 0 {
+            0
+        } else if shift >= 0 {
+            // a u128 has at most 39 digits, so a longer shift can't fit
+            if shift > 38 {
+                return Err(ConversionError::Overflow);
+            }
+            coefficient
+                .checked_mul(pow10(shift as u32))
+                .ok_or(ConversionError::Overflow)?
+        } else {
+            let count = (-shift).min(u32::MAX as i64) as u32;
+            let rounding = mode.unwrap_or(RoundingMode::Down);
+            let (rounded, inexact) = round_coefficient(self.sign, coefficient, count, rounding);
+            if inexact && mode.is_none() {
+                return Err(ConversionError::Inexact);
+            }
+            rounded
+        };
+        Ok(units)
+    }
+
+    /// Splits the value into its integer part and its fractional part, which
+    /// add up to the value. Both keep the value's sign; the integer part has
+    /// exponent 0 (or the value's exponent, if that is positive), and the
+    /// fractional part keeps the value's exponent.
+    ///
+    /// NaN splits into two NaNs, and Infinity into Infinity and a zero.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let amount: Decimal128 = "-12.345".parse().unwrap();
+    /// let (integer, fraction) = amount.trunc_fract();
+    /// assert_eq!("-12", integer.to_string());
+    /// assert_eq!("-0.345", fraction.to_string());
+    /// ```
+    pub fn trunc_fract(&self) -> (Decimal128, Decimal128) {
+        if self.nan {
+            return (self.clone(), self.clone());
+        }
+        if self.inf {
+            return (self.clone(), Decimal128::from_parts(self.sign, 0, 0));
+        }
+        let exponent = self.exponent.to_adjusted();
+        if exponent >= 0 {
+            return (self.clone(), Decimal128::from_parts(self.sign, 0, 0));
+        }
+        let coefficient = self.significand.to_num();
+        // a coefficient has at most 34 digits, so it's all fraction when the
+        // exponent is smaller than that
+        let (integer, fraction) = if -exponent as u32 >= MAX_DIGITS {
+            (0, coefficient)
+        } else {
+            let divisor = pow10(-exponent as u32);
+            (coefficient / divisor, coefficient % divisor)
+        };
+        (
+            Decimal128::from_parts(self.sign, 0, integer),
+            Decimal128::from_parts(self.sign, exponent, fraction),
+        )
     }
 
-    fn scientific_exponent(&self) -> i16 {
-        // first variable is number of digits in a significand
-        (self.significand.count_digits() - 1) + self.exponent.to_adjusted()
+    /// Returns one unit in the last place of the value, `1E<exponent>`: the
+    /// smallest amount by which a value with this exponent can change. It is
+    /// always positive. The ulp of Infinity is Infinity, and of NaN is NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let value: Decimal128 = "-12.50".parse().unwrap();
+    /// assert_eq!("0.01", value.ulp().to_string());
+    /// ```
+    pub fn ulp(&self) -> Decimal128 {
+        if self.nan {
+            return self.clone();
+        }
+        if self.inf {
+            return self.with_sign(false);
+        }
+        Decimal128::from_parts(false, self.exponent.to_adjusted(), 1)
     }
 
-    // for larger numbers we want to know where to put the decimal point.
-    fn get_decimal_point_index(&self) -> i16 {
-        self.significand.count_digits() - self.exponent.to_adjusted().abs()
+    /// The IEEE 754 quantum operation: `1E<exponent>`, the unit a value with
+    /// this exponent is counted in. It is the same value as `ulp`, and
+    /// `rescale` is how to give another value the same quantum.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "19.90".parse().unwrap();
+    /// assert_eq!("0.01", price.quantum().to_string());
+    /// ```
+    pub fn quantum(&self) -> Decimal128 {
+        self.ulp()
     }
 
-    // for very small decimals, we need to know how many zeroes to pad it with.
-    fn get_zero_padding(&self) -> String {
-        let left_zero_pad_count =
-            (self.exponent.to_adjusted() + self.significand.count_digits()).abs();
-        std::iter::repeat("0")
-            .take(left_zero_pad_count as usize)
-            .collect::<String>()
+    /// The IEEE 754 sameQuantum predicate: whether both values have the same
+    /// exponent, whatever their coefficients and signs. Two NaNs or two
+    /// Infinities have the same quantum, and a special value never has the
+    /// same quantum as a finite one.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let a: Decimal128 = "1.50".parse().unwrap();
+    /// let b: Decimal128 = "-300.00".parse().unwrap();
+    /// let c: Decimal128 = "1.5".parse().unwrap();
+    /// assert!(a.same_quantum(&b));
+    /// assert!(!a.same_quantum(&c));
+    /// ```
+    pub fn same_quantum(&self, other: &Decimal128) -> bool {
+        if self.nan || other.nan {
+            return self.nan && other.nan;
+        }
+        if self.inf || other.inf {
+            return self.inf && other.inf;
+        }
+        self.exponent == other.exponent
     }
 
-    /// create a compare functiont that returns a decimal 128 that's either:
-    /// * -1 = less than
-    /// * 0 = equal
-    /// * 1 = greater than
-    /// When comparing and orderign Decimal128, we should end up with:
-    /// (-) NaN | -Infinity | x < 0 | -0 | +0 | x > 0 | +Infinity | (+) NaN
+    /// Returns the encoding in hexadecimal, most significant byte first, with a
+    /// space after every `bytes_per_group` bytes. This is how the IEEE 754
+    /// spec and server logs usually show decimal128 encodings. A group size
+    /// of 0 leaves out the spaces, like `{:x}` does.
+    /// ```
+    /// use decimal128::*;
     ///
-    /// Even though NaN can't be negative or positive, when reading the sign bit,
-    /// (-) NaN < (+) NaN
+    /// let mut vec = [0u8; 16];
+    /// vec[0] = 0x7c;
+    /// let nan = Decimal128::from_raw_bytes(vec);
+    /// assert_eq!(
+    ///     "7c00 0000 0000 0000 0000 0000 0000 0000",
+    ///     nan.to_grouped_hex(2)
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_grouped_hex(&self, bytes_per_group: usize) -> String {
+        let mut hex = String::with_capacity(47);
+        for (i, byte) in self.bytes.iter().enumerate() {
+            if bytes_per_group > 0 && i > 0 && i.is_multiple_of(bytes_per_group) {
+                hex.push(' ');
+            }
+            write!(hex, "{:02x}", byte).expect("writing to a String can't fail");
+        }
+        hex
+    }
+
+    /// Returns raw bytes.
+    pub fn to_raw_bytes(&self) -> [u8; 16] {
+        self.bytes
+    }
+
+    /// Decode a slice of back-to-back 16-byte encodings, in the byte order
+    /// `from_raw_bytes` takes. Returns `ConversionError::Invalid` if the
+    /// length of `bytes` isn't a multiple of 16.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let mut bytes = [0u8; 32];
+    /// bytes[0] = 0x30;
+    /// bytes[1] = 0x40;
+    /// bytes[15] = 0x01;
+    /// bytes[16] = 0x78;
+    /// let decoded = Decimal128::decode_slice(&bytes).unwrap();
+    /// assert_eq!("1", decoded[0].to_string());
+    /// assert_eq!("Infinity", decoded[1].to_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn decode_slice(bytes: &[u8]) -> Result<Vec<Decimal128>, ConversionError> {
+        Ok(Decimal128::decode_iter(bytes)?.collect())
+    }
+
+    /// Like `decode_slice`, but decodes lazily instead of collecting into a
+    /// Vec.
+    pub fn decode_iter(
+        bytes: &[u8],
+    ) -> Result<impl ExactSizeIterator<Item = Decimal128> + '_, ConversionError> {
+        if !bytes.len().is_multiple_of(16) {
+            return Err(ConversionError::Invalid);
+        }
+        Ok(bytes.chunks_exact(16).map(|chunk| {
+            let mut buffer = [0u8; 16];
+            buffer.copy_from_slice(chunk);
+            Decimal128::from_raw_bytes(buffer)
+        }))
+    }
+
+    /// Encode `values` back-to-back into the start of `out`, in the byte
+    /// order `to_raw_bytes` returns, and return the number of bytes written.
+    /// Returns `ConversionError::Invalid`, without writing anything, if `out`
+    /// is shorter than 16 bytes per value.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let values = [Decimal128::from(1), Decimal128::from(-5)];
+    /// let mut out = [0u8; 32];
+    /// assert_eq!(Ok(32), Decimal128::encode_slice(&values, &mut out));
+    /// assert_eq!(0xb0, out[16]);
+    /// ```
+    pub fn encode_slice(values: &[Decimal128], out: &mut [u8]) -> Result<usize, ConversionError> {
+        let len = values.len() * 16;
+        if out.len() < len {
+            return Err(ConversionError::Invalid);
+        }
+        for (chunk, value) in out[..len].chunks_exact_mut(16).zip(values) {
+            chunk.copy_from_slice(&value.bytes);
+        }
+        Ok(len)
+    }
+
+    // Write the to-scientific-string form of this Decimal128 into `out`, or
+    // the to-engineering-string form if `engineering` is set, without going
+    // through any intermediate Strings. The sign is left to the caller.
     //
-    // TODO: once we have a method to create Decimal128 from another number type
-    // (u32/i32/u128/i128), change this return type to be a Decimal128 as well.
-    pub fn compare(&self, other: &Decimal128) -> isize {
-        let self_exp = self.exponent.to_adjusted();
-        let other_exp = other.exponent.to_adjusted();
-        let self_signif = self.significand.to_num();
-        let other_signif = other.significand.to_num();
+    // Positional notation is used when the adjusted exponent is at least the
+    // first of `positional`, and either the exponent is at most 0 or the
+    // adjusted exponent is at most the second. The spec's range is (-6, 0),
+    // which writes every number with a positive exponent in scientific
+    // notation. Without a range, an exponent is always shown.
+    fn write_string<W: Write>(
+        &self,
+        out: &mut W,
+        engineering: bool,
+        positional: Option<(i32, i32)>,
+    ) -> fmt::Result {
+        if self.nan {
+            return out.write_str("NaN");
+        }
+        if self.inf {
+            return out.write_str("Infinity");
+        }
 
-        // NaN and Infinity will be ordered via the sign Check
-        if self.sign > other.sign {
-            -1
-        } else if self.sign < other.sign {
+        let mut digit_buf = [0u8; 39];
+        let digits = write_digits(self.significand.to_num(), &mut digit_buf);
+        let count = digits.len() as i32;
+        let exponent = self.exponent.to_adjusted() as i32;
+        // the exponent the number would have with one digit before the decimal
+        // point
+        let adjusted = exponent + count - 1;
+        // a zero with a positive exponent has no positional form that keeps
+        // the exponent, so it's always written like `0E+3`
+        let zero = self.significand.to_num() == 0;
+        let positional = positional.is_some_and(|(min, max)| {
+            adjusted >= min && (exponent <= 0 || (!zero && adjusted <= max))
+        });
+
+        // number of digits that go before the decimal point
+        let point = if positional {
+            count + exponent
+        } else if !engineering {
             1
+        } else if self.significand.to_num() == 0 {
+            // a zero keeps its exponent by moving the decimal point left,
+            // which pads it with zeros after the point
+            (adjusted + 2).rem_euclid(3) - 1
         } else {
-            // since 1x10^3 is the same number as 10x10^2, we want to try to
-            // even out the exponents before comparing significands.
-            let exp_dif = (self_exp - other_exp).abs();
-            // however, if the difference is greeater than 66, they are
-            // definitely diffferent numbers. so we only try to mingle with
-            // exponents if the difference is less than 66.
-            if exp_dif <= 66 {
-                if self_exp < other_exp {
-                    Decimal128::increase_exponent(self_signif, self_exp, other_exp);
-                    Decimal128::decrease_exponent(other_signif, other_exp, self_exp);
-                } else if self_exp > other_exp {
-                    Decimal128::decrease_exponent(self_signif, self_exp, other_exp);
-                    Decimal128::increase_exponent(other_signif, other_exp, self_exp);
-                }
+            adjusted.rem_euclid(3) + 1
+        };
+
+        if point <= 0 {
+            // for very small decimals, we need to pad with zeros after the
+            // decimal point.
+            out.write_str("0.")?;
+            for _ in 0..-point {
+                out.write_char('0')?;
             }
-            if self_exp == other_exp {
-                if self_signif > other_signif {
-                    1
-                } else if self_signif < other_signif {
-                    -1
-                } else {
-                    0
-                }
-            } else {
-                if self_exp > other_exp {
-                    1
-                } else if self_exp < other_exp {
-                    -1
-                } else {
-                    0
-                }
+            out.write_str(digits)?;
+        } else if point >= count {
+            out.write_str(digits)?;
+            for _ in 0..point - count {
+                out.write_char('0')?;
+            }
+        } else {
+            out.write_str(&digits[..point as usize])?;
+            out.write_char('.')?;
+            out.write_str(&digits[point as usize..])?;
+        }
+
+        // engineering notation leaves out `E+0`, like positional notation
+        let shown_exponent = adjusted + 1 - point;
+        if !positional && (shown_exponent != 0 || !engineering) {
+            let sign = if shown_exponent < 0 { "E-" } else { "E+" };
+            let mut exponent_buf = [0u8; 39];
+            out.write_str(sign)?;
+            out.write_str(write_digits(
+                shown_exponent.unsigned_abs() as u128,
+                &mut exponent_buf,
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Compares the values the way `total_cmp` does and returns -1, 0 or 1.
+    ///
+    /// This puts `-0` below `0` and orders NaN by its sign bit, which isn't
+    /// the specification's compare: use `compare_decimal` or `partial_cmp`
+    /// to compare numerically, or `total_cmp` for an `Ordering` that sorts
+    /// every value.
+    #[deprecated(
+        since = "0.1.0",
+        note = "use `compare_decimal` or `partial_cmp` to compare numerically, or `total_cmp`"
+    )]
+    pub fn compare(&self, other: &Decimal128) -> isize {
+        self.total_cmp(other) as isize
+    }
+
+    /// The `compare` operation from the decimal arithmetic specification:
+    /// compares the values numerically and returns `-1` if `self` is less
+    /// than `other`, `0` if they are equal and `1` if it is greater. Like
+    /// `partial_cmp`, `-0` equals `0` and `1.0` equals `1.00`.
+    ///
+    /// If either value is NaN the result is NaN, keeping the sign and
+    /// payload of the first signaling NaN operand, or of the first NaN if
+    /// neither is signaling.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("-1", d("2.1").compare_decimal(&d("3")).to_string());
+    /// assert_eq!("0", d("2.10").compare_decimal(&d("2.1")).to_string());
+    /// assert_eq!("NaN", d("NaN").compare_decimal(&d("3")).to_string());
+    /// ```
+    pub fn compare_decimal(&self, other: &Decimal128) -> Decimal128 {
+        match self.numeric_cmp(other) {
+            Some(Ordering::Less) => Decimal128::from(-1),
+            Some(Ordering::Equal) => Decimal128::from(0),
+            Some(Ordering::Greater) => Decimal128::from(1),
+            None => {
+                let signaling = |v: &Decimal128| v.nan && v.bytes[0] & 0x02 != 0;
+                let first = self.nan && (signaling(self) || !signaling(other));
+                quiet_nan(if first { self } else { other })
             }
         }
     }
 
-    // This is part of the effort to compare two different Decimal128 numbers.
-    fn increase_exponent(mut significand: u128, mut exponent: i16, goal: i16) {
-        if significand == 0 as u128 {
-            exponent = goal
+    /// The `compare-total` operation from the decimal arithmetic
+    /// specification, with `Less`, `Equal` and `Greater` for its -1, 0 and 1.
+    /// Unlike `total_cmp` it tells apart every encoding with a different
+    /// sign, exponent or payload, so it's only `Equal` for identical values:
+    ///
+    /// -NaN < -sNaN < -Infinity < negative numbers < -0 < +0 < positive
+    /// numbers < +Infinity < +sNaN < +NaN
+    ///
+    /// Numerically equal values are ordered by exponent, the smaller one
+    /// first for positive values and last for negative ones, and NaNs of
+    /// the same kind by payload.
+    /// ```
+    /// use decimal128::*;
+    /// use std::cmp::Ordering;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!(Ordering::Less, d("12.30").compare_total(&d("12.3")));
+    /// assert_eq!(Ordering::Greater, d("-12.30").compare_total(&d("-12.3")));
+    /// assert_eq!(Ordering::Greater, d("0").compare_total(&d("-0")));
+    /// ```
+    pub fn compare_total(&self, other: &Decimal128) -> Ordering {
+        match (self.sign, other.sign) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.compare_total_mag(other),
+            (true, true) => other.compare_total_mag(self),
         }
+    }
 
-        while exponent < goal {
-            let significand_divided_by_10 = significand / 10;
-            if significand % 10 != 0 {
-                break;
+    /// The `compare-total-magnitude` operation: orders the absolute values
+    /// like `compare_total` does, so `-NaN` and `NaN` are `Equal` and `-2`
+    /// is greater than `1`.
+    /// ```
+    /// use decimal128::*;
+    /// use std::cmp::Ordering;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!(Ordering::Greater, d("-2").compare_total_mag(&d("1")));
+    /// assert_eq!(Ordering::Less, d("-12.30").compare_total_mag(&d("12.3")));
+    /// ```
+    pub fn compare_total_mag(&self, other: &Decimal128) -> Ordering {
+        // NaNs rank by kind, quiet above signaling, and then by payload
+        let rank = |v: &Decimal128| match (v.nan, v.bytes[0] & 0x02 != 0) {
+            (true, true) => 1,
+            (true, false) => 2,
+            _ => 0,
+        };
+        if rank(self) != 0 || rank(other) != 0 {
+            return (rank(self), self.significand.to_num())
+                .cmp(&(rank(other), other.significand.to_num()));
+        }
+        self.compare_magnitude(other).then_with(|| {
+            if self.inf {
+                Ordering::Equal
+            } else {
+                self.exponent
+                    .to_adjusted()
+                    .cmp(&other.exponent.to_adjusted())
             }
-            exponent += 1;
-            significand = significand_divided_by_10
+        })
+    }
+
+    /// Orders values by sign first and then by magnitude, which puts every
+    /// value somewhere, unlike `partial_cmp`:
+    ///
+    /// -NaN < -Infinity < negative numbers < -0 < +0 < positive numbers <
+    /// +Infinity < +NaN
+    ///
+    /// Numerically equal values with different exponents, like `1.0` and
+    /// `1.00`, are still `Equal`. This makes it suitable for `sort_by` on
+    /// values that may include NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let mut values: Vec<Decimal128> = ["NaN", "1", "-0", "0", "-Infinity"]
+    ///     .iter()
+    ///     .map(|s| s.parse().unwrap())
+    ///     .collect();
+    /// values.sort_by(|a, b| a.total_cmp(b));
+    /// let strings: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    /// assert_eq!(vec!["-Infinity", "-0", "0", "1", "NaN"], strings);
+    /// ```
+    pub fn total_cmp(&self, other: &Decimal128) -> Ordering {
+        // NaN and Infinity will be ordered via the sign check
+        match (self.sign, other.sign) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // for negative numbers the larger magnitude is the smaller number
+            (true, true) => other.compare_magnitude(self),
+            (false, false) => self.compare_magnitude(other),
         }
     }
 
-    // This is part of the effort to compare two different Decimal128 numbers.
-    fn decrease_exponent(mut significand: u128, mut exponent: i16, goal: i16) {
-        if significand == 0 as u128 {
-            exponent = goal
+    /// Restricts the value to the range `min..=max`: returns `min` if the
+    /// value is less than it, `max` if the value is greater, and the value
+    /// itself otherwise. Values are compared numerically, so `-0` and `0`
+    /// are equal and `1.0` is within `1..=2` and comes back as `1.0`. NaN
+    /// comes back as NaN.
+    ///
+    /// Panics if `min` is greater than `max`, or if either of them is NaN,
+    /// like `f64::clamp` does.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("0.00", d("-3.5").clamp(d("0.00"), d("100")).to_string());
+    /// assert_eq!("42.5", d("42.5").clamp(d("0.00"), d("100")).to_string());
+    /// ```
+    pub fn clamp(self, min: Decimal128, max: Decimal128) -> Decimal128 {
+        assert!(
+            matches!(
+                min.numeric_cmp(&max),
+                Some(Ordering::Less) | Some(Ordering::Equal)
+            ),
+            "clamp needs min <= max, and neither can be NaN"
+        );
+        match (self.numeric_cmp(&min), self.numeric_cmp(&max)) {
+            (Some(Ordering::Less), _) => min,
+            (_, Some(Ordering::Greater)) => max,
+            _ => self,
         }
+    }
 
-        while exponent > goal {
-            let significand_times_10 = significand * 10;
-            if significand_times_10 - Significand::max_value() > 0 {
-                break;
+    // Compare absolute values, with Infinity above every finite number and NaN
+    // above Infinity.
+    fn compare_magnitude(&self, other: &Decimal128) -> Ordering {
+        let rank = |v: &Decimal128| {
+            if v.nan {
+                2
+            } else if v.inf {
+                1
+            } else {
+                0
             }
-            exponent -= 1;
-            significand = significand_times_10
+        };
+        if rank(self) != 0 || rank(other) != 0 {
+            return rank(self).cmp(&rank(other));
+        }
+
+        let self_signif = self.significand.to_num();
+        let other_signif = other.significand.to_num();
+        if self_signif == 0 || other_signif == 0 {
+            return self_signif.cmp(&other_signif);
+        }
+
+        // since 1x10^3 is the same number as 10x10^2, compare the position of
+        // the most significant digit first, and only then line up the
+        // exponents to compare significands.
+        let self_exp = self.exponent.to_adjusted();
+        let other_exp = other.exponent.to_adjusted();
+        let self_top = self.significand.count_digits() + self_exp;
+        let other_top = other.significand.count_digits() + other_exp;
+        if self_top != other_top {
+            return self_top.cmp(&other_top);
+        }
+        // the most significant digits line up, so the exponents are at most
+        // 33 apart and the shifted significand still has at most 34 digits.
+        if self_exp > other_exp {
+            (self_signif * pow10((self_exp - other_exp) as u32)).cmp(&other_signif)
+        } else {
+            self_signif.cmp(&(other_signif * pow10((other_exp - self_exp) as u32)))
         }
     }
+
+    // Compare numerically: NaN isn't ordered, and zeros are equal whatever
+    // their signs.
+    fn numeric_cmp(&self, other: &Decimal128) -> Option<Ordering> {
+        if self.nan || other.nan {
+            return None;
+        }
+        if self.is_zero() && other.is_zero() {
+            return Some(Ordering::Equal);
+        }
+        Some(self.total_cmp(other))
+    }
 }
 
+/// Formats Decimal128 as a string, which is also what `to_string` returns. Uses
+/// information in [speleotrove](http://speleotrove.com/decimal/daconvs.html)
+/// decimal documentation.
+///
+/// Width, fill, alignment and the `+` and `0` flags work like they do for
+/// `f64`: numbers are right-aligned by default, and NaN never gets a sign.
+/// ```
+/// use decimal128::*;
+///
+/// let dec128 = Decimal128::from(-42);
+/// assert_eq!("     -42", format!("{:>8}", dec128));
+/// assert_eq!("-0000042", format!("{:08}", dec128));
+/// assert_eq!("+42", format!("{:+}", Decimal128::from(42)));
+/// ```
+///
+/// A precision rounds the value to that many digits after the decimal point,
+/// with ties going to the even neighbour, and always uses positional notation.
+/// NaN and Infinity ignore it.
+/// ```
+/// use decimal128::*;
+///
+/// let dec128 = Decimal128::from_mysql_decimal("2.675", RoundingMode::HalfEven).unwrap();
+/// assert_eq!("2.68", format!("{:.2}", dec128));
+/// assert_eq!("2.67500", format!("{:.5}", dec128));
+/// assert_eq!("3", format!("{:.0}", dec128));
+/// ```
 impl fmt::Display for Decimal128 {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "{}", self.to_string())
+        if let (Some(precision), false, false) = (fmt.precision(), self.nan, self.inf) {
+            return self.fmt_fixed(fmt, precision, 0);
+        }
+        let mut buf = StrBuf::new();
+        self.write_string(&mut buf, false, Some(POSITIONAL_EXPONENTS))?;
+        pad_number(fmt, self.format_sign(fmt), &[Part::Str(buf.as_str())])
+    }
+}
+
+impl Decimal128 {
+    // The sign a formatted Decimal128 starts with: `-` for negative numbers,
+    // and `+` for the rest if the Formatter asks for it. NaN never gets a
+    // sign.
+    fn format_sign(&self, fmt: &fmt::Formatter) -> &'static str {
+        if self.nan {
+            ""
+        } else if self.sign {
+            "-"
+        } else if fmt.sign_plus() {
+            "+"
+        } else {
+            ""
+        }
+    }
+
+    // Format a finite Decimal128 positionally with exactly `precision` digits
+    // after the decimal point, rounding half-even if it has more. Runs of
+    // zeros are written as they are padded, so large exponents and
+    // precisions don't need a buffer. The value is multiplied by 10^`shift`
+    // first, which can't overflow here.
+    fn fmt_fixed(&self, fmt: &mut fmt::Formatter, precision: usize, shift: i64) -> fmt::Result {
+        let mut coefficient = self.significand.to_num();
+        let mut exponent = self.exponent.to_adjusted() as i64 + shift;
+        let target = -(precision.min(i64::MAX as usize) as i64);
+        if exponent < target {
+            let count = (target - exponent).min(u32::MAX as i64) as u32;
+            coefficient =
+                round_coefficient(self.sign, coefficient, count, RoundingMode::HalfEven).0;
+            exponent = target;
+        }
+        if coefficient == 0 {
+            // a zero has no integer digits to pad, so 0E+3 is written as 0
+            // rather than 0000
+            exponent = exponent.min(0);
+        }
+
+        let mut digit_buf = [0u8; 39];
+        let digits = write_digits(coefficient, &mut digit_buf);
+        // digits of the coefficient that go after the decimal point
+        let fraction_digits = (-exponent).max(0) as usize;
+        let trailing_zeros = precision - fraction_digits;
+        let point = if precision > 0 { "." } else { "" };
+
+        let parts = if exponent >= 0 {
+            [
+                Part::Str(digits),
+                Part::Zeros(exponent as usize),
+                Part::Str(point),
+                Part::Zeros(precision),
+                Part::Str(""),
+            ]
+        } else if digits.len() > fraction_digits {
+            let (integer, fraction) = digits.split_at(digits.len() - fraction_digits);
+            [
+                Part::Str(integer),
+                Part::Str(point),
+                Part::Str(fraction),
+                Part::Zeros(trailing_zeros),
+                Part::Str(""),
+            ]
+        } else {
+            [
+                Part::Str("0"),
+                Part::Str(point),
+                Part::Zeros(fraction_digits - digits.len()),
+                Part::Str(digits),
+                Part::Zeros(trailing_zeros),
+            ]
+        };
+        pad_number(fmt, self.format_sign(fmt), &parts)
+    }
+
+    // Format with one digit before the decimal point and an exponent after
+    // `marker`, for LowerExp and UpperExp.
+    fn fmt_exp(&self, fmt: &mut fmt::Formatter, marker: &str) -> fmt::Result {
+        if self.nan || self.inf {
+            let body = if self.nan { "NaN" } else { "Infinity" };
+            return pad_number(fmt, self.format_sign(fmt), &[Part::Str(body)]);
+        }
+
+        let mut coefficient = self.significand.to_num();
+        let mut count = digit_count(coefficient) as usize;
+        let mut exponent = self.exponent.to_adjusted() as i32 + (count as i32 - 1);
+        let mut trailing_zeros = 0;
+        match fmt.precision() {
+            Some(precision) if precision < count - 1 => {
+                let dropped = (count - 1 - precision) as u32;
+                coefficient =
+                    round_coefficient(self.sign, coefficient, dropped, RoundingMode::HalfEven).0;
+                count = precision + 1;
+                // rounding 9.99 up gives 10.0, one digit too many
+                if digit_count(coefficient) as usize > count {
+                    coefficient /= 10;
+                    exponent += 1;
+                }
+            }
+            Some(precision) => trailing_zeros = precision - (count - 1),
+            None => {}
+        }
+
+        let mut digit_buf = [0u8; 39];
+        let digits = write_digits(coefficient, &mut digit_buf);
+        let mut exponent_buf = [0u8; 39];
+        let exponent_digits = write_digits(exponent.unsigned_abs() as u128, &mut exponent_buf);
+        let (first, rest) = digits.split_at(1);
+        let point = if rest.is_empty() && trailing_zeros == 0 {
+            ""
+        } else {
+            "."
+        };
+        let exponent_sign = if exponent < 0 { "-" } else { "" };
+
+        let parts = [
+            Part::Str(first),
+            Part::Str(point),
+            Part::Str(rest),
+            Part::Zeros(trailing_zeros),
+            Part::Str(marker),
+            Part::Str(exponent_sign),
+            Part::Str(exponent_digits),
+        ];
+        pad_number(fmt, self.format_sign(fmt), &parts)
     }
 }
 
@@ -459,52 +1586,136 @@ impl fmt::Debug for Decimal128 {
     }
 }
 
+/// Orders values numerically, like `f64` does: `-0` and `0` are equal, and
+/// NaN isn't ordered with anything. Use `total_cmp` for an ordering that
+/// includes NaN.
 impl PartialOrd<Decimal128> for Decimal128 {
     fn partial_cmp(&self, other: &Decimal128) -> Option<Ordering> {
-        match self.compare(other) {
-            v if v == 0 => Some(Ordering::Equal),
-            v if v > 0 => Some(Ordering::Greater),
-            v if v < 0 => Some(Ordering::Less),
-            _ => None,
-        }
+        self.numeric_cmp(other)
     }
 }
 
+/// Values are equal when they're numerically equal, whatever their
+/// exponents, so `1.0 == 1.00` and `-0 == 0`. Like `f64`, NaN isn't equal to
+/// anything, not even itself.
 impl PartialEq<Decimal128> for Decimal128 {
     fn eq(&self, other: &Decimal128) -> bool {
-        self.compare(other) == 0
+        self.numeric_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+/// Hashes values that compare equal the same way, so `1.0` and `1.00`, or
+/// `-0` and `0`, hash alike. Finite values hash their coefficient with the
+/// trailing zeros stripped, together with the exponent that leaves them.
+impl Hash for Decimal128 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.nan {
+            // NaN isn't equal to anything, so any hash would do
+            state.write_u8(2);
+        } else if self.inf {
+            state.write_u8(1);
+            self.sign.hash(state);
+        } else if self.is_zero() {
+            state.write_u8(0);
+        } else {
+            let mut coefficient = self.significand.to_num();
+            let mut exponent = self.exponent.to_adjusted();
+            while coefficient.is_multiple_of(10) {
+                coefficient /= 10;
+                exponent += 1;
+            }
+            state.write_u8(0);
+            self.sign.hash(state);
+            coefficient.hash(state);
+            exponent.hash(state);
+        }
     }
 }
 
-/// Format Decimal128 as an engineering string
-/// TODO: this currently only uses the default to_string method for Decimal128
-/// and needs to actually do the engineering string formatting.
+/// Formats Decimal128 in scientific notation with one digit before the decimal
+/// point and a lowercase `e`, like `f64` does. All the digits of the
+/// coefficient are kept, so `5.00` formats as `5.00e0`. A precision rounds the
+/// mantissa half-even to that many digits after the decimal point.
+/// ```
+/// use decimal128::*;
+///
+/// let dec128 = Decimal128::from_mysql_decimal("-1234.50", RoundingMode::HalfEven).unwrap();
+/// assert_eq!("-1.23450e3", format!("{:e}", dec128));
+/// assert_eq!("-1.2e3", format!("{:.1e}", dec128));
+/// ```
 impl fmt::LowerExp for Decimal128 {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(self, fmt)
+        self.fmt_exp(fmt, "e")
+    }
+}
+
+/// Formats Decimal128 like LowerExp, with an uppercase `E`.
+/// ```
+/// use decimal128::*;
+///
+/// let dec128 = Decimal128::from_mysql_decimal("0.00012", RoundingMode::HalfEven).unwrap();
+/// assert_eq!("1.2E-4", format!("{:E}", dec128));
+/// ```
+impl fmt::UpperExp for Decimal128 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_exp(fmt, "E")
     }
 }
-/// Formats Decimal128 to hexadecimal binary representation.
+
+/// Formats Decimal128 to hexadecimal binary representation: all 32 digits of
+/// the encoding, most significant first. The alternate flag adds a `0x`
+/// prefix, and width and fill work like they do for integers.
+/// ```
+/// use decimal128::*;
+///
+/// let mut vec = [0u8; 16];
+/// vec[0] = 0x7c;
+/// let nan = Decimal128::from_raw_bytes(vec);
+/// assert_eq!("7c000000000000000000000000000000", format!("{:x}", nan));
+/// assert_eq!("0x7c000000000000000000000000000000", format!("{:#x}", nan));
+/// ```
 impl fmt::LowerHex for Decimal128 {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        for b in self.bytes.iter().rev() {
-            write!(fmt, "{:02x}", b)?;
-        }
-        Ok(())
+        let mut buf = [0u8; 128];
+        let digits = write_radix(
+            u128::from_be_bytes(self.bytes),
+            4,
+            b"0123456789abcdef",
+            &mut buf,
+        );
+        fmt.pad_integral(true, "0x", digits)
+    }
+}
+
+/// Formats Decimal128 like LowerHex, with uppercase digits.
+impl fmt::UpperHex for Decimal128 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; 128];
+        let digits = write_radix(
+            u128::from_be_bytes(self.bytes),
+            4,
+            b"0123456789ABCDEF",
+            &mut buf,
+        );
+        fmt.pad_integral(true, "0x", digits)
+    }
+}
+
+/// Formats Decimal128 to binary representation: all 128 bits of the encoding,
+/// starting with the sign bit. The alternate flag adds a `0b` prefix.
+impl fmt::Binary for Decimal128 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; 128];
+        let digits = write_radix(u128::from_be_bytes(self.bytes), 1, b"01", &mut buf);
+        fmt.pad_integral(true, "0b", digits)
     }
 }
 
 /// Exponent is a 14-bit portion of decimal128 that follows the sign bit. Here we
-/// are storing it as a 16-bit BitVec that can be later converted to a u16.
+/// are storing it as a u16 with the two most significant bits unset.
 impl Exponent {
     pub fn new() -> Self {
-        Exponent {
-            vec: bitvec![BigEndian, u8; 0; 2],
-        }
-    }
-
-    pub fn append(&mut self, vec: &mut BitVec) {
-        self.vec.append(vec)
+        Exponent { num: 0 }
     }
 
     pub fn is_zero(&self) -> bool {
@@ -512,69 +1723,291 @@ impl Exponent {
     }
 
     pub fn to_num(&self) -> u16 {
-        let mut reader = Cursor::new(&self.vec);
-        reader.read_u16::<byteorder::BigEndian>().unwrap()
+        self.num
     }
 
     // compare current exponent value with exponent bias (largest possible
     // exponent value)
-    // TODO: check if 6176 (exponent bias) can be stored as u16
     pub fn to_adjusted(&self) -> i16 {
-        self.to_num() as i16 - 6176 as i16
+        self.to_num() as i16 - EXPONENT_BIAS
     }
 }
 
-/// Significand is a padded 111- or 113-bit coefficient. We are storing it as a
-/// 128-bit BitVec with the padded difference. This can be converted to a u128.
+/// Significand is a 111- or 113-bit coefficient, with the implicit leading bits
+/// of the 111-bit form filled in. We are storing it as a u128.
 impl Significand {
     pub fn new() -> Self {
-        Significand {
-            vec: bitvec![BigEndian, u8; 0; 14],
-        }
-    }
-
-    pub fn append(&mut self, vec: &mut BitVec) {
-        self.vec.append(vec)
+        Significand { num: 0 }
     }
 
     pub fn is_zero(&self) -> bool {
-        // FIXME: Very inefficient, but docs are down
-        self.count_digits() == 0
+        self.to_num() == 0
     }
 
+    // Coefficients larger than 34 digits are non-canonical, and the spec
+    // treats them as zero.
     pub fn to_num(&self) -> u128 {
-        let mut reader = Cursor::new(&self.vec);
-        reader.read_u128::<byteorder::BigEndian>().unwrap()
+        if self.num > Significand::max_value() {
+            0
+        } else {
+            self.num
+        }
     }
 
     pub fn max_value() -> u128 {
-        u128::from_str_radix("9999999999999999999999999999999999", 10).unwrap()
+        pow10(MAX_DIGITS) - 1
     }
 
-    // count the number of digits in the significand. This method first converts
-    // significand BitVec into a u128 number, then converts it to string to
-    // count characters and collects them in a vec to look at the vec's length.
+    // count the number of digits in the significand.
     //
-    // We return a u16 number of digits, as it's easier to compare to the
-    // exponent since that's also stored as a u16.
+    // We return a i16 number of digits, as it's easier to compare to the
+    // adjusted exponent since that's also an i16.
     fn count_digits(&self) -> i16 {
-        self.as_digit_vec().len() as i16
+        Digits::new(self.to_num()).len() as i16
     }
+}
+
+// The decimal digits of a u128, most significant first, worked out one at a
+// time by dividing by the place value of the next digit.
+struct Digits {
+    num: u128,
+    place: u128,
+    remaining: usize,
+}
 
-    fn as_digit_vec(&self) -> Vec<u32> {
-        let digits: Vec<u32> = self
-            .to_num()
-            .to_string()
-            .chars()
-            .map(|c| c.to_digit(10).unwrap())
-            .collect();
-        return digits;
+impl Digits {
+    fn new(num: u128) -> Self {
+        let count = digit_count(num);
+        Digits {
+            num,
+            place: 10u128.pow(count - 1),
+            remaining: count as usize,
+        }
     }
 }
 
-fn stringify_vec(vec: Vec<u32>) -> String {
-    vec.into_iter()
-        .map(|d| d.to_string())
-        .collect::<Vec<String>>()
-        .join("")
+impl Iterator for Digits {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let digit = (self.num / self.place) as u8;
+        self.num %= self.place;
+        self.place /= 10;
+        self.remaining -= 1;
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+// Number of decimal digits in `num`, counting 0 as one digit.
+fn digit_count(num: u128) -> u32 {
+    match num.checked_ilog10() {
+        Some(log) => log + 1,
+        None => 1,
+    }
+}
+
+// Encode Infinity or NaN (`bits` is INFINITY or NAN, or for parsing a
+// signaling NaN) with a sign and, for NaN, a payload.
+fn special(sign: bool, bits: u128, payload: u128) -> Decimal128 {
+    let bits = ((sign as u128) << 127) | bits | payload;
+    Decimal128::from_raw_bytes(bits.to_be_bytes())
+}
+
+// A NaN result for an operation with NaN operand `v`: a quiet NaN with the
+// same sign and payload.
+fn quiet_nan(v: &Decimal128) -> Decimal128 {
+    special(v.sign, NAN, v.significand.to_num())
+}
+
+// 10^exp as a u128. Only valid for exp <= 38.
+const fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
+}
+
+// Drop `count` digits from the end of a coefficient, rounding what's left with
+// `mode`. Also returns whether any of the dropped digits were non-zero.
+fn round_coefficient(
+    sign: bool,
+    coefficient: u128,
+    count: u32,
+    mode: RoundingMode,
+) -> (u128, bool) {
+    if count == 0 {
+        return (coefficient, false);
+    }
+    // a u128 has at most 39 digits, so anything past that only tells us
+    // whether the coefficient was zero
+    let (kept, digit, sticky, rest) = if count > 39 {
+        (0, 0, coefficient != 0, coefficient)
+    } else if count == 39 {
+        let divisor = pow10(38);
+        (
+            0,
+            (coefficient / divisor) as u8,
+            !coefficient.is_multiple_of(divisor),
+            coefficient % divisor,
+        )
+    } else {
+        let divisor = pow10(count - 1);
+        let kept = coefficient / divisor / 10;
+        let digit = (coefficient / divisor % 10) as u8;
+        (
+            kept,
+            digit,
+            !coefficient.is_multiple_of(divisor),
+            coefficient % divisor,
+        )
+    };
+    // the digits after `digit` are `rest` padded with zeros to `count - 1`
+    let rest_digits = Digits::new(rest);
+    let zeros = (count as usize - 1).saturating_sub(rest_digits.len());
+    let rest_digits = core::iter::repeat_n(0, zeros).chain(rest_digits);
+    let rounded = if mode.rounds_up(sign, kept % 2 == 1, digit, sticky, rest_digits) {
+        kept + 1
+    } else {
+        kept
+    };
+    (rounded, digit != 0 || sticky)
+}
+
+// A piece of a formatted number: either text, or a run of zeros that is
+// written out without needing a buffer to hold it.
+enum Part<'a> {
+    Str(&'a str),
+    Zeros(usize),
+}
+
+impl Part<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Part::Str(s) => s.len(),
+            Part::Zeros(n) => *n,
+        }
+    }
+
+    fn write(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::Str(s) => fmt.write_str(s),
+            Part::Zeros(n) => write_repeated(fmt, '0', *n),
+        }
+    }
+}
+
+// Write a formatted number to `fmt`, honoring its width, fill, alignment and
+// `0` flag the way the float formatters do. With the `0` flag, zeros go
+// between the sign and the rest of the number.
+fn pad_number(fmt: &mut fmt::Formatter, sign: &str, parts: &[Part]) -> fmt::Result {
+    let len = sign.len() + parts.iter().map(Part::len).sum::<usize>();
+    let padding = fmt.width().map_or(0, |width| width.saturating_sub(len));
+
+    let (before, after) = if fmt.sign_aware_zero_pad() {
+        (0, 0)
+    } else {
+        match fmt.align() {
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(fmt::Alignment::Right) | None => (padding, 0),
+        }
+    };
+    let fill = fmt.fill();
+    write_repeated(fmt, fill, before)?;
+    fmt.write_str(sign)?;
+    if fmt.sign_aware_zero_pad() {
+        write_repeated(fmt, '0', padding)?;
+    }
+    for part in parts {
+        part.write(fmt)?;
+    }
+    write_repeated(fmt, fill, after)
+}
+
+fn write_repeated(fmt: &mut fmt::Formatter, c: char, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        fmt.write_char(c)?;
+    }
+    Ok(())
+}
+
+// Write the decimal digits of `num` as ASCII into the end of `buf`, and return
+// the part of `buf` holding them. A u128 has at most 39 digits.
+fn write_digits(num: u128, buf: &mut [u8; 39]) -> &str {
+    let digits = Digits::new(num);
+    let start = buf.len() - digits.len();
+    for (b, digit) in buf[start..].iter_mut().zip(digits) {
+        *b = b'0' + digit;
+    }
+    // only ASCII digits are ever written
+    core::str::from_utf8(&buf[start..]).unwrap()
+}
+
+// Write every digit of `bits` in a power-of-two radix, `bits_per_digit` bits
+// per digit, most significant first. Returns the part of `buf` holding them.
+fn write_radix<'a>(
+    mut bits: u128,
+    bits_per_digit: u32,
+    alphabet: &[u8],
+    buf: &'a mut [u8; 128],
+) -> &'a str {
+    let start = buf.len() - (128 / bits_per_digit) as usize;
+    let mask = (1 << bits_per_digit) - 1;
+    for byte in buf[start..].iter_mut().rev() {
+        *byte = alphabet[(bits & mask) as usize];
+        bits >>= bits_per_digit;
+    }
+    // only ASCII digits are ever written
+    core::str::from_utf8(&buf[start..]).unwrap()
+}
+
+// The smallest adjusted exponent `to_string` writes without an exponent, and
+// the largest one it does for numbers with a positive exponent, which is
+// none of them.
+const POSITIONAL_EXPONENTS: (i32, i32) = (-6, 0);
+
+/// A buffer length in bytes that every string `to_string` produces fits in.
+/// The longest strings are a sign, 34 digits, a decimal point and an
+/// exponent like `E-6143`, or a sign and `0.00000` in front of 34 digits.
+/// A buffer this long always fits `format_into`.
+pub const MAX_STRING_LEN: usize = 43;
+
+// A fixed-size buffer that strings are formatted into before they are written
+// to a Formatter, so formatting doesn't allocate.
+#[derive(Clone, Copy)]
+struct StrBuf {
+    bytes: [u8; MAX_STRING_LEN],
+    len: usize,
+}
+
+impl StrBuf {
+    fn new() -> Self {
+        StrBuf {
+            bytes: [0u8; MAX_STRING_LEN],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // only whole strs are ever written
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl Write for StrBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > MAX_STRING_LEN {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
 }
@@ -0,0 +1,113 @@
+//! Parsing decimal literals at compile time, so constants can be written as
+//! strings instead of coefficients and exponents.
+use crate::{Decimal128, MAX_DIGITS};
+
+// Exponents are only read this far; anything larger is out of range anyway,
+// whatever the number of digits.
+const EXPONENT_LIMIT: i64 = 1 << 30;
+
+impl Decimal128 {
+    /// Parses a finite decimal literal in a `const` context. It reads the
+    /// same numbers `parse` does, with an optional sign, a decimal point and
+    /// an exponent, and gives the same encoding for them.
+    ///
+    /// NaN and Infinity aren't accepted, and nothing is rounded: a literal
+    /// that is malformed, has more than 34 significant digits or is out of
+    /// range panics, which in a constant is a compile error.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// const FEE: Decimal128 = Decimal128::parse_const("0.0025");
+    /// const LIMIT: Decimal128 = Decimal128::parse_const("-1.5E+6");
+    /// assert_eq!("0.0025", FEE.to_string());
+    /// assert_eq!("-1.5E+6", LIMIT.to_string());
+    /// ```
+    /// ```compile_fail
+    /// use decimal128::*;
+    ///
+    /// const RATE: Decimal128 = Decimal128::parse_const("1.5%");
+    /// ```
+    pub const fn parse_const(s: &str) -> Decimal128 {
+        let s = s.as_bytes();
+        let (sign, mut i) = match s.first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+
+        // the coefficient, with zeros past the 34th digit dropped and
+        // counted in the exponent instead
+        let mut coefficient: u128 = 0;
+        let mut digits = 0;
+        let mut dropped_zeros: i64 = 0;
+        let mut seen_digit = false;
+        let mut fraction_digits: Option<i64> = None;
+        while i < s.len() && s[i] != b'e' && s[i] != b'E' {
+            match s[i] {
+                b'0'..=b'9' => {
+                    let digit = (s[i] - b'0') as u128;
+                    seen_digit = true;
+                    if digits < MAX_DIGITS {
+                        coefficient = coefficient * 10 + digit;
+                        if coefficient != 0 {
+                            digits += 1;
+                        }
+                    } else if digit == 0 {
+                        dropped_zeros += 1;
+                    } else {
+                        panic!("decimal literal has more than 34 significant digits");
+                    }
+                    if let Some(count) = fraction_digits {
+                        fraction_digits = Some(count + 1);
+                    }
+                }
+                b'.' if fraction_digits.is_none() => fraction_digits = Some(0),
+                _ => panic!("invalid character in decimal literal"),
+            }
+            i += 1;
+        }
+        if !seen_digit {
+            panic!("decimal literal has no digits");
+        }
+
+        let mut exponent: i64 = 0;
+        if i < s.len() {
+            // skip the exponent marker
+            i += 1;
+            let negative = i < s.len() && s[i] == b'-';
+            if i < s.len() && (s[i] == b'-' || s[i] == b'+') {
+                i += 1;
+            }
+            if i == s.len() {
+                panic!("decimal literal has an exponent marker without digits");
+            }
+            while i < s.len() {
+                match s[i] {
+                    b'0'..=b'9' => {
+                        exponent = exponent * 10 + (s[i] - b'0') as i64;
+                        if exponent > EXPONENT_LIMIT {
+                            exponent = EXPONENT_LIMIT;
+                        }
+                    }
+                    _ => panic!("invalid character in decimal literal exponent"),
+                }
+                i += 1;
+            }
+            if negative {
+                exponent = -exponent;
+            }
+        }
+        let fraction_digits = match fraction_digits {
+            Some(count) => count,
+            None => 0,
+        };
+        let exponent = exponent - fraction_digits + dropped_zeros;
+        if exponent < i32::MIN as i64 || exponent > i32::MAX as i64 {
+            panic!("decimal literal exponent is out of range");
+        }
+        match Decimal128::from_parts_exact(sign, exponent as i32, coefficient) {
+            Ok(dec128) => dec128,
+            Err(_) => panic!("decimal literal exponent is out of range"),
+        }
+    }
+}
@@ -0,0 +1,597 @@
+//! Exponential and logarithmic functions, and cube roots.
+//!
+//! Results that a Decimal128 can hold exactly, like `exp10(3)` or
+//! `log10(1000)`, are exact.
+//! Everything else is worked out to 60 significant digits and rounded
+//! half-even to 34 only once, at the end, so the result is correctly rounded
+//! except in the rarest of cases.
+use crate::iter::{add_digits, compare_digits, multiply_digits, subtract_digits, to_digits};
+use crate::parse::from_digits_rounded;
+use crate::{quiet_nan, special, Decimal128, RoundingMode, INFINITY, MAX_DIGITS, NAN};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+// Significant digits intermediate results are worked out to: the 34 a
+// Decimal128 holds, and enough more that the errors from rounding each step
+// never reach them.
+pub(crate) const PRECISION: usize = 60;
+
+impl Decimal128 {
+    /// Returns 10 raised to the power of the value. An integer power is
+    /// exact, so `exp10(3)` is `1E+3`; other powers are rounded half-even to
+    /// 34 digits.
+    ///
+    /// NaN gives NaN, Infinity gives Infinity, and -Infinity gives 0.
+    /// Results too large for a Decimal128 are Infinity, and results too
+    /// small are rounded to a subnormal value or 0.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("1E+3", d("3").exp10().to_string());
+    /// assert_eq!("3.162277660168379331998893544432719", d("0.5").exp10().to_string());
+    /// ```
+    pub fn exp10(&self) -> Decimal128 {
+        if let Some(result) = exp_special(self) {
+            return result;
+        }
+        // 10^6200 overflows and 10^-6300 rounds to zero, whatever the digits
+        if *self > Decimal128::from(6200) {
+            return special(false, INFINITY, 0);
+        }
+        if *self < Decimal128::from(-6300) {
+            return from_digits_rounded(false, &[], i64::MIN, RoundingMode::HalfEven).0;
+        }
+
+        // 10^x = 10^n * e^(f * ln 10), with n the nearest integer and f what's
+        // left over, at most a half either way
+        let (n, fraction) = split_integer(self);
+        let mut result = if fraction.is_zero() {
+            Working::from_int(1)
+        } else {
+            exp(&fraction.mul(&ln10()))
+        };
+        result.exponent += n;
+        result.to_decimal()
+    }
+
+    /// Returns 2 raised to the power of the value. Integer powers are exact
+    /// when the result has at most 34 digits, so `exp2(10)` is `1024` and
+    /// `exp2(-3)` is `0.125`; anything else is rounded half-even to 34
+    /// digits.
+    ///
+    /// Special values and results out of range are handled like `exp10`
+    /// handles them.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("1024", d("10").exp2().to_string());
+    /// assert_eq!("0.125", d("-3").exp2().to_string());
+    /// assert_eq!("1.414213562373095048801688724209698", d("0.5").exp2().to_string());
+    /// ```
+    pub fn exp2(&self) -> Decimal128 {
+        if let Some(result) = exp_special(self) {
+            return result;
+        }
+        // 2^21000 is about 10^6321.6, and 2^-21000 about 10^-6321.6
+        if *self > Decimal128::from(21000) {
+            return special(false, INFINITY, 0);
+        }
+        if *self < Decimal128::from(-21000) {
+            return from_digits_rounded(false, &[], i64::MIN, RoundingMode::HalfEven).0;
+        }
+
+        // 2^x = 2^n * e^(f * ln 2), and for negative n, 2^n = 5^-n * 10^n
+        let (n, fraction) = split_integer(self);
+        let mut result = if n >= 0 {
+            Working::from_int(2).pow(n as u64)
+        } else {
+            let mut power = Working::from_int(5).pow(n.unsigned_abs());
+            power.exponent += n;
+            power
+        };
+        if !fraction.is_zero() {
+            result = result.mul(&exp(&fraction.mul(&ln2())));
+        }
+        result.to_decimal()
+    }
+
+    /// Returns the natural logarithm of the value, rounded half-even to 34
+    /// digits. The logarithm of 1 is exactly 0.
+    ///
+    /// Zero of either sign gives -Infinity, and Infinity gives Infinity.
+    /// NaN and negative values, including -Infinity, give NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("2.302585092994045684017991454684364", d("10").ln().to_string());
+    /// assert_eq!("0", d("1.00").ln().to_string());
+    /// ```
+    pub fn ln(&self) -> Decimal128 {
+        match ln(self) {
+            Ok(result) => result.to_decimal(),
+            Err(special) => special,
+        }
+    }
+
+    /// Returns the base 10 logarithm of the value, like `log` does with a
+    /// base of 10, so the logarithm of a power of ten is an exact integer.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("-3", d("0.001").log10().to_string());
+    /// assert_eq!("0.3010299956639811952137388947244930", d("2").log10().to_string());
+    /// ```
+    pub fn log10(&self) -> Decimal128 {
+        self.log(&Decimal128::from(10))
+    }
+
+    /// Returns the logarithm of the value to the given base, ln(value) /
+    /// ln(base), rounded half-even to 34 digits only once, so it's as
+    /// accurate as `ln`. When the logarithm is an integer and the base
+    /// raised to it is exactly the value, the result is that integer, like
+    /// `3` for 8 in base 2.
+    ///
+    /// The special cases follow from dividing the two natural logarithms:
+    /// NaN, a negative value or base, or both logarithms infinite, as with a
+    /// base of 0, gives NaN; a base of 1 gives an infinite result (or NaN,
+    /// for a value of 1 too); an infinite base gives 0.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("3", d("8").log(&d("2")).to_string());
+    /// // years for money to double at 5% a year
+    /// assert_eq!("14.20669908289047413032023363185645", d("2").log(&d("1.05")).to_string());
+    /// ```
+    pub fn log(&self, base: &Decimal128) -> Decimal128 {
+        let (ln_value, ln_base) = match (ln(self), ln(base)) {
+            (Err(nan), _) | (_, Err(nan)) if nan.nan => return nan,
+            (Err(_), Err(_)) => return special(false, NAN, 0),
+            (Err(infinity), Ok(ln_base)) => {
+                return special(infinity.sign != ln_base.negative, INFINITY, 0)
+            }
+            (Ok(ln_value), Err(infinity)) => {
+                return Decimal128::from_parts(ln_value.negative != infinity.sign, 0, 0)
+            }
+            (Ok(ln_value), Ok(ln_base)) => (ln_value, ln_base),
+        };
+        if ln_base.is_zero() {
+            if ln_value.is_zero() {
+                return special(false, NAN, 0);
+            }
+            return special(ln_value.negative, INFINITY, 0);
+        }
+        if ln_value.is_zero() {
+            return Decimal128::from(0);
+        }
+
+        let result = ln_value.div(&ln_base).to_decimal();
+        // check whether a result that came out as an integer is exactly one
+        match result.to_minor_units_exact(0) {
+            Ok(n) if n != 0 && n.unsigned_abs() <= 100_000 => {
+                let power = Working::from_decimal(base).pow(n.unsigned_abs() as u64);
+                let (value, power) = if n > 0 {
+                    (Working::from_decimal(self), power)
+                } else {
+                    (
+                        Working::from_int(1),
+                        power.mul(&Working::from_decimal(self)),
+                    )
+                };
+                if power.exact && power.sub(&value).is_zero() {
+                    return Decimal128::from_parts(n < 0, 0, n.unsigned_abs());
+                }
+                result
+            }
+            _ => result,
+        }
+    }
+    /// Returns the cube root of the value, rounded half-even to 34 digits.
+    /// Negative values have negative cube roots.
+    ///
+    /// A root that is exact, like the cube root of 27, comes out with the
+    /// exponent closest to a third of the value's exponent that keeps it
+    /// exact, so `0.001` gives `0.1` and `8.000` gives `2.0`. Zero keeps its
+    /// sign, and NaN and the infinities are returned as they are, with NaN
+    /// made quiet.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("3", d("27").cbrt().to_string());
+    /// assert_eq!("-0.1", d("-0.001").cbrt().to_string());
+    /// assert_eq!("1.259921049894873164767210607278228", d("2").cbrt().to_string());
+    /// ```
+    pub fn cbrt(&self) -> Decimal128 {
+        if self.nan {
+            return quiet_nan(self);
+        }
+        if self.inf {
+            return special(self.sign, INFINITY, 0);
+        }
+        let ideal = (self.exponent.to_adjusted() as i64).div_euclid(3);
+        if self.is_zero() {
+            return Decimal128::from_parts(self.sign, ideal as i16, 0);
+        }
+
+        // |x| = m * 10^3k, with m within [1, 1000)
+        let x = Working {
+            negative: false,
+            ..Working::from_decimal(self)
+        };
+        let k = (x.top() - 1).div_euclid(3);
+        let m = Working {
+            exponent: x.exponent - 3 * k,
+            ..x.clone()
+        };
+        // Newton's method for y^3 = m, y' = (2y + m / y^2) / 3, starting from
+        // the integer cube root of m * 10^30, which is good to 11 digits;
+        // each step doubles them
+        let mut y = Working::from_parts(integer_cbrt(m.to_u128(30)), -10);
+        let three = Working::from_int(3);
+        for _ in 0..4 {
+            y = y.add(&y).add(&m.div(&y.mul(&y))).div(&three);
+        }
+        y.negative = self.sign;
+        y.exponent += k;
+        y.exact = false;
+        let result = y.to_decimal();
+
+        // a root that cubes back to exactly the value loses the zeros that
+        // rounding padded it with, down to the ideal exponent
+        let root = Working::from_decimal(&result);
+        let cube = root.mul(&root).mul(&root);
+        let x = Working::from_decimal(self);
+        if !(cube.exact && cube.sub(&x).is_zero()) {
+            return result;
+        }
+        let mut coefficient = result.significand.to_num();
+        let mut exponent = result.exponent.to_adjusted() as i64;
+        while exponent < ideal && coefficient.is_multiple_of(10) {
+            coefficient /= 10;
+            exponent += 1;
+        }
+        Decimal128::from_parts(self.sign, exponent as i16, coefficient)
+    }
+}
+
+// The largest integer whose cube is at most n, for n below 2^111.
+fn integer_cbrt(n: u128) -> u128 {
+    let (mut low, mut high) = (0, 1 << 37);
+    while low + 1 < high {
+        let mid = (low + high) / 2;
+        if mid * mid * mid <= n {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+// The result of an exponential function for NaN and the infinities.
+fn exp_special(v: &Decimal128) -> Option<Decimal128> {
+    if v.nan {
+        Some(quiet_nan(v))
+    } else if v.inf && v.sign {
+        Some(Decimal128::from(0))
+    } else if v.inf {
+        Some(special(false, INFINITY, 0))
+    } else {
+        None
+    }
+}
+
+// Split a finite value into the nearest integer, rounding half-even, and the
+// exact difference between the two. The value has to be well within the
+// range of an i64.
+fn split_integer(v: &Decimal128) -> (i64, Working) {
+    let n = v
+        .to_minor_units(0, RoundingMode::HalfEven)
+        .expect("the value is in range") as i64;
+    (n, Working::from_decimal(v).sub(&Working::from_int(n)))
+}
+
+// e^x, for x within a few units of zero.
+fn exp(x: &Working) -> Working {
+    // e^x = (e^(x / 1024))^1024, and the series converges much faster for
+    // the smaller argument
+    let x = x.div(&Working::from_int(1024));
+    let mut sum = Working::from_int(1);
+    let mut term = Working::from_int(1);
+    for k in 1.. {
+        term = term.mul(&x).div(&Working::from_int(k));
+        if term.is_zero() || term.top() < sum.top() - PRECISION as i64 {
+            break;
+        }
+        sum = sum.add(&term);
+    }
+    for _ in 0..10 {
+        sum = sum.mul(&sum);
+    }
+    sum.exact = false;
+    sum
+}
+
+// ln v, or the result for NaN, zero, Infinity and negative values. ln 1 is
+// an exact zero.
+fn ln(v: &Decimal128) -> Result<Working, Decimal128> {
+    if v.nan {
+        return Err(quiet_nan(v));
+    }
+    if v.is_zero() {
+        return Err(special(true, INFINITY, 0));
+    }
+    if v.sign {
+        return Err(special(false, NAN, 0));
+    }
+    if v.inf {
+        return Err(special(false, INFINITY, 0));
+    }
+    let one = Working::from_int(1);
+    let x = Working::from_decimal(v);
+    if x.sub(&one).is_zero() {
+        return Ok(Working::from_int(0));
+    }
+
+    // x = m * 10^a with m within [0.5, 5), so that for x near 1, a is 0 and
+    // the two terms of the sum can't cancel each other out
+    let a = if x.digits[0] >= 5 {
+        x.top()
+    } else {
+        x.top() - 1
+    };
+    let mut m = Working {
+        exponent: x.exponent - a,
+        ..x
+    };
+    // and m = 2^j * f with f within [0.75, 1.5)
+    let mut j = 0;
+    let (half, two) = (Working::from_parts(5, -1), Working::from_int(2));
+    while !m.sub(&Working::from_parts(15, -1)).negative {
+        m = m.mul(&half);
+        j += 1;
+    }
+    while m.sub(&Working::from_parts(75, -2)).negative {
+        m = m.mul(&two);
+        j -= 1;
+    }
+
+    // ln f = 2 atanh((f - 1) / (f + 1))
+    let ln_f = atanh(&m.sub(&one).div(&m.add(&one))).mul(&two);
+    let ln_2j = ln2().mul(&Working::from_int(j));
+    Ok(ln_f.add(&ln_2j).add(&ln10().mul(&Working::from_int(a))))
+}
+
+// ln 2 and ln 10, rounded to 64 digits.
+const LN2: &[u8] = b"6931471805599453094172321214581765680755001343602552541206800095";
+const LN10: &[u8] = b"2302585092994045684017991454684364207601101488628772976033327901";
+
+fn ln2() -> Working {
+    Working::from_constant(LN2, -64)
+}
+
+fn ln10() -> Working {
+    Working::from_constant(LN10, -63)
+}
+
+// atanh(z) = z + z^3/3 + z^5/5 + ..., for |z| well below 1.
+fn atanh(z: &Working) -> Working {
+    let square = z.mul(z);
+    let mut power = z.clone();
+    let mut sum = z.clone();
+    for k in 1.. {
+        power = power.mul(&square);
+        let term = power.div(&Working::from_int(2 * k + 1));
+        if term.is_zero() || term.top() < sum.top() - PRECISION as i64 {
+            break;
+        }
+        sum = sum.add(&term);
+    }
+    sum.exact = false;
+    sum
+}
+
+// A number carried to more digits than a Decimal128 holds: `digits`, most
+// significant first and without leading zeros, times 10^exponent. `exact`
+// is false once digits have been rounded off.
+#[derive(Debug, Clone)]
+pub(crate) struct Working {
+    pub(crate) negative: bool,
+    pub(crate) digits: Vec<u8>,
+    pub(crate) exponent: i64,
+    pub(crate) exact: bool,
+}
+
+impl Working {
+    pub(crate) fn from_int(n: i64) -> Working {
+        Working::new(n < 0, to_digits(n.unsigned_abs() as u128, 0), 0, true)
+    }
+
+    // coefficient * 10^exponent
+    pub(crate) fn from_parts(coefficient: u128, exponent: i64) -> Working {
+        Working::new(false, to_digits(coefficient, 0), exponent, true)
+    }
+
+    // One of the constants above, written out in ASCII digits.
+    pub(crate) fn from_constant(digits: &[u8], exponent: i64) -> Working {
+        let digits = digits.iter().map(|d| d - b'0').collect();
+        Working::new(false, digits, exponent, false)
+    }
+
+    // A finite Decimal128.
+    pub(crate) fn from_decimal(v: &Decimal128) -> Working {
+        let digits = to_digits(v.significand.to_num(), 0);
+        Working::new(v.sign, digits, v.exponent.to_adjusted() as i64, true)
+    }
+
+    // Strip leading zeros and cut the digits down to PRECISION.
+    pub(crate) fn new(
+        negative: bool,
+        mut digits: Vec<u8>,
+        mut exponent: i64,
+        mut exact: bool,
+    ) -> Working {
+        let leading_zeros = digits.iter().take_while(|d| **d == 0).count();
+        digits.drain(..leading_zeros);
+        if digits.len() > PRECISION {
+            let dropped = digits.split_off(PRECISION);
+            exact &= dropped.iter().all(|d| *d == 0);
+            exponent += dropped.len() as i64;
+        }
+        Working {
+            negative,
+            digits,
+            exponent,
+            exact,
+        }
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    // The magnitude times 10^shift, without the digits after the decimal
+    // point. It has to fit in a u128.
+    pub(crate) fn to_u128(&self, shift: i64) -> u128 {
+        let places = (self.top() + shift).max(0) as usize;
+        (0..places).fold(0, |n, i| {
+            n * 10 + self.digits.get(i).copied().unwrap_or(0) as u128
+        })
+    }
+
+    // The power of ten just above the most significant digit.
+    pub(crate) fn top(&self) -> i64 {
+        self.exponent + self.digits.len() as i64
+    }
+
+    pub(crate) fn add(&self, other: &Working) -> Working {
+        if other.is_zero() {
+            return self.clone();
+        }
+        if self.is_zero() {
+            return other.clone();
+        }
+        // a value too small to reach the other's digits only makes the sum
+        // inexact
+        if self.top() > other.top() + PRECISION as i64 {
+            return Working {
+                exact: false,
+                ..self.clone()
+            };
+        }
+        if other.top() > self.top() + PRECISION as i64 {
+            return Working {
+                exact: false,
+                ..other.clone()
+            };
+        }
+
+        // line the digits up on the smaller exponent
+        let exponent = self.exponent.min(other.exponent);
+        let pad = |v: &Working| {
+            let mut digits = v.digits.clone();
+            digits.resize(digits.len() + (v.exponent - exponent) as usize, 0);
+            digits
+        };
+        let (a, b) = (pad(self), pad(other));
+        let exact = self.exact && other.exact;
+        if self.negative == other.negative {
+            return Working::new(self.negative, add_digits(&a, &b), exponent, exact);
+        }
+        match compare_digits(&a, &b) {
+            Ordering::Greater => {
+                Working::new(self.negative, subtract_digits(&a, &b), exponent, exact)
+            }
+            Ordering::Less => {
+                Working::new(other.negative, subtract_digits(&b, &a), exponent, exact)
+            }
+            Ordering::Equal => Working::new(false, Vec::new(), exponent, exact),
+        }
+    }
+
+    pub(crate) fn sub(&self, other: &Working) -> Working {
+        let negated = Working {
+            negative: !other.negative,
+            ..other.clone()
+        };
+        self.add(&negated)
+    }
+
+    pub(crate) fn mul(&self, other: &Working) -> Working {
+        Working::new(
+            self.negative != other.negative,
+            multiply_digits(&self.digits, &other.digits),
+            self.exponent + other.exponent,
+            self.exact && other.exact,
+        )
+    }
+
+    // Long division, one digit at a time, until the quotient comes out even
+    // or has one more digit than PRECISION.
+    pub(crate) fn div(&self, other: &Working) -> Working {
+        let mut quotient = Vec::new();
+        let mut remainder: Vec<u8> = Vec::new();
+        let mut exponent = self.exponent - other.exponent;
+        let mut significant = 0;
+        for i in 0.. {
+            let digit = if i < self.digits.len() {
+                self.digits[i]
+            } else if remainder.is_empty() || significant > PRECISION {
+                break;
+            } else {
+                exponent -= 1;
+                0
+            };
+            remainder.push(digit);
+            let mut q = 0;
+            while compare_digits(&remainder, &other.digits) != Ordering::Less {
+                remainder = subtract_digits(&remainder, &other.digits);
+                q += 1;
+            }
+            let leading_zeros = remainder.iter().take_while(|d| **d == 0).count();
+            remainder.drain(..leading_zeros);
+            if q > 0 || significant > 0 {
+                significant += 1;
+            }
+            quotient.push(q);
+        }
+        let exact = self.exact && other.exact && remainder.is_empty();
+        Working::new(self.negative != other.negative, quotient, exponent, exact)
+    }
+
+    // self^n, by repeated squaring.
+    pub(crate) fn pow(&self, mut n: u64) -> Working {
+        let mut result = Working::from_int(1);
+        let mut square = self.clone();
+        while n > 0 {
+            if n % 2 == 1 {
+                result = result.mul(&square);
+            }
+            n /= 2;
+            if n > 0 {
+                square = square.mul(&square);
+            }
+        }
+        result
+    }
+
+    // Round to a Decimal128, half-even. A non-zero digit past the last one
+    // tells rounding that an inexact value isn't exactly halfway; it goes
+    // after the 35th digit at the earliest, so it is always rounded off.
+    pub(crate) fn to_decimal(&self) -> Decimal128 {
+        let mut digits = self.digits.clone();
+        let mut exponent = self.exponent;
+        if !self.exact && !self.is_zero() {
+            let zeros = (MAX_DIGITS as usize + 1).saturating_sub(digits.len());
+            digits.resize(digits.len() + zeros, 0);
+            digits.push(1);
+            exponent -= zeros as i64 + 1;
+        }
+        from_digits_rounded(self.negative, &digits, exponent, RoundingMode::HalfEven).0
+    }
+}
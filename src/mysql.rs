@@ -0,0 +1,208 @@
+//! Conversions to and from MySQL's DECIMAL(M, D) textual representation.
+//!
+//! MySQL sends and accepts DECIMAL values as plain positional strings such as
+//! `-1234.50`, with exactly D digits after the decimal point and at most M
+//! digits in total. M can be at most 65 and D at most 30.
+use crate::{digit_count, round_coefficient, ConversionError, Decimal128, RoundingMode};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+const MAX_PRECISION: u32 = 65;
+const MAX_SCALE: u32 = 30;
+
+impl Decimal128 {
+    /// Parse a MySQL DECIMAL value.
+    ///
+    /// The input must be a plain decimal number: an optional sign, digits,
+    /// and an optional fractional part. DECIMAL columns can hold up to 65
+    /// digits, so values with more than 34 significant digits are rounded with
+    /// `mode`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_mysql_decimal("-1234.50", RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("-1234.50", dec128.to_string());
+    /// ```
+    pub fn from_mysql_decimal(s: &str, mode: RoundingMode) -> Result<Self, ConversionError> {
+        let (sign, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (integer, fraction) = match unsigned.find('.') {
+            Some(i) => (&unsigned[..i], &unsigned[i + 1..]),
+            None => (unsigned, ""),
+        };
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(ConversionError::Invalid);
+        }
+
+        let digits = integer
+            .bytes()
+            .chain(fraction.bytes())
+            .map(|b| match b {
+                b'0'..=b'9' => Ok(b - b'0'),
+                _ => Err(ConversionError::Invalid),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        Decimal128::from_digits(sign, &digits, -(fraction.len() as i32), mode)
+    }
+
+    /// Format this Decimal128 for a MySQL DECIMAL(precision, scale) column.
+    ///
+    /// The result always has exactly `scale` digits after the decimal point;
+    /// extra fractional digits are rounded with `mode`. Returns
+    /// `ConversionError::Overflow` if the integer part doesn't fit in
+    /// `precision - scale` digits, `ConversionError::Invalid` if the column
+    /// type itself is not valid, and `ConversionError::NotFinite` for NaN and
+    /// Infinity, which MySQL can't store.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_mysql_decimal("2.675", RoundingMode::HalfEven).unwrap();
+    /// let text = dec128.to_mysql_decimal(10, 2, RoundingMode::HalfUp).unwrap();
+    /// assert_eq!("2.68", text);
+    /// ```
+    pub fn to_mysql_decimal(
+        &self,
+        precision: u32,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Result<String, ConversionError> {
+        if precision == 0 || precision > MAX_PRECISION || scale > MAX_SCALE || scale > precision {
+            return Err(ConversionError::Invalid);
+        }
+        if self.nan || self.inf {
+            return Err(ConversionError::NotFinite);
+        }
+
+        let target = -(scale as i32);
+        let mut exponent = self.exponent.to_adjusted() as i32;
+        let mut coefficient = self.significand.to_num();
+        if exponent < target {
+            let (rounded, _) =
+                round_coefficient(self.sign, coefficient, (target - exponent) as u32, mode);
+            coefficient = rounded;
+            exponent = target;
+        }
+        if coefficient == 0 {
+            exponent = target;
+        }
+
+        let integer_digits = if coefficient == 0 {
+            0
+        } else {
+            digit_count(coefficient) as i32 + exponent
+        };
+        if integer_digits > (precision - scale) as i32 {
+            return Err(ConversionError::Overflow);
+        }
+        Ok(positional_string(
+            self.sign,
+            coefficient,
+            (exponent - target) as usize,
+            scale as usize,
+        ))
+    }
+
+    /// Returns the smallest (precision, scale) pair of a MySQL DECIMAL column
+    /// that can store this Decimal128 without rounding.
+    ///
+    /// Returns `ConversionError::Overflow` if that would need more than 65
+    /// digits or a scale over 30, and `ConversionError::NotFinite` for NaN and
+    /// Infinity.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_mysql_decimal("-1234.50", RoundingMode::HalfEven).unwrap();
+    /// assert_eq!(Ok((6, 2)), dec128.mysql_precision_scale());
+    /// ```
+    pub fn mysql_precision_scale(&self) -> Result<(u32, u32), ConversionError> {
+        if self.nan || self.inf {
+            return Err(ConversionError::NotFinite);
+        }
+        let exponent = self.exponent.to_adjusted() as i32;
+        let coefficient = self.significand.to_num();
+
+        let scale = (-exponent).max(0);
+        let integer_digits = if coefficient == 0 {
+            0
+        } else {
+            (digit_count(coefficient) as i32 + exponent).max(0)
+        };
+        let precision = (integer_digits + scale).max(1);
+        if scale > MAX_SCALE as i32 || precision > MAX_PRECISION as i32 {
+            return Err(ConversionError::Overflow);
+        }
+        Ok((precision as u32, scale as u32))
+    }
+}
+
+// Write `coefficient` followed by `zeros` zeros, with a decimal point placed
+// `scale` digits from the end.
+fn positional_string(sign: bool, coefficient: u128, zeros: usize, scale: usize) -> String {
+    let mut digits = coefficient.to_string();
+    if coefficient != 0 {
+        digits.push_str(&"0".repeat(zeros));
+    }
+    if scale > 0 {
+        if digits.len() <= scale {
+            digits.insert_str(0, &"0".repeat(scale + 1 - digits.len()));
+        }
+        digits.insert(digits.len() - scale, '.');
+    }
+    if sign && coefficient != 0 {
+        digits.insert(0, '-');
+    }
+    digits
+}
+
+#[cfg(feature = "sqlx-mysql")]
+mod sqlx {
+    use super::positional_string;
+    use crate::{Decimal128, RoundingMode};
+    use sqlx_core::decode::Decode;
+    use sqlx_core::encode::{Encode, IsNull};
+    use sqlx_core::error::BoxDynError;
+    use sqlx_core::type_info::TypeInfo;
+    use sqlx_core::types::Type;
+    use sqlx_mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+    // Decimal128 is sent to the server as text, which MySQL converts to the
+    // column's DECIMAL type.
+    impl Type<MySql> for Decimal128 {
+        fn type_info() -> MySqlTypeInfo {
+            <str as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty: &MySqlTypeInfo) -> bool {
+            ty.name() == "DECIMAL" || <str as Type<MySql>>::compatible(ty)
+        }
+    }
+
+    impl Encode<'_, MySql> for Decimal128 {
+        fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+            if self.nan || self.inf {
+                return Err(crate::ConversionError::NotFinite.into());
+            }
+            let exponent = self.exponent.to_adjusted() as i32;
+            let text = positional_string(
+                self.sign,
+                self.significand.to_num(),
+                exponent.max(0) as usize,
+                (-exponent).max(0) as usize,
+            );
+            <&str as Encode<MySql>>::encode(text.as_str(), buf)
+        }
+    }
+
+    impl Decode<'_, MySql> for Decimal128 {
+        fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+            let text = <&str as Decode<MySql>>::decode(value)?;
+            Ok(Decimal128::from_mysql_decimal(
+                text,
+                RoundingMode::HalfEven,
+            )?)
+        }
+    }
+}
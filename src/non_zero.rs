@@ -0,0 +1,136 @@
+//! A Decimal128 that is finite and not zero, so it's always safe to divide
+//! by.
+use crate::{ConversionError, Decimal128};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use core::ops::Div;
+
+/// A Decimal128 that is checked to be finite and non-zero when it's made,
+/// for values like exchange rates or unit sizes that a model divides by.
+///
+/// Dividing a Decimal128 by it with `/` can't divide by zero, so there's
+/// no NaN to check for unless the dividend is NaN itself. Like NotNan, it's
+/// `Eq`, `Ord` and `Hash`, comparing values numerically.
+/// ```
+/// use decimal128::*;
+///
+/// let rate = NonZeroDecimal128::new("1.25".parse().unwrap()).unwrap();
+/// let amount: Decimal128 = "100.00".parse().unwrap();
+/// assert_eq!("80", (amount / rate).to_string());
+/// assert!(NonZeroDecimal128::new("0.00".parse().unwrap()).is_err());
+/// ```
+#[derive(Clone)]
+pub struct NonZeroDecimal128(Decimal128);
+
+impl NonZeroDecimal128 {
+    /// Wraps `value`, failing with `ConversionError::NotFinite` if it is NaN
+    /// or Infinity, and with `ConversionError::Invalid` if it is zero.
+    pub fn new(value: Decimal128) -> Result<NonZeroDecimal128, ConversionError> {
+        if value.is_nan() || value.inf {
+            return Err(ConversionError::NotFinite);
+        }
+        if value.is_zero() {
+            return Err(ConversionError::Invalid);
+        }
+        Ok(NonZeroDecimal128(value))
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> Decimal128 {
+        self.0
+    }
+}
+
+impl Deref for NonZeroDecimal128 {
+    type Target = Decimal128;
+
+    fn deref(&self) -> &Decimal128 {
+        &self.0
+    }
+}
+
+impl AsRef<Decimal128> for NonZeroDecimal128 {
+    fn as_ref(&self) -> &Decimal128 {
+        &self.0
+    }
+}
+
+impl TryFrom<Decimal128> for NonZeroDecimal128 {
+    type Error = ConversionError;
+
+    fn try_from(value: Decimal128) -> Result<Self, ConversionError> {
+        NonZeroDecimal128::new(value)
+    }
+}
+
+impl From<NonZeroDecimal128> for Decimal128 {
+    fn from(value: NonZeroDecimal128) -> Self {
+        value.0
+    }
+}
+
+/// Divides like `overflowing_div`, rounding half-even to 34 digits. The
+/// quotient is Infinity if it overflows, and NaN only if `self` is NaN.
+#[cfg(feature = "alloc")]
+impl<'a> Div<&'a NonZeroDecimal128> for &'a Decimal128 {
+    type Output = Decimal128;
+
+    fn div(self, divisor: &NonZeroDecimal128) -> Decimal128 {
+        self.overflowing_div(&divisor.0).0
+    }
+}
+
+/// Divides like the implementation for references does.
+#[cfg(feature = "alloc")]
+impl Div<NonZeroDecimal128> for Decimal128 {
+    type Output = Decimal128;
+
+    fn div(self, divisor: NonZeroDecimal128) -> Decimal128 {
+        &self / &divisor
+    }
+}
+
+impl PartialEq for NonZeroDecimal128 {
+    fn eq(&self, other: &NonZeroDecimal128) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for NonZeroDecimal128 {}
+
+impl PartialOrd for NonZeroDecimal128 {
+    fn partial_cmp(&self, other: &NonZeroDecimal128) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NonZeroDecimal128 {
+    fn cmp(&self, other: &NonZeroDecimal128) -> Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .expect("finite values are always ordered")
+    }
+}
+
+/// Hashes like Decimal128 does, so equal values hash alike.
+impl Hash for NonZeroDecimal128 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl fmt::Display for NonZeroDecimal128 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl fmt::Debug for NonZeroDecimal128 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, fmt)
+    }
+}
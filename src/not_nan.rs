@@ -0,0 +1,112 @@
+//! A Decimal128 that can't be NaN, so it can be totally ordered.
+use crate::{ConversionError, Decimal128};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// A Decimal128 that is checked not to be NaN when it's made, like
+/// `ordered_float::NotNan`. Without NaN, the numeric comparisons of
+/// Decimal128 are a total order, so this is `Eq`, `Ord` and `Hash` and can
+/// be a key in a `HashMap` or `BTreeMap`.
+///
+/// Values compare numerically, so `1.0` and `1.00` are the same key, and so
+/// are `-0` and `0`. Infinity and -Infinity are allowed.
+/// ```
+/// use decimal128::*;
+/// use std::collections::BTreeMap;
+///
+/// let mut prices = BTreeMap::new();
+/// for (price, item) in [("2.50", "tea"), ("1.0", "water"), ("1.00", "juice")].iter() {
+///     prices.insert(NotNan::new(price.parse().unwrap()).unwrap(), *item);
+/// }
+/// let items: Vec<&str> = prices.values().copied().collect();
+/// assert_eq!(vec!["juice", "tea"], items);
+/// ```
+#[derive(Clone)]
+pub struct NotNan(Decimal128);
+
+impl NotNan {
+    /// Wraps `value`, failing with `ConversionError::Invalid` if it is NaN.
+    pub fn new(value: Decimal128) -> Result<NotNan, ConversionError> {
+        if value.is_nan() {
+            return Err(ConversionError::Invalid);
+        }
+        Ok(NotNan(value))
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> Decimal128 {
+        self.0
+    }
+}
+
+impl Deref for NotNan {
+    type Target = Decimal128;
+
+    fn deref(&self) -> &Decimal128 {
+        &self.0
+    }
+}
+
+impl AsRef<Decimal128> for NotNan {
+    fn as_ref(&self) -> &Decimal128 {
+        &self.0
+    }
+}
+
+impl TryFrom<Decimal128> for NotNan {
+    type Error = ConversionError;
+
+    fn try_from(value: Decimal128) -> Result<Self, ConversionError> {
+        NotNan::new(value)
+    }
+}
+
+impl From<NotNan> for Decimal128 {
+    fn from(value: NotNan) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq for NotNan {
+    fn eq(&self, other: &NotNan) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for NotNan {}
+
+impl PartialOrd for NotNan {
+    fn partial_cmp(&self, other: &NotNan) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NotNan {
+    fn cmp(&self, other: &NotNan) -> Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .expect("values other than NaN are always ordered")
+    }
+}
+
+/// Hashes like Decimal128 does, so equal values hash alike.
+impl Hash for NotNan {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl fmt::Display for NotNan {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl fmt::Debug for NotNan {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, fmt)
+    }
+}
@@ -0,0 +1,204 @@
+//! `num_traits` numeric and conversion traits for Decimal128, so it can be
+//! used in generic code written against `Num` or `Signed`.
+//!
+//! The arithmetic comes from the operators, which round half-even to 34
+//! digits. `Signed::is_positive` and `Signed::is_negative` compare the value
+//! with zero, unlike the inherent `is_positive` and `is_negative`, which
+//! read the sign bit.
+use crate::{pow10, quiet_nan, Decimal128, ParseDecimal128Error, RoundingMode};
+use ::num_traits::{FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+use std::convert::TryFrom;
+
+impl Zero for Decimal128 {
+    fn zero() -> Self {
+        Decimal128::from(0)
+    }
+
+    /// Zeros of either sign and any exponent are zero.
+    fn is_zero(&self) -> bool {
+        Decimal128::is_zero(self)
+    }
+}
+
+impl One for Decimal128 {
+    fn one() -> Self {
+        Decimal128::from(1)
+    }
+}
+
+impl Num for Decimal128 {
+    type FromStrRadixErr = ParseDecimal128Error;
+
+    /// Parses like `FromStr`. Decimal128 strings are always in base 10, so
+    /// any other radix fails with `ParseDecimal128Error::InvalidRadix`.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseDecimal128Error> {
+        if radix != 10 {
+            return Err(ParseDecimal128Error::InvalidRadix);
+        }
+        s.parse()
+    }
+}
+
+/// Signs are numeric: zeros of either sign are neither positive nor
+/// negative and have a signum of 0, and NaN is neither and has a signum of
+/// NaN.
+impl Signed for Decimal128 {
+    /// Clears the sign, of NaN too, like `f64::abs`.
+    fn abs(&self) -> Self {
+        self.with_sign(false)
+    }
+
+    /// `self - other` if that's positive, and 0 otherwise. NaN operands give
+    /// NaN.
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            Decimal128::from(0)
+        } else {
+            self - other
+        }
+    }
+
+    fn signum(&self) -> Self {
+        if self.nan {
+            quiet_nan(self)
+        } else if Decimal128::is_zero(self) {
+            Decimal128::from(0)
+        } else if self.sign {
+            Decimal128::from(-1)
+        } else {
+            Decimal128::from(1)
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        !self.sign && !self.nan && !Decimal128::is_zero(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign && !self.nan && !Decimal128::is_zero(self)
+    }
+}
+
+impl FromPrimitive for Decimal128 {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Decimal128::from_integer(n < 0, n.unsigned_abs() as u128))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Decimal128::from_integer(false, n as u128))
+    }
+
+    /// Integers with more than 34 digits are rounded half-even.
+    fn from_i128(n: i128) -> Option<Self> {
+        Some(Decimal128::from_integer(n < 0, n.unsigned_abs()))
+    }
+
+    /// Integers with more than 34 digits are rounded half-even.
+    fn from_u128(n: u128) -> Option<Self> {
+        Some(Decimal128::from_integer(false, n))
+    }
+
+    /// Converts the exact binary value of `n`, rounded half-even to 34
+    /// digits, so 0.1 becomes 0.1000000000000000055511151231257827.
+    fn from_f64(n: f64) -> Option<Self> {
+        Some(from_f64_rounded(n))
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        Some(from_f64_rounded(n as f64))
+    }
+}
+
+/// Integer conversions truncate toward zero, like they do for `f64`, and
+/// return `None` for NaN, Infinity, and values out of the integer's range.
+impl ToPrimitive for Decimal128 {
+    fn to_i64(&self) -> Option<i64> {
+        self.to_i128().and_then(|n| i64::try_from(n).ok())
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.to_u128().and_then(|n| u64::try_from(n).ok())
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        let (sign, magnitude) = truncated_magnitude(self)?;
+        if sign {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    /// Negative values that truncate to zero, like `-0.5`, give 0.
+    fn to_u128(&self) -> Option<u128> {
+        match truncated_magnitude(self)? {
+            (true, magnitude) if magnitude != 0 => None,
+            (_, magnitude) => Some(magnitude),
+        }
+    }
+
+    /// Rounds to the nearest `f64`.
+    fn to_f64(&self) -> Option<f64> {
+        self.to_string().parse().ok()
+    }
+}
+
+// Create a Decimal128 from the exact binary value of an f64, rounded to 34
+// digits.
+fn from_f64_rounded(v: f64) -> Decimal128 {
+    let mut bytes = [0u8; 16];
+    if v.is_nan() {
+        bytes[0] = 0x7c;
+        return Decimal128::from_raw_bytes(bytes);
+    }
+    if v.is_infinite() {
+        bytes[0] = if v < 0.0 { 0xf8 } else { 0x78 };
+        return Decimal128::from_raw_bytes(bytes);
+    }
+    // Rust formats floats exactly, so asking for 34 significant digits gives
+    // us the correctly rounded coefficient, e.g. 1.000...000e-1
+    let formatted = format!("{:.33e}", v.abs());
+    let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+    let exponent: i32 = exponent[1..].parse().unwrap();
+    let mut digits: Vec<u8> = mantissa
+        .bytes()
+        .filter(|b| *b != b'.')
+        .map(|b| b - b'0')
+        .collect();
+    // the trailing zeros are just padding from the formatter
+    while digits.len() > 1 && digits.last() == Some(&0) {
+        digits.pop();
+    }
+    let exponent = exponent - (digits.len() as i32 - 1);
+    // f64 exponents are well within range of a Decimal128
+    Decimal128::from_digits(
+        v.is_sign_negative(),
+        &digits,
+        exponent,
+        RoundingMode::HalfEven,
+    )
+    .unwrap()
+}
+
+// The sign and the magnitude of the integer part of a finite Decimal128, if
+// the magnitude fits in a u128.
+fn truncated_magnitude(v: &Decimal128) -> Option<(bool, u128)> {
+    if v.nan || v.inf {
+        return None;
+    }
+    let exponent = v.exponent.to_adjusted() as i32;
+    let coefficient = v.significand.to_num();
+    let magnitude = if coefficient == 0 {
+        0
+    } else if exponent >= 0 {
+        if exponent > 38 {
+            return None;
+        }
+        coefficient.checked_mul(pow10(exponent as u32))?
+    } else if -exponent > 38 {
+        0
+    } else {
+        coefficient / pow10(-exponent as u32)
+    };
+    Some((v.sign, magnitude))
+}
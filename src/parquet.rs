@@ -0,0 +1,125 @@
+//! Conversions to and from the Parquet DECIMAL physical representation.
+//!
+//! Parquet stores a decimal as its unscaled value in big-endian two's
+//! complement, in a FIXED_LEN_BYTE_ARRAY or BYTE_ARRAY column. The scale is
+//! declared once in the column's schema, and the value is `unscaled * 10^-scale`.
+#[cfg(feature = "alloc")]
+use crate::pow10;
+use crate::{ConversionError, Decimal128};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+impl Decimal128 {
+    /// Create a Decimal128 from a Parquet DECIMAL unscaled value and the
+    /// column's scale.
+    ///
+    /// `bytes` is the big-endian two's complement unscaled value. Values wider
+    /// than 16 bytes are accepted as long as the extra leading bytes are only
+    /// sign extension. Unscaled values with more than 34 digits are accepted
+    /// if they can be stored without rounding, otherwise this returns
+    /// `ConversionError::Inexact`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// // 12345 with a scale of 2
+    /// let dec128 = Decimal128::from_parquet_bytes(&[0x30, 0x39], 2).unwrap();
+    /// assert_eq!("123.45", dec128.to_string());
+    /// ```
+    pub fn from_parquet_bytes(bytes: &[u8], scale: i32) -> Result<Self, ConversionError> {
+        let negative = bytes.first().is_some_and(|b| b & 0x80 != 0);
+        let extension = if negative { 0xff } else { 0x00 };
+
+        let mut buffer = [extension; 16];
+        if bytes.len() > 16 {
+            let (high, low) = bytes.split_at(bytes.len() - 16);
+            // the extra leading bytes can only carry the sign
+            if high.iter().any(|b| *b != extension) || (low[0] & 0x80 != 0) != negative {
+                return Err(ConversionError::Overflow);
+            }
+            buffer.copy_from_slice(low);
+        } else {
+            buffer[16 - bytes.len()..].copy_from_slice(bytes);
+        }
+
+        let unscaled = i128::from_be_bytes(buffer);
+        let exponent = -(scale as i64);
+        if exponent < i32::MIN as i64 || exponent > i32::MAX as i64 {
+            return Err(ConversionError::Overflow);
+        }
+        Decimal128::from_parts_exact(negative, exponent as i32, unscaled.unsigned_abs())
+    }
+
+    /// Encode this Decimal128 as a Parquet DECIMAL unscaled value with the
+    /// given scale, in a big-endian two's complement array of `len` bytes.
+    ///
+    /// Returns `ConversionError::Inexact` if the value has more fractional
+    /// digits than `scale` allows, `ConversionError::Overflow` if the unscaled
+    /// value does not fit in `len` bytes, and `ConversionError::NotFinite` for
+    /// NaN and Infinity.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_parquet_bytes(&[0x30, 0x39], 2).unwrap();
+    /// let bytes = dec128.to_parquet_bytes(3, 4).unwrap();
+    /// assert_eq!(vec![0x00, 0x01, 0xe2, 0x3a], bytes);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_parquet_bytes(&self, scale: i32, len: usize) -> Result<Vec<u8>, ConversionError> {
+        if self.nan || self.inf {
+            return Err(ConversionError::NotFinite);
+        }
+
+        let exponent = self.exponent.to_adjusted() as i64;
+        let mut coefficient = self.significand.to_num();
+        let shift = exponent + scale as i64;
+        if coefficient != 0 {
+            if shift > 0 {
+                // the value has fewer fractional digits than the scale, so
+                // the coefficient gets padded with zeros
+                coefficient = if shift > 38 {
+                    None
+                } else {
+                    coefficient.checked_mul(pow10(shift as u32))
+                }
+                .ok_or(ConversionError::Overflow)?;
+            } else if shift < 0 {
+                // the value has more fractional digits than the scale, which
+                // is only fine if they're all zeros
+                if -shift > 38 || !coefficient.is_multiple_of(pow10(-shift as u32)) {
+                    return Err(ConversionError::Inexact);
+                }
+                coefficient /= pow10(-shift as u32);
+            }
+        }
+
+        let unscaled = if self.sign {
+            if coefficient > 1 << 127 {
+                return Err(ConversionError::Overflow);
+            }
+            (coefficient as i128).wrapping_neg()
+        } else {
+            if coefficient > i128::MAX as u128 {
+                return Err(ConversionError::Overflow);
+            }
+            coefficient as i128
+        };
+
+        if len == 0 {
+            return Err(ConversionError::Overflow);
+        }
+        if len < 16 {
+            let bits = 8 * len as u32;
+            let max = (1i128 << (bits - 1)) - 1;
+            let min = -max - 1;
+            if unscaled < min || unscaled > max {
+                return Err(ConversionError::Overflow);
+            }
+        }
+
+        let extension = if unscaled < 0 { 0xff } else { 0x00 };
+        let mut bytes = vec![extension; len.saturating_sub(16)];
+        let unscaled_bytes = unscaled.to_be_bytes();
+        bytes.extend_from_slice(&unscaled_bytes[16 - len.min(16)..]);
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,443 @@
+//! Parsing Decimal128 from strings in the decimal arithmetic specification's
+//! [numeric string](http://speleotrove.com/decimal/daconvs.html#refnumsyn)
+//! syntax, e.g. `-12.50`, `1E+3` or `Infinity`.
+use crate::arithmetic::subnormal;
+use crate::{
+    digit_count, pow10, special, Decimal128, Flags, ParseDecimal128Error, RoundingMode, INFINITY,
+    MAX_DIGITS, MAX_EXPONENT, MIN_EXPONENT, NAN,
+};
+use alloc::format;
+use alloc::vec::Vec;
+use core::iter;
+use core::str::FromStr;
+
+// Exponents larger than this are out of range whatever the coefficient is,
+// so longer exponents are saturated here instead of overflowing.
+const EXPONENT_LIMIT: i64 = 1 << 30;
+
+/// Parses a numeric string: an optional sign, digits with an optional decimal
+/// point, and an optional exponent. The number of digits after the decimal
+/// point is kept, so `"1.50"` and `"1.5"` parse to different encodings of the
+/// same value.
+///
+/// Like the BSON specification requires, numbers are never rounded: strings
+/// with more than 34 significant digits, or with an exponent out of range, are
+/// errors unless dropping or adding zeros makes them fit exactly. Use
+/// `Decimal128::from_str_rounded` to round them instead.
+///
+/// `Infinity`, `Inf`, `NaN` and `sNaN` (signaling NaN) are accepted in any
+/// case, with an optional sign. A NaN can be followed by up to 33 digits of
+/// diagnostic payload, as in `NaN123`, which is stored in its coefficient.
+/// ```
+/// use decimal128::*;
+///
+/// let dec128: Decimal128 = "-1.50E+3".parse().unwrap();
+/// assert_eq!("-1.50E+3", dec128.to_string());
+///
+/// let err = "1.2.3".parse::<Decimal128>().unwrap_err();
+/// assert_eq!(ParseDecimal128Error::InvalidCharacter { index: 3 }, err);
+/// ```
+impl FromStr for Decimal128 {
+    type Err = ParseDecimal128Error;
+
+    fn from_str(s: &str) -> Result<Self, ParseDecimal128Error> {
+        Decimal128::parse_ascii(s.as_bytes())
+    }
+}
+
+impl Decimal128 {
+    /// Parses a numeric string from bytes, exactly like `FromStr` does, so
+    /// input read off the wire doesn't have to be validated as UTF-8 first.
+    /// Any byte that isn't part of the syntax, including non-ASCII ones, is
+    /// reported as an `InvalidCharacter` at its byte index.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::parse_ascii(b"-12.50").unwrap();
+    /// assert_eq!("-12.50", dec128.to_string());
+    ///
+    /// let err = Decimal128::parse_ascii(b"1\xff").unwrap_err();
+    /// assert_eq!(ParseDecimal128Error::InvalidCharacter { index: 1 }, err);
+    /// ```
+    pub fn parse_ascii(s: &[u8]) -> Result<Decimal128, ParseDecimal128Error> {
+        match parse(s)? {
+            Number::Special(dec128) => Ok(dec128),
+            Number::Finite {
+                sign,
+                digits,
+                exponent,
+            } => from_digits_exact(sign, &digits, exponent),
+        }
+    }
+
+    /// Parses a numeric string like `FromStr` does, but also accepts
+    /// underscores after digits as separators, like Rust numeric literals
+    /// allow, so values copied from source code or config files parse as they
+    /// are. A leading `+` is accepted by `FromStr` already.
+    ///
+    /// The index of an `InvalidCharacter` error is into `s`, underscores
+    /// included.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::parse_lenient("+1_000_000.25").unwrap();
+    /// assert_eq!("1000000.25", dec128.to_string());
+    ///
+    /// let err = Decimal128::parse_lenient("_1").unwrap_err();
+    /// assert_eq!(ParseDecimal128Error::InvalidCharacter { index: 0 }, err);
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<Decimal128, ParseDecimal128Error> {
+        let bytes = s.as_bytes();
+        if !bytes.contains(&b'_') {
+            return Decimal128::parse_ascii(bytes);
+        }
+
+        // the kept bytes, and where each of them was in `s`
+        let mut kept = Vec::with_capacity(bytes.len());
+        let mut indices = Vec::with_capacity(bytes.len());
+        for (i, b) in bytes.iter().enumerate() {
+            if *b != b'_' {
+                kept.push(*b);
+                indices.push(i);
+            } else if i == 0 || !(bytes[i - 1].is_ascii_digit() || bytes[i - 1] == b'_') {
+                return Err(ParseDecimal128Error::InvalidCharacter { index: i });
+            }
+        }
+        Decimal128::parse_ascii(&kept).map_err(|e| match e {
+            ParseDecimal128Error::InvalidCharacter { index } => {
+                ParseDecimal128Error::InvalidCharacter {
+                    index: indices[index],
+                }
+            }
+            e => e,
+        })
+    }
+
+    /// Parses a numeric string like `FromStr` does, but rounds the result to
+    /// fit in a Decimal128 following the decimal arithmetic specification's
+    /// to-number rules, instead of failing:
+    ///
+    /// - more than 34 significant digits are rounded with `mode`;
+    /// - numbers too small for the smallest exponent are rounded to fewer
+    ///   digits, down to zero;
+    /// - numbers too large for the largest exponent become Infinity or the
+    ///   largest finite number, depending on `mode`;
+    /// - exponents a little too large for the coefficient are clamped by
+    ///   adding zeros to the coefficient, which never rounds.
+    ///
+    /// Also returns whether the result is inexact, i.e. whether rounding
+    /// changed the value.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let (dec128, inexact) =
+    ///     Decimal128::from_str_rounded("1.23456789012345678901234567890123456", RoundingMode::HalfEven)
+    ///         .unwrap();
+    /// assert_eq!("1.234567890123456789012345678901235", dec128.to_string());
+    /// assert!(inexact);
+    /// ```
+    pub fn from_str_rounded(
+        s: &str,
+        mode: RoundingMode,
+    ) -> Result<(Decimal128, bool), ParseDecimal128Error> {
+        let (dec128, flags) = Decimal128::from_str_rounded_with_flags(s, mode)?;
+        Ok((dec128, flags.inexact))
+    }
+
+    /// Parses like `from_str_rounded`, but returns the conditions rounding
+    /// raised instead of only whether it was inexact. Numbers too large for
+    /// a Decimal128 raise `overflow` even when `mode` rounds them to the
+    /// largest finite number instead of Infinity, and numbers rounded to a
+    /// subnormal value or zero raise `underflow`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let (dec128, flags) =
+    ///     Decimal128::from_str_rounded_with_flags("1E+6145", RoundingMode::Down).unwrap();
+    /// assert_eq!("9.999999999999999999999999999999999E+6144", dec128.to_string());
+    /// assert!(flags.overflow && flags.inexact);
+    /// let (dec128, flags) =
+    ///     Decimal128::from_str_rounded_with_flags("1E-6177", RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("0E-6176", dec128.to_string());
+    /// assert!(flags.underflow && flags.inexact);
+    /// ```
+    pub fn from_str_rounded_with_flags(
+        s: &str,
+        mode: RoundingMode,
+    ) -> Result<(Decimal128, Flags), ParseDecimal128Error> {
+        match parse(s.as_bytes())? {
+            Number::Special(dec128) => Ok((dec128, Flags::default())),
+            Number::Finite {
+                sign,
+                digits,
+                exponent,
+            } => Ok(from_digits_with_flags(sign, &digits, exponent, mode)),
+        }
+    }
+
+    /// Converts an `f64` to the shortest decimal that rounds back to the
+    /// same `f64`, which is the number `f64`'s `Display` prints. So `0.1`
+    /// becomes `0.1`, not the `0.1000000000000000055511151231257827` its
+    /// exact binary value would round to. NaN and the infinities convert to
+    /// their Decimal128 counterparts, and `-0.0` to `-0`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// assert_eq!("0.1", Decimal128::from_f64_shortest(0.1).to_string());
+    /// assert_eq!("1.5E+300", Decimal128::from_f64_shortest(1.5e300).to_string());
+    /// assert_eq!("0.30000000000000004", Decimal128::from_f64_shortest(0.1 + 0.2).to_string());
+    /// ```
+    pub fn from_f64_shortest(v: f64) -> Decimal128 {
+        // `{:e}` writes the shortest round-trip digits, at most 17 of them
+        // with an exponent within ±324, so parsing it never fails or rounds
+        format!("{:e}", v)
+            .parse()
+            .expect("f64 strings are valid Decimal128 strings")
+    }
+}
+
+// A parsed numeric string, before the digits are fit into a Decimal128.
+enum Number {
+    Special(Decimal128),
+    Finite {
+        sign: bool,
+        digits: Vec<u8>,
+        exponent: i64,
+    },
+}
+
+fn parse(s: &[u8]) -> Result<Number, ParseDecimal128Error> {
+    let (sign, start) = match s.first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+    let unsigned = &s[start..];
+    if unsigned.is_empty() {
+        return Err(ParseDecimal128Error::Empty);
+    }
+
+    if unsigned.eq_ignore_ascii_case(b"infinity") || unsigned.eq_ignore_ascii_case(b"inf") {
+        return Ok(Number::Special(special(sign, INFINITY, 0)));
+    }
+    if let Some((nan, len)) = nan_prefix(unsigned) {
+        let payload = parse_payload(&unsigned[len..], start + len)?;
+        return Ok(Number::Special(special(sign, nan, payload)));
+    }
+
+    let exponent_start = unsigned
+        .iter()
+        .position(|b| *b == b'e' || *b == b'E')
+        .unwrap_or(unsigned.len());
+    let (mantissa, exponent) = unsigned.split_at(exponent_start);
+
+    let mut digits = Vec::with_capacity(mantissa.len());
+    let mut fraction_digits = None;
+    for (i, b) in mantissa.iter().enumerate() {
+        match b {
+            b'0'..=b'9' => digits.push(b - b'0'),
+            b'.' if fraction_digits.is_none() => fraction_digits = Some(digits.len()),
+            _ => return Err(ParseDecimal128Error::InvalidCharacter { index: start + i }),
+        }
+    }
+    if digits.is_empty() {
+        return Err(ParseDecimal128Error::Empty);
+    }
+    let fraction_digits = fraction_digits.map_or(0, |point| digits.len() - point);
+
+    let exponent = match exponent.split_first() {
+        Some((_, exponent)) => parse_exponent(exponent, start + exponent_start + 1)?,
+        None => 0,
+    };
+    let exponent = (exponent - fraction_digits as i64).clamp(-EXPONENT_LIMIT, EXPONENT_LIMIT);
+    Ok(Number::Finite {
+        sign,
+        digits,
+        exponent,
+    })
+}
+
+// Whether a string starts with `NaN` or `sNaN`, in any case, and if so the
+// bits of that NaN and the length of the prefix.
+fn nan_prefix(s: &[u8]) -> Option<(u128, usize)> {
+    let starts_with =
+        |prefix: &[u8]| s.len() >= prefix.len() && s[..prefix.len()].eq_ignore_ascii_case(prefix);
+    if starts_with(b"nan") {
+        Some((NAN, 3))
+    } else if starts_with(b"snan") {
+        Some((SNAN, 4))
+    } else {
+        None
+    }
+}
+
+// Parse the diagnostic payload after `NaN`. `offset` is the index of its
+// first digit in the whole string.
+fn parse_payload(s: &[u8], offset: usize) -> Result<u128, ParseDecimal128Error> {
+    let mut payload: u128 = 0;
+    let mut digits = 0;
+    for (i, b) in s.iter().enumerate() {
+        match b {
+            b'0'..=b'9' => {
+                payload = payload * 10 + (b - b'0') as u128;
+                if payload != 0 {
+                    digits += 1;
+                }
+                if digits > MAX_DIGITS - 1 {
+                    return Err(ParseDecimal128Error::TooManyDigits);
+                }
+            }
+            _ => return Err(ParseDecimal128Error::InvalidCharacter { index: offset + i }),
+        }
+    }
+    Ok(payload)
+}
+
+// Parse the digits after an exponent marker, with an optional sign. `offset`
+// is the index of the first of them in the whole string.
+fn parse_exponent(s: &[u8], offset: usize) -> Result<i64, ParseDecimal128Error> {
+    let (negative, skip) = match s.first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+    if s.len() == skip {
+        return Err(ParseDecimal128Error::MalformedExponent);
+    }
+    let mut exponent: i64 = 0;
+    for (i, b) in s.iter().enumerate().skip(skip) {
+        match b {
+            b'0'..=b'9' => exponent = (exponent * 10 + (b - b'0') as i64).min(EXPONENT_LIMIT),
+            _ => return Err(ParseDecimal128Error::InvalidCharacter { index: offset + i }),
+        }
+    }
+    Ok(if negative { -exponent } else { exponent })
+}
+
+// Encode the parsed digits without rounding. Zeros are only dropped, or
+// added, where that doesn't change the value.
+fn from_digits_exact(
+    sign: bool,
+    digits: &[u8],
+    mut exponent: i64,
+) -> Result<Decimal128, ParseDecimal128Error> {
+    let leading_zeros = digits.iter().take_while(|d| **d == 0).count();
+    let mut digits = &digits[leading_zeros..];
+    while digits.len() > MAX_DIGITS as usize && digits.last() == Some(&0) {
+        digits = &digits[..digits.len() - 1];
+        exponent += 1;
+    }
+    if digits.len() > MAX_DIGITS as usize {
+        return Err(ParseDecimal128Error::TooManyDigits);
+    }
+
+    let coefficient = digits.iter().fold(0u128, |c, d| c * 10 + *d as u128);
+    Decimal128::from_parts_exact(sign, exponent as i32, coefficient)
+        .map_err(|_| ParseDecimal128Error::ExponentOverflow)
+}
+
+// The combination field of signaling NaN and the bit after it.
+const SNAN: u128 = 0b111111 << 121;
+
+// Encode a finite number from its decimal digits (most significant first)
+// and exponent, rounding with `mode` where they don't fit. Also returns
+// whether rounding changed the value.
+pub(crate) fn from_digits_rounded(
+    sign: bool,
+    digits: &[u8],
+    exponent: i64,
+    mode: RoundingMode,
+) -> (Decimal128, bool) {
+    let (dec128, flags) = from_digits_with_flags(sign, digits, exponent, mode);
+    (dec128, flags.inexact)
+}
+
+// Like `from_digits_rounded`, but returns the conditions rounding raised.
+// Overflow is raised whenever the rounded number is too large, including
+// when `mode` makes it the largest finite number instead of Infinity.
+pub(crate) fn from_digits_with_flags(
+    sign: bool,
+    digits: &[u8],
+    exponent: i64,
+    mode: RoundingMode,
+) -> (Decimal128, Flags) {
+    let leading_zeros = digits.iter().take_while(|d| **d == 0).count();
+    let digits = &digits[leading_zeros..];
+    if digits.is_empty() {
+        let exponent = exponent.clamp(MIN_EXPONENT as i64, MAX_EXPONENT as i64);
+        let zero = Decimal128::from_parts(sign, exponent as i16, 0);
+        return (zero, Flags::default());
+    }
+
+    // drop enough digits to leave at most 34, and to bring the exponent up to
+    // the smallest one there is
+    let excess = digits.len().saturating_sub(MAX_DIGITS as usize) as i64;
+    let dropped = excess.max(MIN_EXPONENT as i64 - exponent).max(0);
+    let kept = digits.len().saturating_sub(dropped as usize);
+    let mut coefficient = digits[..kept]
+        .iter()
+        .fold(0u128, |c, d| c * 10 + *d as u128);
+    let mut exponent = exponent + dropped;
+    // the dropped digits after `digit` are `zeros` zeros and then `rest`
+    let (digit, sticky, zeros, rest) = if dropped == 0 {
+        (0, false, 0, &[][..])
+    } else if dropped as usize > digits.len() {
+        // every digit is dropped, along with zeros in front of them
+        (0, true, dropped as usize - digits.len() - 1, digits)
+    } else {
+        let rest = &digits[kept + 1..];
+        (digits[kept], rest.iter().any(|d| *d != 0), 0, rest)
+    };
+    let inexact = digit != 0 || sticky;
+    let rest = iter::repeat_n(0, zeros).chain(rest.iter().copied());
+    if mode.rounds_up(sign, coefficient % 2 == 1, digit, sticky, rest) {
+        coefficient += 1;
+    }
+    if coefficient == pow10(MAX_DIGITS) {
+        coefficient /= 10;
+        exponent += 1;
+    }
+
+    let flags = |dec128: &Decimal128| Flags {
+        inexact,
+        underflow: inexact && subnormal(dec128),
+        ..Flags::default()
+    };
+    if coefficient == 0 {
+        let zero = Decimal128::from_parts(sign, exponent as i16, 0);
+        let flags = flags(&zero);
+        return (zero, flags);
+    }
+    let adjusted = exponent + digit_count(coefficient) as i64 - 1;
+    if adjusted > MAX_EXPONENT as i64 + MAX_DIGITS as i64 - 1 {
+        let flags = Flags {
+            inexact: true,
+            overflow: true,
+            ..Flags::default()
+        };
+        return (overflow(sign, mode), flags);
+    }
+    // clamp the exponent by padding the coefficient with zeros
+    while exponent > MAX_EXPONENT as i64 {
+        coefficient *= 10;
+        exponent -= 1;
+    }
+    let dec128 = Decimal128::from_parts(sign, exponent as i16, coefficient);
+    let flags = flags(&dec128);
+    (dec128, flags)
+}
+
+// The result of rounding a number too large for any finite Decimal128.
+fn overflow(sign: bool, mode: RoundingMode) -> Decimal128 {
+    let infinite = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Ceiling => !sign,
+        RoundingMode::Floor => sign,
+        _ => true,
+    };
+    if infinite {
+        special(sign, INFINITY, 0)
+    } else {
+        Decimal128::from_parts(sign, MAX_EXPONENT, pow10(MAX_DIGITS) - 1)
+    }
+}
@@ -0,0 +1,65 @@
+//! Percentages: rates kept as fractions, like `0.125`, written and read as
+//! `12.5%`.
+use crate::{Decimal128, ParseDecimal128Error};
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+impl Decimal128 {
+    /// Writes the value as a percentage with `dp` digits after the decimal
+    /// point: it's multiplied by 100, exactly, rounded half-even to `dp`
+    /// places and followed by `%`. NaN and Infinity are written as
+    /// `to_string` writes them, followed by `%`.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let rate: Decimal128 = "0.125".parse().unwrap();
+    /// assert_eq!("12.5%", rate.to_percent_string(1));
+    /// assert_eq!("12.50%", rate.to_percent_string(2));
+    /// assert_eq!("12%", rate.to_percent_string(0));
+    /// ```
+    pub fn to_percent_string(&self, dp: u32) -> String {
+        format!("{:.*}%", dp as usize, Percent(self))
+    }
+
+    /// Parses a percentage like `12.5%` into the fraction it stands for,
+    /// `0.125`. The `%` is optional, and the number before it is read like
+    /// `parse` reads it; dividing it by 100 only moves its exponent, so the
+    /// result is exact and keeps the number's digits.
+    ///
+    /// Fails like `parse` does, and with
+    /// `ParseDecimal128Error::ExponentOverflow` for the few numbers too small
+    /// to divide by 100 exactly.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// assert_eq!("0.125", Decimal128::from_percent_str("12.5%").unwrap().to_string());
+    /// assert_eq!("-0.0300", Decimal128::from_percent_str("-3.00%").unwrap().to_string());
+    /// assert_eq!("0.07", Decimal128::from_percent_str("7").unwrap().to_string());
+    /// ```
+    pub fn from_percent_str(s: &str) -> Result<Decimal128, ParseDecimal128Error> {
+        let number: Decimal128 = s.strip_suffix('%').unwrap_or(s).parse()?;
+        if number.nan || number.inf {
+            return Ok(number);
+        }
+        Decimal128::from_parts_exact(
+            number.sign,
+            number.exponent.to_adjusted() as i32 - 2,
+            number.significand.to_num(),
+        )
+        .map_err(|_| ParseDecimal128Error::ExponentOverflow)
+    }
+}
+
+// A value formatted as 100 times itself, with the precision of the Formatter
+// as the number of decimal places.
+struct Percent<'a>(&'a Decimal128);
+
+impl fmt::Display for Percent<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.nan || self.0.inf {
+            return fmt::Display::fmt(self.0, fmt);
+        }
+        self.0.fmt_fixed(fmt, fmt.precision().unwrap_or(0), 2)
+    }
+}
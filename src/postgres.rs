@@ -0,0 +1,150 @@
+//! PostgreSQL NUMERIC support for tokio-postgres and postgres.
+//!
+//! NUMERIC's binary wire format is a header of four 16-bit fields followed by
+//! the digits in base 10000:
+//! [ndigits] [weight] [sign] [dscale] [digit 0] ... [digit ndigits - 1]
+//!
+//! The value is `sum(digit[i] * 10000^(weight - i))` and dscale is the number
+//! of digits shown after the decimal point.
+use crate::{pow10, ConversionError, Decimal128, MAX_DIGITS};
+use bytes::{BufMut, BytesMut};
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+
+impl<'a> FromSql<'a> for Decimal128 {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if raw.len() < 8 {
+            return Err("invalid buffer size for NUMERIC".into());
+        }
+        let read_u16 = |i: usize| u16::from_be_bytes([raw[2 * i], raw[2 * i + 1]]);
+        let ndigits = read_u16(0) as usize;
+        let weight = read_u16(1) as i16;
+        let sign = read_u16(2);
+        let dscale = read_u16(3);
+        if raw.len() != 8 + 2 * ndigits {
+            return Err("invalid buffer size for NUMERIC".into());
+        }
+
+        let mut bytes = [0u8; 16];
+        match sign {
+            NUMERIC_NAN => bytes[0] = 0x7c,
+            NUMERIC_PINF => bytes[0] = 0x78,
+            NUMERIC_NINF => bytes[0] = 0xf8,
+            NUMERIC_POS | NUMERIC_NEG => {}
+            _ => return Err("invalid sign for NUMERIC".into()),
+        }
+        if bytes[0] != 0 {
+            return Ok(Decimal128::from_raw_bytes(bytes));
+        }
+
+        let mut digits: Vec<u16> = (0..ndigits).map(|i| read_u16(4 + i)).collect();
+        if digits.iter().any(|d| *d >= 10000) {
+            return Err("invalid digit for NUMERIC".into());
+        }
+        // trailing zero groups don't change the value, so drop them before
+        // they can overflow the coefficient
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        let mut coefficient: u128 = 0;
+        for digit in &digits {
+            coefficient = coefficient
+                .checked_mul(10000)
+                .and_then(|c| c.checked_add(*digit as u128))
+                .ok_or(ConversionError::Inexact)?;
+        }
+        // keep as many digits after the decimal point as dscale says, so 1.50
+        // stays 1.50 rather than becoming 1.5
+        let target = -(dscale as i32);
+        let mut exponent = if digits.is_empty() {
+            target
+        } else {
+            4 * (weight as i32 - digits.len() as i32 + 1)
+        };
+        let max_coefficient = pow10(MAX_DIGITS) - 1;
+        while exponent > target && coefficient <= max_coefficient / 10 {
+            coefficient *= 10;
+            exponent -= 1;
+        }
+        while exponent < target && coefficient.is_multiple_of(10) {
+            coefficient /= 10;
+            exponent += 1;
+        }
+
+        Ok(Decimal128::from_parts_exact(
+            sign == NUMERIC_NEG,
+            exponent,
+            coefficient,
+        )?)
+    }
+
+    accepts!(NUMERIC);
+}
+
+impl ToSql for Decimal128 {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if self.nan || self.inf {
+            let sign = if self.nan {
+                NUMERIC_NAN
+            } else if self.sign {
+                NUMERIC_NINF
+            } else {
+                NUMERIC_PINF
+            };
+            out.put_u16(0);
+            out.put_i16(0);
+            out.put_u16(sign);
+            out.put_u16(0);
+            return Ok(IsNull::No);
+        }
+
+        let exponent = self.exponent.to_adjusted() as i32;
+        let dscale = (-exponent).max(0) as u16;
+        // line the coefficient up with base 10000 digit boundaries; the
+        // coefficient has at most 34 digits, so three more always fit in a u128
+        let pad = exponent.rem_euclid(4);
+        let mut coefficient = self.significand.to_num() * pow10(pad as u32);
+        let group_exponent = (exponent - pad) / 4;
+
+        let mut digits = Vec::new();
+        while coefficient > 0 {
+            digits.push((coefficient % 10000) as i16);
+            coefficient /= 10000;
+        }
+        // digits were collected least significant first, so trailing zero
+        // groups are at the front
+        let trailing_zeros = digits.iter().take_while(|d| **d == 0).count();
+        digits.drain(..trailing_zeros);
+        digits.reverse();
+
+        let weight = if digits.is_empty() {
+            0
+        } else {
+            group_exponent + trailing_zeros as i32 + digits.len() as i32 - 1
+        };
+        let sign = if self.sign && !digits.is_empty() {
+            NUMERIC_NEG
+        } else {
+            NUMERIC_POS
+        };
+
+        out.put_u16(digits.len() as u16);
+        out.put_i16(weight as i16);
+        out.put_u16(sign);
+        out.put_u16(dscale);
+        for digit in digits {
+            out.put_i16(digit);
+        }
+        Ok(IsNull::No)
+    }
+
+    accepts!(NUMERIC);
+    to_sql_checked!();
+}
@@ -0,0 +1,95 @@
+//! [proptest](https://docs.rs/proptest) strategies for Decimal128.
+//!
+//! Finite values shrink toward positive zero: the coefficient shrinks toward
+//! 0, the exponent toward 0 and the sign toward positive. `Decimal128` also
+//! implements `Arbitrary`, so `any::<Decimal128>()` draws from all of the
+//! strategies below.
+//! ```
+//! use decimal128::proptest::finite;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(dec128 in finite())| {
+//!     let bytes = dec128.to_raw_bytes();
+//!     prop_assert_eq!(bytes, decimal128::Decimal128::from_raw_bytes(bytes).to_raw_bytes());
+//! });
+//! ```
+use crate::{pow10, Decimal128, EXPONENT_BIAS, MAX_DIGITS, MAX_EXPONENT, MIN_EXPONENT};
+use ::proptest::arbitrary::Arbitrary;
+use ::proptest::prelude::*;
+use ::proptest::strategy::BoxedStrategy;
+
+/// Canonical finite values with any sign, exponent and coefficient.
+pub fn finite() -> impl Strategy<Value = Decimal128> {
+    // every exponent is equally likely; boundary_exponent weights the ends.
+    // A signed range shrinks toward 0, not toward its start.
+    let exponents = MIN_EXPONENT..=MAX_EXPONENT;
+    (any::<bool>(), exponents, 0..pow10(MAX_DIGITS)).prop_map(|(sign, exponent, coefficient)| {
+        Decimal128::from_parts(sign, exponent, coefficient)
+    })
+}
+
+/// NaN and Infinity, with both signs.
+pub fn special() -> impl Strategy<Value = Decimal128> {
+    prop_oneof![Just(0x7c), Just(0xfc), Just(0x78), Just(0xf8)].prop_map(|first_byte| {
+        let mut bytes = [0u8; 16];
+        bytes[0] = first_byte;
+        Decimal128::from_raw_bytes(bytes)
+    })
+}
+
+/// Non-canonical finite encodings, whose coefficient is larger than 34 digits
+/// allow. The spec treats all of these as zero.
+///
+/// This covers coefficients between 10^34 and 2^113 - 1, and encodings that
+/// use the `11` combination field, where the implicit coefficient is always
+/// larger than 2^113.
+pub fn non_canonical() -> impl Strategy<Value = Decimal128> {
+    let biased_exponent = 0..=(MAX_EXPONENT + EXPONENT_BIAS) as u128;
+    let large_coefficient = (
+        any::<bool>(),
+        biased_exponent.clone(),
+        pow10(MAX_DIGITS)..1 << 113,
+    )
+        .prop_map(|(sign, exponent, coefficient)| {
+            ((sign as u128) << 127) | (exponent << 113) | coefficient
+        });
+    let combination_11 = (any::<bool>(), biased_exponent, 0..1u128 << 111).prop_map(
+        |(sign, exponent, coefficient)| {
+            ((sign as u128) << 127) | (0b11 << 125) | (exponent << 111) | coefficient
+        },
+    );
+    prop_oneof![large_coefficient, combination_11]
+        .prop_map(|bits| Decimal128::from_raw_bytes(bits.to_be_bytes()))
+}
+
+/// Finite values whose exponent is at or next to the ends of the exponent
+/// range, or next to zero.
+pub fn boundary_exponent() -> impl Strategy<Value = Decimal128> {
+    let exponents = prop_oneof![
+        Just(MIN_EXPONENT),
+        Just(MIN_EXPONENT + 1),
+        Just(-1),
+        Just(0),
+        Just(1),
+        Just(MAX_EXPONENT - 1),
+        Just(MAX_EXPONENT),
+    ];
+    (any::<bool>(), exponents, 0..pow10(MAX_DIGITS)).prop_map(|(sign, exponent, coefficient)| {
+        Decimal128::from_parts(sign, exponent, coefficient)
+    })
+}
+
+impl Arbitrary for Decimal128 {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Decimal128>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            6 => finite(),
+            2 => boundary_exponent(),
+            1 => special(),
+            1 => non_canonical(),
+        ]
+        .boxed()
+    }
+}
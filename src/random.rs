@@ -0,0 +1,196 @@
+//! Random Decimal128 generation with the `rand` crate.
+use crate::{
+    digit_count, pow10, round_coefficient, Decimal128, RoundingMode, MAX_DIGITS, MAX_EXPONENT,
+    MIN_EXPONENT,
+};
+use ::rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use ::rand::distributions::{Distribution, Standard};
+use ::rand::Rng;
+
+/// Samples values in `[0, 1)` with 34 digits after the decimal point, like
+/// `Standard` does for floats.
+impl Distribution<Decimal128> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Decimal128 {
+        let coefficient = rng.gen_range(0..pow10(MAX_DIGITS));
+        Decimal128::from_parts(false, -(MAX_DIGITS as i16), coefficient)
+    }
+}
+
+/// Samples Decimal128 values uniformly from a range.
+///
+/// Values are drawn from a grid of evenly spaced decimals: the finest one on
+/// which both ends of the range have at most 34 digits. For `1.5..=20`, that's
+/// every multiple of 10^-33 between them.
+/// ```
+/// use decimal128::*;
+/// use rand::Rng;
+///
+/// let low = Decimal128::from_mysql_decimal("1.50", RoundingMode::HalfEven).unwrap();
+/// let high = Decimal128::from_mysql_decimal("20", RoundingMode::HalfEven).unwrap();
+/// let dec128 = rand::thread_rng().gen_range(low..=high);
+/// assert!(dec128.is_positive());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UniformDecimal128 {
+    low: i128,
+    high: i128,
+    exponent: i16,
+}
+
+impl UniformDecimal128 {
+    fn from_bounds(low: &Decimal128, high: &Decimal128, inclusive: bool) -> Self {
+        assert!(
+            !low.nan && !low.inf && !high.nan && !high.inf,
+            "UniformDecimal128 needs finite bounds"
+        );
+        let exponent = common_exponent(low, high);
+        let (low, _) = to_scaled(low, exponent, RoundingMode::Ceiling);
+        let (mut high, high_exact) = to_scaled(high, exponent, RoundingMode::Floor);
+        if !inclusive && high_exact {
+            // `high` itself is on the grid, so leave it out
+            high -= 1;
+        }
+        assert!(low <= high, "UniformDecimal128 needs low < high");
+        UniformDecimal128 {
+            low,
+            high,
+            exponent,
+        }
+    }
+}
+
+impl UniformSampler for UniformDecimal128 {
+    type X = Decimal128;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Decimal128> + Sized,
+        B2: SampleBorrow<Decimal128> + Sized,
+    {
+        UniformDecimal128::from_bounds(low.borrow(), high.borrow(), false)
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Decimal128> + Sized,
+        B2: SampleBorrow<Decimal128> + Sized,
+    {
+        UniformDecimal128::from_bounds(low.borrow(), high.borrow(), true)
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Decimal128 {
+        let n = rng.gen_range(self.low..=self.high);
+        // rounding the bounds can carry them up to 10^34, which still has
+        // an exact encoding with one less zero
+        Decimal128::from_parts_exact(n < 0, self.exponent as i32, n.unsigned_abs()).unwrap()
+    }
+}
+
+impl SampleUniform for Decimal128 {
+    type Sampler = UniformDecimal128;
+}
+
+impl Decimal128 {
+    /// Samples a value uniformly from `low..=high` with exactly `scale`
+    /// digits after the decimal point, e.g. whole cents for a scale of 2.
+    /// Every multiple of 10^-scale in the range is equally likely, and the
+    /// result always has exponent `-scale`, so `2` comes back as `2.00`.
+    ///
+    /// Panics if a bound is NaN or Infinity, if `-scale` isn't a valid
+    /// exponent, if a bound needs more than 34 digits at that scale, or if
+    /// there is no such value in the range.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let low: Decimal128 = "1".parse().unwrap();
+    /// let high: Decimal128 = "100".parse().unwrap();
+    /// let price = Decimal128::random_range(&low, &high, 2, &mut rand::thread_rng());
+    /// assert_eq!(Some(2), price.scale());
+    /// ```
+    pub fn random_range<R: Rng + ?Sized>(
+        low: &Decimal128,
+        high: &Decimal128,
+        scale: i32,
+        rng: &mut R,
+    ) -> Decimal128 {
+        assert!(
+            !low.nan && !low.inf && !high.nan && !high.inf,
+            "random_range needs finite bounds"
+        );
+        let exponent = scale.checked_neg().unwrap_or(i32::MAX);
+        assert!(
+            exponent >= MIN_EXPONENT as i32 && exponent <= MAX_EXPONENT as i32,
+            "random_range needs a scale within -6111..=6176"
+        );
+        let exponent = exponent as i16;
+        assert!(
+            fits(low, exponent) && fits(high, exponent),
+            "random_range bounds need at most 34 digits at the given scale"
+        );
+        let (low, _) = to_scaled(low, exponent, RoundingMode::Ceiling);
+        let (high, _) = to_scaled(high, exponent, RoundingMode::Floor);
+        assert!(
+            low <= high,
+            "random_range needs a value with the given scale between low and high"
+        );
+        let n = rng.gen_range(low..=high);
+        Decimal128::from_parts(n < 0, exponent, n.unsigned_abs())
+    }
+}
+
+// Whether a finite value has at most 34 digits when written as a multiple of
+// 10^exponent, leaving aside the digits that would be rounded off.
+fn fits(v: &Decimal128, exponent: i16) -> bool {
+    let coefficient = v.significand.to_num();
+    let own_exponent = v.exponent.to_adjusted();
+    coefficient == 0
+        || own_exponent <= exponent
+        || digit_count(coefficient) as i32 + (own_exponent - exponent) as i32 <= MAX_DIGITS as i32
+}
+
+// The smallest exponent at which both values have at most 34 digits.
+fn common_exponent(a: &Decimal128, b: &Decimal128) -> i16 {
+    let exponent = a.exponent.to_adjusted().min(b.exponent.to_adjusted());
+    let top = |v: &Decimal128| {
+        let coefficient = v.significand.to_num();
+        if coefficient == 0 {
+            i16::MIN
+        } else {
+            digit_count(coefficient) as i16 - 1 + v.exponent.to_adjusted()
+        }
+    };
+    let top = top(a).max(top(b));
+    exponent.max(top.saturating_sub(MAX_DIGITS as i16 - 1))
+}
+
+// A finite value as a signed integer number of 10^exponent units, rounded with
+// `mode` if it has digits below that. Also returns whether it was exact.
+fn to_scaled(v: &Decimal128, exponent: i16, mode: RoundingMode) -> (i128, bool) {
+    let own_exponent = v.exponent.to_adjusted();
+    let coefficient = v.significand.to_num();
+    let (magnitude, inexact) = if coefficient == 0 {
+        (0, false)
+    } else if own_exponent >= exponent {
+        (coefficient * pow10((own_exponent - exponent) as u32), false)
+    } else {
+        round_coefficient(v.sign, coefficient, (exponent - own_exponent) as u32, mode)
+    };
+    let n = magnitude as i128;
+    (if v.sign { -n } else { n }, !inexact)
+}
+
+// Whether stochastic rounding rounds up, given the discarded digits, most
+// significant first. They're compared with the digits of a random fraction
+// in [0, 1), one at a time until the two differ, which rounds up with a
+// probability of exactly the discarded fraction and usually needs only one
+// random digit.
+pub(crate) fn rounds_up(discarded: impl Iterator<Item = u8>) -> bool {
+    let mut rng = ::rand::thread_rng();
+    for digit in discarded {
+        let random = rng.gen_range(0..10);
+        if random != digit {
+            return random < digit;
+        }
+    }
+    false
+}
@@ -0,0 +1,155 @@
+//! Conversions between Decimal128 and ratios of integers.
+use crate::{
+    digit_count, pow10, round_coefficient, ConversionError, Decimal128, RoundingMode, MAX_DIGITS,
+};
+
+impl Decimal128 {
+    /// Returns `numerator / denominator`, rounded to 34 digits with `mode`
+    /// only once, for odds, ratios and rates that arrive as pairs of
+    /// integers. Dividing the integers directly keeps every digit of the
+    /// quotient that matters, where converting them to Decimal128 first
+    /// would round integers with more than 34 digits before dividing.
+    ///
+    /// An exact quotient has as few digits after the decimal point as it
+    /// needs, so 1/4 is `0.25` and 6/3 is `2`. A zero numerator gives 0
+    /// whatever the sign of the denominator. Fails with
+    /// `ConversionError::Invalid` if the denominator is zero.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let odds = Decimal128::from_ratio(2, 3, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("0.6666666666666666666666666666666667", odds.to_string());
+    /// let odds = Decimal128::from_ratio(2, 3, RoundingMode::Down).unwrap();
+    /// assert_eq!("0.6666666666666666666666666666666666", odds.to_string());
+    /// let share = Decimal128::from_ratio(-3, 8, RoundingMode::HalfEven).unwrap();
+    /// assert_eq!("-0.375", share.to_string());
+    /// ```
+    pub fn from_ratio(
+        numerator: i128,
+        denominator: i128,
+        mode: RoundingMode,
+    ) -> Result<Decimal128, ConversionError> {
+        if denominator == 0 {
+            return Err(ConversionError::Invalid);
+        }
+        let sign = numerator != 0 && (numerator < 0) != (denominator < 0);
+        let (dividend, divisor) = (numerator.unsigned_abs(), denominator.unsigned_abs());
+
+        // long division, one digit at a time, until it comes out even or
+        // there are 35 digits: 34 to keep and one to round with
+        let (mut quotient, mut remainder) = (dividend / divisor, dividend % divisor);
+        let mut exponent: i32 = 0;
+        while remainder != 0 && quotient < pow10(MAX_DIGITS) {
+            let (digit, rest) = next_digit(remainder, divisor);
+            quotient = quotient * 10 + digit;
+            remainder = rest;
+            exponent -= 1;
+        }
+        if remainder != 0 {
+            // a non-zero digit past the rounding digit tells rounding that
+            // the rest isn't exactly zero. A quotient of more than 35 digits
+            // already has digits past it, and making the last one non-zero
+            // does the same without overflowing.
+            if quotient < pow10(MAX_DIGITS + 1) {
+                quotient = quotient * 10 + 1;
+                exponent -= 1;
+            } else if quotient % 10 == 0 {
+                quotient += 1;
+            }
+        }
+
+        let count = digit_count(quotient).saturating_sub(MAX_DIGITS);
+        let (mut coefficient, _) = round_coefficient(sign, quotient, count, mode);
+        exponent += count as i32;
+        if coefficient == pow10(MAX_DIGITS) {
+            coefficient /= 10;
+            exponent += 1;
+        }
+        // 128-bit integers and their quotients are far from the limits of
+        // the exponent
+        Ok(Decimal128::from_parts(sign, exponent as i16, coefficient))
+    }
+
+    /// Returns the value as an exact fraction, a numerator and a positive
+    /// denominator in lowest terms, for exact arithmetic that can't afford
+    /// to round the value first. The denominator is always a power of ten
+    /// divided by whatever the coefficient shares with it, so `1.50` is 3/2
+    /// and `-120` is -120/1. Zeros of either sign are 0/1.
+    ///
+    /// Fails with `ConversionError::NotFinite` for NaN and Infinity, and with
+    /// `ConversionError::Overflow` if the numerator doesn't fit in an i128 or
+    /// the denominator in a u128.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let price: Decimal128 = "1.50".parse().unwrap();
+    /// assert_eq!(Ok((3, 2)), price.to_ratio());
+    /// let rate: Decimal128 = "-0.0125".parse().unwrap();
+    /// assert_eq!(Ok((-1, 80)), rate.to_ratio());
+    /// let big: Decimal128 = "1.2E+3".parse().unwrap();
+    /// assert_eq!(Ok((1200, 1)), big.to_ratio());
+    /// ```
+    pub fn to_ratio(&self) -> Result<(i128, u128), ConversionError> {
+        if self.nan || self.inf {
+            return Err(ConversionError::NotFinite);
+        }
+        let mut coefficient = self.significand.to_num();
+        let exponent = self.exponent.to_adjusted() as i32;
+        if coefficient == 0 {
+            return Ok((0, 1));
+        }
+
+        let (numerator, denominator) = if exponent >= 0 {
+            let numerator = 10u128
+                .checked_pow(exponent as u32)
+                .and_then(|scale| coefficient.checked_mul(scale))
+                .ok_or(ConversionError::Overflow)?;
+            (numerator, 1)
+        } else {
+            // the denominator is 2^twos * 5^fives, starting from 10^-exponent
+            // and cancelling the factors of 2 and 5 in the coefficient, which
+            // are the only factors the two can share
+            let (mut twos, mut fives) = (exponent.unsigned_abs(), exponent.unsigned_abs());
+            while twos > 0 && coefficient.is_multiple_of(2) {
+                coefficient /= 2;
+                twos -= 1;
+            }
+            while fives > 0 && coefficient.is_multiple_of(5) {
+                coefficient /= 5;
+                fives -= 1;
+            }
+            let denominator = 1u128
+                .checked_shl(twos)
+                .and_then(|power| power.checked_mul(5u128.checked_pow(fives)?))
+                .ok_or(ConversionError::Overflow)?;
+            (coefficient, denominator)
+        };
+        // a multiple of 10^exponent is never 2^127, so the magnitude of any
+        // numerator that fits is at most i128::MAX
+        if numerator > i128::MAX as u128 {
+            return Err(ConversionError::Overflow);
+        }
+        let numerator = numerator as i128;
+        Ok((if self.sign { -numerator } else { numerator }, denominator))
+    }
+}
+
+// Ten times `remainder` divided by `divisor`, as the next digit of a long
+// division and the remainder after it. `remainder` is less than `divisor`,
+// and taking the digit off one addition at a time keeps the product from
+// overflowing when both are close to 2^127.
+fn next_digit(remainder: u128, divisor: u128) -> (u128, u128) {
+    if let Some(scaled) = remainder.checked_mul(10) {
+        return (scaled / divisor, scaled % divisor);
+    }
+    let (mut digit, mut rest) = (0, 0);
+    for _ in 0..10 {
+        if rest >= divisor - remainder {
+            rest -= divisor - remainder;
+            digit += 1;
+        } else {
+            rest += remainder;
+        }
+    }
+    (digit, rest)
+}
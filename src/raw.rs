@@ -0,0 +1,68 @@
+//! A plain-bytes view of the Decimal128 encoding, for reinterpreting
+//! buffers of encoded values in place.
+//!
+//! Decimal128 keeps its decoded sign, exponent and coefficient next to the
+//! encoding, so it can't be cast from bytes. RawDecimal128 is nothing but
+//! the 16 bytes, with the alignment of a byte, so a buffer holding values
+//! back to back can be viewed as a slice of them with `bytemuck` or
+//! `zerocopy` and decoded one value at a time.
+use crate::Decimal128;
+
+/// The 16 bytes of a Decimal128 encoding, most significant first like
+/// `to_raw_bytes`. Equality and hashing compare the bytes, so unlike
+/// Decimal128, `1.0` and `1.00` aren't equal. It's also the `decimal128_t`
+/// the C bindings pass around.
+///
+/// With the `bytemuck` feature this is `Pod`, and with the `zerocopy`
+/// feature it's `FromBytes`, `IntoBytes`, `KnownLayout` and `Immutable`.
+/// ```
+/// use decimal128::*;
+///
+/// let price: Decimal128 = "12.50".parse().unwrap();
+/// let raw = RawDecimal128::from(&price);
+/// assert_eq!(price.to_raw_bytes(), raw.bytes);
+/// assert_eq!("12.50", Decimal128::from(raw).to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
+#[repr(C)]
+pub struct RawDecimal128 {
+    pub bytes: [u8; 16],
+}
+
+impl From<RawDecimal128> for Decimal128 {
+    fn from(raw: RawDecimal128) -> Self {
+        Decimal128::from_raw_bytes(raw.bytes)
+    }
+}
+
+impl From<&Decimal128> for RawDecimal128 {
+    fn from(v: &Decimal128) -> Self {
+        RawDecimal128 {
+            bytes: v.to_raw_bytes(),
+        }
+    }
+}
+
+impl From<Decimal128> for RawDecimal128 {
+    fn from(v: Decimal128) -> Self {
+        RawDecimal128 {
+            bytes: v.to_raw_bytes(),
+        }
+    }
+}
+
+// Safety: a struct holding only a byte array has no padding, and every bit
+// pattern, including all zeros, is a valid value.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for RawDecimal128 {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for RawDecimal128 {}
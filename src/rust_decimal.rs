@@ -0,0 +1,111 @@
+//! Conversions between Decimal128 and `rust_decimal::Decimal`.
+//!
+//! rust_decimal stores a 96-bit coefficient (up to 28 or 29 digits) and a
+//! scale between 0 and 28, so every rust_decimal value fits in a Decimal128,
+//! but going the other way can overflow or need rounding.
+use crate::{pow10, round_coefficient, ConversionError, Decimal128, RoundingMode};
+use std::convert::TryFrom;
+
+// Largest coefficient a rust_decimal can hold, 2^96 - 1.
+const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+impl From<::rust_decimal::Decimal> for Decimal128 {
+    fn from(v: ::rust_decimal::Decimal) -> Self {
+        let coefficient = v.mantissa().unsigned_abs();
+        Decimal128::from_parts(v.is_sign_negative(), -(v.scale() as i16), coefficient)
+    }
+}
+
+impl TryFrom<Decimal128> for ::rust_decimal::Decimal {
+    type Error = ConversionError;
+
+    /// Converts without rounding. Returns `ConversionError::Inexact` if the
+    /// value has more digits than a rust_decimal can hold; use
+    /// `Decimal128::to_rust_decimal` to round instead.
+    fn try_from(v: Decimal128) -> Result<Self, ConversionError> {
+        to_rust_decimal(&v, None)
+    }
+}
+
+impl Decimal128 {
+    /// Convert to a `rust_decimal::Decimal`, rounding with `mode` if this
+    /// value has more fractional digits than rust_decimal can hold.
+    ///
+    /// Returns `ConversionError::Overflow` if the integer part alone doesn't
+    /// fit in 96 bits, and `ConversionError::NotFinite` for NaN and Infinity.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let dec128 = Decimal128::from_mysql_decimal(
+    ///     "0.12345678901234567890123456789",
+    ///     RoundingMode::HalfEven,
+    /// )
+    /// .unwrap();
+    /// let decimal = dec128.to_rust_decimal(RoundingMode::Down).unwrap();
+    /// assert_eq!("0.1234567890123456789012345678", decimal.to_string());
+    /// ```
+    pub fn to_rust_decimal(
+        &self,
+        mode: RoundingMode,
+    ) -> Result<::rust_decimal::Decimal, ConversionError> {
+        to_rust_decimal(self, Some(mode))
+    }
+}
+
+// Convert to rust_decimal, rounding with `mode` or failing with `Inexact` if
+// there isn't one.
+fn to_rust_decimal(
+    v: &Decimal128,
+    mode: Option<RoundingMode>,
+) -> Result<::rust_decimal::Decimal, ConversionError> {
+    if v.nan || v.inf {
+        return Err(ConversionError::NotFinite);
+    }
+
+    let exponent = v.exponent.to_adjusted() as i32;
+    let coefficient = v.significand.to_num();
+    let max_scale = ::rust_decimal::Decimal::MAX_SCALE as i32;
+
+    let (mantissa, scale) = if exponent >= 0 {
+        // positive exponents have to be folded into the coefficient
+        let mantissa = if coefficient == 0 {
+            0
+        } else if exponent > 38 {
+            return Err(ConversionError::Overflow);
+        } else {
+            coefficient
+                .checked_mul(pow10(exponent as u32))
+                .ok_or(ConversionError::Overflow)?
+        };
+        (mantissa, 0)
+    } else {
+        // drop as few trailing digits as it takes to get both the scale and
+        // the coefficient into range
+        let mut drop = (-exponent - max_scale).max(0);
+        loop {
+            if drop > -exponent {
+                return Err(ConversionError::Overflow);
+            }
+            let rounding = mode.unwrap_or(RoundingMode::Down);
+            let (rounded, inexact) = round_coefficient(v.sign, coefficient, drop as u32, rounding);
+            if inexact && mode.is_none() {
+                return Err(ConversionError::Inexact);
+            }
+            if rounded <= MAX_MANTISSA {
+                break (rounded, (-exponent - drop) as u32);
+            }
+            drop += 1;
+        }
+    };
+
+    if mantissa > MAX_MANTISSA {
+        return Err(ConversionError::Overflow);
+    }
+    Ok(::rust_decimal::Decimal::from_parts(
+        mantissa as u32,
+        (mantissa >> 32) as u32,
+        (mantissa >> 64) as u32,
+        v.sign,
+        scale,
+    ))
+}
@@ -0,0 +1,137 @@
+//! serde support.
+//!
+//! Decimal128 serializes as its string, like `to_string` writes it, so no
+//! digits are lost to formats that read numbers as doubles. Deserializing is
+//! lenient, and `serde_strict` is there for fields that should only ever be
+//! strings.
+use crate::{ConversionError, Decimal128};
+use ::serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use ::serde::{Serialize, Serializer};
+use core::convert::TryFrom;
+use core::fmt;
+
+// The Extended JSON key wrapping a decimal string.
+const NUMBER_DECIMAL: &str = "$numberDecimal";
+
+impl Serialize for Decimal128 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Accepts a decimal string, an integer, a float, or MongoDB Extended
+/// JSON's `{"$numberDecimal": "..."}`, since real payloads mix all of them.
+/// Integers convert exactly, and floats with `from_f64_shortest`, so the
+/// JSON number `0.1` becomes `0.1`.
+/// ```
+/// use decimal128::*;
+///
+/// let values: Vec<Decimal128> =
+///     serde_json::from_str(r#"["12.50", 42, 0.1, {"$numberDecimal": "1E+400"}]"#).unwrap();
+/// let strings: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+/// assert_eq!(vec!["12.50", "42", "0.1", "1E+400"], strings);
+/// assert_eq!(r#""12.50""#, serde_json::to_string(&values[0]).unwrap());
+/// ```
+impl<'de> Deserialize<'de> for Decimal128 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(LenientVisitor)
+    }
+}
+
+struct LenientVisitor;
+
+impl<'de> Visitor<'de> for LenientVisitor {
+    type Value = Decimal128;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a decimal string, a number or a $numberDecimal object")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Decimal128, E> {
+        StrictVisitor.visit_str(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Decimal128, E> {
+        self.visit_i128(v as i128)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Decimal128, E> {
+        self.visit_i128(v as i128)
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Decimal128, E> {
+        Decimal128::from_minor_units(v, 0).map_err(E::custom)
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Decimal128, E> {
+        // anything past i128 has more than 34 digits anyway
+        let v = i128::try_from(v).map_err(|_| E::custom(ConversionError::Overflow))?;
+        self.visit_i128(v)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Decimal128, E> {
+        Ok(Decimal128::from_f64_shortest(v))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Decimal128, A::Error> {
+        let value = match map.next_key::<String>()? {
+            Some(key) if key == NUMBER_DECIMAL => map.next_value_seed(Strict)?,
+            Some(key) => return Err(de::Error::unknown_field(&key, &[NUMBER_DECIMAL])),
+            None => return Err(de::Error::missing_field(NUMBER_DECIMAL)),
+        };
+        if let Some(key) = map.next_key::<String>()? {
+            return Err(de::Error::unknown_field(&key, &[]));
+        }
+        Ok(value)
+    }
+}
+
+struct StrictVisitor;
+
+impl<'de> Visitor<'de> for StrictVisitor {
+    type Value = Decimal128;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a decimal string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Decimal128, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+// Deserializes a string, for the value of `$numberDecimal`.
+struct Strict;
+
+impl<'de> de::DeserializeSeed<'de> for Strict {
+    type Value = Decimal128;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Decimal128, D::Error> {
+        strict::deserialize(deserializer)
+    }
+}
+
+/// Serializes and deserializes Decimal128 as a string only, for fields that
+/// should reject numbers and `$numberDecimal` objects:
+/// `#[serde(with = "decimal128::serde_strict")]`.
+/// ```
+/// let mut de = serde_json::Deserializer::from_str("0.1");
+/// assert!(decimal128::serde_strict::deserialize(&mut de).is_err());
+/// let mut de = serde_json::Deserializer::from_str(r#""0.1""#);
+/// assert_eq!("0.1", decimal128::serde_strict::deserialize(&mut de).unwrap().to_string());
+/// ```
+pub mod strict {
+    use super::StrictVisitor;
+    use crate::Decimal128;
+    use ::serde::{Deserializer, Serializer};
+
+    /// Serializes the value as its string, like `Serialize` does.
+    pub fn serialize<S: Serializer>(value: &Decimal128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    /// Deserializes a decimal string, failing on anything else.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal128, D::Error> {
+        deserializer.deserialize_str(StrictVisitor)
+    }
+}
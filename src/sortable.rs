@@ -0,0 +1,152 @@
+//! An encoding of Decimal128 whose byte order is the value order, for using
+//! values as keys in stores that sort keys bytewise, like RocksDB or
+//! FoundationDB.
+//!
+//! The 18 bytes start with a tag for the kind of value and its sign. A
+//! finite non-zero value follows it with its adjusted exponent (the exponent
+//! in scientific notation), its coefficient with zeros appended to make 34
+//! digits, and finally how many of those zeros there are, fewest first so
+//! that `1.00` comes before `1.0`. Zeros only need their exponent, and NaNs
+//! their payload. Negative values have everything after the tag inverted,
+//! which reverses their order.
+use crate::{
+    pow10, special, ConversionError, Decimal128, INFINITY, MAX_DIGITS, MAX_EXPONENT, MIN_EXPONENT,
+    NAN,
+};
+
+// Tags for the kinds of positive value, in order; negative values use
+// `NEGATIVE - tag`, which puts them below every positive value in reverse.
+const ZERO: u8 = 5;
+const FINITE: u8 = 6;
+const INFINITE: u8 = 7;
+const SIGNALING_NAN: u8 = 8;
+const QUIET_NAN: u8 = 9;
+const NEGATIVE: u8 = 9;
+
+// The bit that makes a NaN signaling, and the bits its payload is in.
+const SIGNALING: u128 = 1 << 121;
+const PAYLOAD: u128 = (1 << 110) - 1;
+
+// Largest value of the field following the tag, an exponent shifted to be
+// non-negative: zeros have exponents up to 6111, and adjusted exponents go
+// up to 6144.
+const MAX_ZERO_FIELD: u16 = (MAX_EXPONENT - MIN_EXPONENT) as u16;
+const MAX_FINITE_FIELD: u16 = MAX_ZERO_FIELD + MAX_DIGITS as u16 - 1;
+
+impl Decimal128 {
+    /// Encodes the value into 18 bytes that compare, byte by byte, in the
+    /// same order as `compare_total` compares values. That order puts every
+    /// value somewhere, from -NaN up to NaN, and numerically equal values
+    /// with different exponents next to each other, so sorted keys are in
+    /// numeric order and a range of keys is a range of values.
+    ///
+    /// `from_sortable_bytes` decodes the bytes again.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// let mut keys = vec![
+    ///     d("10").to_sortable_bytes(),
+    ///     d("-2.5").to_sortable_bytes(),
+    ///     d("9.99").to_sortable_bytes(),
+    /// ];
+    /// keys.sort();
+    /// let values: Vec<String> = keys
+    ///     .iter()
+    ///     .map(|key| Decimal128::from_sortable_bytes(*key).unwrap().to_string())
+    ///     .collect();
+    /// assert_eq!(vec!["-2.5", "9.99", "10"], values);
+    /// ```
+    pub fn to_sortable_bytes(&self) -> [u8; 18] {
+        let (tag, field, rest) = if self.nan {
+            let signaling = self.bytes[0] & 0x02 != 0;
+            let tag = if signaling { SIGNALING_NAN } else { QUIET_NAN };
+            (tag, 0, self.significand.to_num())
+        } else if self.inf {
+            (INFINITE, 0, 0)
+        } else if self.significand.is_zero() {
+            let exponent = self.exponent.to_adjusted() - MIN_EXPONENT;
+            (ZERO, exponent as u16, 0)
+        } else {
+            let digits = self.digit_count();
+            let zeros = MAX_DIGITS - digits;
+            let adjusted = self.exponent.to_adjusted() + digits as i16 - 1 - MIN_EXPONENT;
+            let coefficient = self.significand.to_num() * pow10(zeros);
+            (FINITE, adjusted as u16, coefficient << 6 | zeros as u128)
+        };
+
+        // the field takes up the top 14 of the 136 bits after the tag, above
+        // the 119 bits of the rest
+        let high = (field >> 9) as u8;
+        let low = (field as u128 & 0x1ff) << 119 | rest;
+        let mut bytes = [0u8; 18];
+        bytes[0] = if self.sign { NEGATIVE - tag } else { tag };
+        bytes[1] = high;
+        bytes[2..].copy_from_slice(&low.to_be_bytes());
+        if self.sign {
+            for b in &mut bytes[1..] {
+                *b = !*b;
+            }
+        }
+        bytes
+    }
+
+    /// Decodes bytes from `to_sortable_bytes`. Values come back with the
+    /// same sign, exponent and coefficient, or the same NaN payload, in
+    /// their canonical encoding.
+    ///
+    /// Fails with `ConversionError::Invalid` if the bytes aren't ones
+    /// `to_sortable_bytes` could have written.
+    pub fn from_sortable_bytes(mut bytes: [u8; 18]) -> Result<Decimal128, ConversionError> {
+        let (sign, tag) = match bytes[0] {
+            tag if tag < ZERO => (true, NEGATIVE - tag),
+            tag if tag <= QUIET_NAN => (false, tag),
+            _ => return Err(ConversionError::Invalid),
+        };
+        if sign {
+            for b in &mut bytes[1..] {
+                *b = !*b;
+            }
+        }
+        let mut low = [0u8; 16];
+        low.copy_from_slice(&bytes[2..]);
+        let low = u128::from_be_bytes(low);
+        let field = (bytes[1] as u16) << 9 | (low >> 119) as u16;
+        let rest = low & ((1 << 119) - 1);
+
+        match tag {
+            QUIET_NAN | SIGNALING_NAN if field == 0 && rest <= PAYLOAD => {
+                let bits = if tag == SIGNALING_NAN {
+                    NAN | SIGNALING
+                } else {
+                    NAN
+                };
+                Ok(special(sign, bits, rest))
+            }
+            INFINITE if field == 0 && rest == 0 => Ok(special(sign, INFINITY, 0)),
+            ZERO if field <= MAX_ZERO_FIELD && rest == 0 => {
+                Ok(Decimal128::from_parts(sign, field as i16 + MIN_EXPONENT, 0))
+            }
+            FINITE if field <= MAX_FINITE_FIELD => {
+                let (coefficient, zeros) = (rest >> 6, (rest & 0x3f) as u32);
+                if zeros >= MAX_DIGITS
+                    || coefficient < pow10(MAX_DIGITS - 1)
+                    || coefficient >= pow10(MAX_DIGITS)
+                    || coefficient % pow10(zeros) != 0
+                {
+                    return Err(ConversionError::Invalid);
+                }
+                let exponent = field as i16 + MIN_EXPONENT - (MAX_DIGITS - zeros) as i16 + 1;
+                if !(MIN_EXPONENT..=MAX_EXPONENT).contains(&exponent) {
+                    return Err(ConversionError::Invalid);
+                }
+                Ok(Decimal128::from_parts(
+                    sign,
+                    exponent,
+                    coefficient / pow10(zeros),
+                ))
+            }
+            _ => Err(ConversionError::Invalid),
+        }
+    }
+}
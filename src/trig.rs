@@ -0,0 +1,354 @@
+//! Trigonometric functions, with the `math-extra` feature.
+//!
+//! Like the functions in the math module, results are worked out to 60
+//! significant digits and rounded half-even to 34 once, at the end.
+//!
+//! Arguments of any size are reduced exactly: `x` is multiplied by the
+//! digits of 2/π that can reach the fractional part of the product, which
+//! tells which quarter turn `x` is in and how far into it. This is how libm
+//! reduces large doubles, and is why this module carries 2/π to enough
+//! digits for the largest Decimal128.
+use crate::iter::{multiply_digits, subtract_digits, to_digits};
+use crate::math::{Working, PRECISION};
+use crate::{quiet_nan, special, Decimal128, NAN};
+use alloc::vec;
+use alloc::vec::Vec;
+
+impl Decimal128 {
+    /// Returns the sine of the value, in radians, rounded half-even to 34
+    /// digits. Zero keeps its sign and exponent; NaN and the infinities give
+    /// NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("0.8414709848078965066525023216302990", d("1").sin().to_string());
+    /// assert_eq!("-0.8522008497671888017727058937530294", d("1E+22").sin().to_string());
+    /// ```
+    pub fn sin(&self) -> Decimal128 {
+        if let Some(result) = trig_special(self) {
+            return result;
+        }
+        let (quadrant, r) = reduce(self);
+        let result = match quadrant {
+            0 => sin(&r),
+            1 => cos(&r),
+            2 => negate(sin(&r)),
+            _ => negate(cos(&r)),
+        };
+        sign(result, self.sign).to_decimal()
+    }
+
+    /// Returns the cosine of the value, in radians, rounded half-even to 34
+    /// digits. The cosine of zero is exactly 1; NaN and the infinities give
+    /// NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("0.5403023058681397174009366074429766", d("1").cos().to_string());
+    /// assert_eq!("1", d("0").cos().to_string());
+    /// ```
+    pub fn cos(&self) -> Decimal128 {
+        if self.nan || self.inf {
+            return trig_special(self).expect("NaN and Infinity are special");
+        }
+        if self.is_zero() {
+            return Decimal128::from(1);
+        }
+        let (quadrant, r) = reduce(self);
+        let result = match quadrant {
+            0 => cos(&r),
+            1 => negate(sin(&r)),
+            2 => negate(cos(&r)),
+            _ => sin(&r),
+        };
+        result.to_decimal()
+    }
+
+    /// Returns the tangent of the value, in radians, rounded half-even to
+    /// 34 digits. Zero keeps its sign and exponent; NaN and the infinities
+    /// give NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("1.557407724654902230506974807458360", d("1").tan().to_string());
+    /// ```
+    pub fn tan(&self) -> Decimal128 {
+        if let Some(result) = trig_special(self) {
+            return result;
+        }
+        let (quadrant, r) = reduce(self);
+        let result = if quadrant % 2 == 0 {
+            sin(&r).div(&cos(&r))
+        } else {
+            negate(cos(&r).div(&sin(&r)))
+        };
+        sign(result, self.sign).to_decimal()
+    }
+
+    /// Returns the arctangent of the value, in radians between -π/2 and
+    /// π/2, rounded half-even to 34 digits. Zero keeps its sign and
+    /// exponent, Infinity gives π/2 and -Infinity -π/2, and NaN gives NaN.
+    /// ```
+    /// use decimal128::*;
+    ///
+    /// let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    /// assert_eq!("0.7853981633974483096156608458198757", d("1").atan().to_string());
+    /// assert_eq!("-1.570796326794896619231321691639751", d("-Infinity").atan().to_string());
+    /// ```
+    pub fn atan(&self) -> Decimal128 {
+        if self.nan {
+            return quiet_nan(self);
+        }
+        if self.inf {
+            return sign(half_pi(), self.sign).to_decimal();
+        }
+        if self.is_zero() {
+            return self.clone();
+        }
+
+        let one = Working::from_int(1);
+        let mut a = Working {
+            negative: false,
+            ..Working::from_decimal(self)
+        };
+        // atan a = π/2 - atan(1/a)
+        let invert = !a.sub(&one).negative;
+        if invert {
+            a = one.div(&a);
+        }
+        // and atan a = 2 atan(a / (1 + sqrt(1 + a^2))), twice, which leaves
+        // a below tan(π/16), about 0.2
+        for _ in 0..2 {
+            a = a.div(&one.add(&sqrt(&one.add(&a.mul(&a)))));
+        }
+        let mut result = atan(&a).mul(&Working::from_int(4));
+        if invert {
+            result = half_pi().sub(&result);
+        }
+        sign(result, self.sign).to_decimal()
+    }
+}
+
+// The result of sin, cos or tan for NaN, the infinities and zero, which is
+// the same for sin and tan.
+fn trig_special(v: &Decimal128) -> Option<Decimal128> {
+    if v.nan {
+        Some(quiet_nan(v))
+    } else if v.inf {
+        Some(special(false, NAN, 0))
+    } else if v.is_zero() {
+        Some(v.clone())
+    } else {
+        None
+    }
+}
+
+// |v| = (4n + quadrant) π/2 + r, with r within [-π/4, π/4].
+fn reduce(v: &Decimal128) -> (u8, Working) {
+    let x = Working {
+        negative: false,
+        ..Working::from_decimal(v)
+    };
+    if x.sub(&half_pi().mul(&Working::from_parts(5, -1))).negative {
+        return (0, x);
+    }
+
+    // |v| * 2/π, with |v| = c * 10^e, only needs the digits of 2/π from the
+    // one worth 10^(1 - e), since those before it add multiples of 100 to
+    // the product and so don't change the quarter turn, up to the ones
+    // REDUCTION_DIGITS past the decimal point of the product
+    let e = v.exponent.to_adjusted() as i64;
+    let first = (e - 1).max(1) as usize;
+    let last = (e + REDUCTION_DIGITS as i64) as usize;
+    let window: Vec<u8> = TWO_OVER_PI[first - 1..last]
+        .iter()
+        .map(|d| d - b'0')
+        .collect();
+    let product = multiply_digits(&to_digits(v.significand.to_num(), 0), &window);
+    let (integer, fraction) = product.split_at(product.len() - REDUCTION_DIGITS);
+
+    let last_two = integer.iter().rev().take(2).rev();
+    let quadrant = (last_two.fold(0, |n, d| n * 10 + *d as u32) % 4) as u8;
+    // a fraction past a half is nearer to the next quarter turn. It's taken
+    // from 1 before it's cut down to PRECISION digits, so that an argument
+    // just below a multiple of π/2 keeps all of its digits.
+    let (quadrant, negative, fraction) = if fraction[0] >= 5 {
+        let mut one = vec![0; REDUCTION_DIGITS + 1];
+        one[0] = 1;
+        ((quadrant + 1) % 4, true, subtract_digits(&one, fraction))
+    } else {
+        (quadrant, false, fraction.to_vec())
+    };
+    let fraction = Working::new(negative, fraction, -(REDUCTION_DIGITS as i64), true);
+    (quadrant, fraction.mul(&half_pi()))
+}
+
+// sin r = r - r^3/3! + r^5/5! - ..., for |r| up to π/4.
+fn sin(r: &Working) -> Working {
+    series(r.clone(), &r.mul(r), 2)
+}
+
+// cos r = 1 - r^2/2! + r^4/4! - ..., for |r| up to π/4.
+fn cos(r: &Working) -> Working {
+    series(Working::from_int(1), &r.mul(r), 1)
+}
+
+// first - first * square / (k (k + 1)) + ..., the terms of the sine and
+// cosine series, with `k` the number of the first term's factorial plus 1.
+fn series(first: Working, square: &Working, mut k: i64) -> Working {
+    let mut term = first.clone();
+    let mut sum = first;
+    loop {
+        term = negate(term.mul(square).div(&Working::from_int(k * (k + 1))));
+        if term.is_zero() || term.top() < sum.top() - PRECISION as i64 {
+            break;
+        }
+        sum = sum.add(&term);
+        k += 2;
+    }
+    sum.exact = false;
+    sum
+}
+
+// atan a = a - a^3/3 + a^5/5 - ..., for |a| well below 1.
+fn atan(a: &Working) -> Working {
+    let square = a.mul(a);
+    let mut power = a.clone();
+    let mut sum = a.clone();
+    for k in 1.. {
+        power = negate(power.mul(&square));
+        let term = power.div(&Working::from_int(2 * k + 1));
+        if term.is_zero() || term.top() < sum.top() - PRECISION as i64 {
+            break;
+        }
+        sum = sum.add(&term);
+    }
+    sum.exact = false;
+    sum
+}
+
+// The square root of v, which is within [1, 2], by Newton's method from the
+// integer square root of its first 37 digits, which is good to 18.
+fn sqrt(v: &Working) -> Working {
+    let mut y = Working::from_parts(v.to_u128(36).isqrt(), -18);
+    let half = Working::from_parts(5, -1);
+    for _ in 0..4 {
+        y = y.add(&v.div(&y)).mul(&half);
+    }
+    y
+}
+
+fn negate(v: Working) -> Working {
+    Working {
+        negative: !v.negative,
+        ..v
+    }
+}
+
+// `v`, negated if `negative`.
+fn sign(v: Working, negative: bool) -> Working {
+    if negative {
+        negate(v)
+    } else {
+        v
+    }
+}
+
+// π/2, rounded to 64 digits.
+const HALF_PI: &[u8] = b"1570796326794896619231321691639751442098584699687552910487472296";
+
+fn half_pi() -> Working {
+    Working::from_constant(HALF_PI, -63)
+}
+
+// Digits of the product |v| * 2/π past its decimal point that reduction
+// keeps: 60 for the result, and enough more that arguments that are very
+// nearly a multiple of π/2 still have 60 left once the fraction's leading
+// zeros are gone.
+const REDUCTION_DIGITS: usize = 180;
+
+// The first 6300 digits of 2/π after the decimal point, enough for
+// reducing arguments with exponents up to 6111.
+const TWO_OVER_PI: &[u8] = b"\
+    63661977236758134307553505349005744813783858296182579499066937623558719053690614\
+    03604552110650123438242913709070318321475716473844583146115118696429267993569169\
+    59867749636310292310985587701230754869571584869590646773449560966894516047329520\
+    45689079902286376184756034761069582448195764374775137634211489239978577360099468\
+    93909578384435932923871322996246679458512187977946087515262991462678569641559834\
+    96557394439935472396799849771502340684715433724470075068642186190147952038957841\
+    45903733507223720997798654122130862710201288129911126558866409178699247839266336\
+    24240672121439925356479499953311466177411190202800649627102575553982852435204887\
+    97504590725511058951562532272185831913927045249709256279843100098001191039428356\
+    22761118714052610084006527098408369924642496224582481258593635699383676574084630\
+    16302248034861064272088686365630298983308903909851415995006213175632559270896374\
+    33019188293314876162799903630630831397388157435931234869370256146758046650182823\
+    77331052507460010449087188461284503980175467178015050224334526846781039032512899\
+    76649333725804244941475142524545467686685682789878405170023133442124784343780393\
+    58226874839818986041726495262070323357771919883998021017550264517783533227384203\
+    14116606056416195719540255526431047879722936415599831476756239237495108824750172\
+    89087572054650210449551215501555244272562706173633131141077337071982242831615442\
+    41410955984980503982997105188094376382337204659318564742310849623017797828087159\
+    07916963796130917908086659841426127261417601536275949887076635505276386602785761\
+    91078827507346271124191191818014135830332075273547517510644992598122398623208763\
+    34395004140508516172926321994878747511037862653848841368177634219914015170954777\
+    17414647751131714943751373881292094858335169422847454536771784073272916785666003\
+    51323173254139911639898345971610698024395747563783532201348122152218924928632377\
+    27907041291325256759238999289753340697427959390004158002735520159146894398432096\
+    01095604349981941915169427304455979561307598970833398445968331561510713897214201\
+    82738243346859172338268933081419415702248083473572963982488470132735760838831742\
+    83099861995234744265443874647868149898168411324877007384899339964644598266224151\
+    87870455972513198431043311196040313214400935309195163416095504622978172370404764\
+    02173519935561861968499318064282914120209088409440700932526927190372442013126204\
+    37495654558581223170428720334471819506898583921895909169792436803748503147673331\
+    58354513596174347466655902693780563801454930876697245552265532290369211038938024\
+    21928511121482613511321286839509398662739632013079540269671658587340331264674132\
+    57344642923980599412479278935033776839366623816609002573577251457761535534246035\
+    19086580068258827007509824236643486743143175690493902532684453199462376638756287\
+    94027549769202300767908227601528735702488135496941450272334166260691884352468871\
+    83747330259540749998994834212466393224405568578178406459538110810045644280994086\
+    95898041546694561549144039869957269424724828469619155974755462276923139400922282\
+    28576254554528094740804296402299436912446288787201591299038120066783408849213856\
+    75094601741870585826263887604492339068397238834365134586676767107755165733262266\
+    02679252865660840358284691449537042827138070404453803202797907368942795849952206\
+    31039238135883234190023901450625961375778168232715457427321680012603823789737570\
+    10179402699657163459005769213285329827804653978271015757696144362175334211316973\
+    68813979374646058652914409910666641981256262937430212056363311952365914677373969\
+    09504105399913198280726478572849325619030515899363315646963899130551596726799757\
+    94999086079592749066517840732215833310083694540274155569138729890398901132030674\
+    27750334638891679297718989624655273245583322697739406771438953294957064960973800\
+    79912397616087584539337094454705799655308616664253699317454967402449044344528479\
+    94533851388397673597709718236625133359619215284700046448466688207650317214211716\
+    96453761246453644998127354370783396177538723139638959312354211881806122159656039\
+    54795363534619346608898674496349016056160364714968488180923013389589015259761553\
+    67623473692463785290977356264500649572425132781295533568526138225526047008140434\
+    98382328044950174390726213607496295773614535912155268840181267673180779518367069\
+    58167115169741104696289842375664109291315178727745965157988598137302108943666371\
+    92289919943224507602932875378107177340182320780997026522481950646453746135968115\
+    01808342213765763962051930909818636472528893136204666462602839350229734918194524\
+    81644868655236624246446629280003332244584247251213050347838064098528664554306459\
+    21887973083108526576480637984044253132208303833394012203163823399319287469611593\
+    54205532958280832305590201716903939058828406570789753801723666345811344129973441\
+    74186289502316645465296481831239878862653608863522183177253131120220984528355607\
+    49684843697956416402086198723884548830160228438536265725429817596639077743155683\
+    17370247113208894804594569970095699491485252808706694430265823930904382966264093\
+    75149745165284389943588602852295641629057416567188228890619192152605103831649601\
+    01378721928810469369196004081932249852135185898712762007247321500615211518093733\
+    67820085427590836516224572715151683448229799970315902760739684129682588554076455\
+    52590256083904221958317514056561658122060633585712930616240824132475663462810883\
+    45001079665575006111549442432458227793684128963109090968660545693746797086536123\
+    76212299226107403720663568547685657251748536424628614856248159139047370601191231\
+    44250678798432367368939053401909868760698018057846655313848329634694380409485211\
+    61777511763414013781770533652250522983805532124091725877378673314070653129660608\
+    40717690577582872486868087025968785779758612888875063395297804763760536201772855\
+    94345144843327175758433775592076591495590893241145240525947820850482073112253978\
+    28474651113026395324021406209266639375763608872252578180848519158937885954965033\
+    07289544094410843992476608227529388959343205346427351453154717144789294690144267\
+    40867425280477959122935833676762663833547141176496748728691195002441578425927834\
+    29824802435684913665577495386198359728113924945733864478829297238183436293447514\
+    51625274006604250703074048654303547852298079968800431067073237879259902490729739\
+    17468524336484087808359792764977619500468423673765596315578231007384864761661237\
+    38175211235754512292950314461071188457329296787943122255052072353754656242870147\
+    328545051868489704377141604438528730510604804680902117171586";
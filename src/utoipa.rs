@@ -0,0 +1,25 @@
+//! OpenAPI schemas for utoipa.
+//!
+//! Decimal128 is described as a string with the custom format `decimal128`,
+//! the way the GraphQL scalars write it, since a JSON number would be read
+//! as a double by most clients.
+use crate::Decimal128;
+use ::utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, SchemaType, Type};
+use ::utoipa::openapi::RefOr;
+use ::utoipa::{PartialSchema, ToSchema};
+
+impl PartialSchema for Decimal128 {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(SchemaType::Type(Type::String))
+            .format(Some(SchemaFormat::Custom("decimal128".to_string())))
+            .description(Some(
+                "An IEEE 754 decimal128 number, as a decimal string like \"12.50\" or \"1E+400\"",
+            ))
+            .examples(["12.50"])
+            .into()
+    }
+}
+
+/// Named `Decimal128` in the components of an OpenAPI document.
+impl ToSchema for Decimal128 {}
@@ -0,0 +1,77 @@
+//! JavaScript bindings for Decimal128, built with wasm-bindgen.
+//!
+//! The exported class is called `Decimal128` on the JavaScript side, so a
+//! BSON inspector running in the browser can decode values the same way the
+//! Rust side does:
+//!
+//! ```js
+//! const value = Decimal128.fromBytes(bytes);
+//! console.log(value.toString());
+//! ```
+use crate::Decimal128;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use core::convert::TryInto;
+use wasm_bindgen::prelude::*;
+
+/// A Decimal128 exported to JavaScript.
+#[wasm_bindgen(js_name = Decimal128)]
+#[derive(Clone)]
+pub struct WasmDecimal128 {
+    inner: Decimal128,
+}
+
+#[wasm_bindgen(js_class = Decimal128)]
+impl WasmDecimal128 {
+    /// Decodes 16 bytes, most significant first. Throws if there aren't
+    /// exactly 16 of them.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmDecimal128, JsError> {
+        let buffer: [u8; 16] = bytes
+            .try_into()
+            .map_err(|_| JsError::new("Decimal128 needs exactly 16 bytes"))?;
+        Ok(Decimal128::from_raw_bytes(buffer).into())
+    }
+
+    /// Parses a numeric string, exactly like `FromStr`. Throws if the string
+    /// isn't a valid number or doesn't fit without rounding.
+    #[wasm_bindgen(js_name = fromString)]
+    pub fn from_string(s: &str) -> Result<WasmDecimal128, JsError> {
+        s.parse::<Decimal128>()
+            .map(WasmDecimal128::from)
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// The 16 bytes of the value, most significant first.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Box<[u8]> {
+        Box::new(self.inner.to_raw_bytes())
+    }
+
+    /// Formats the value the way `to_string` does in Rust.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.inner.to_string()
+    }
+
+    /// Compares two values numerically like `Decimal128::compare_decimal`:
+    /// returns -1, 0 or 1, or NaN if either value is NaN.
+    pub fn compare(&self, other: &WasmDecimal128) -> f64 {
+        match self.inner.partial_cmp(&other.inner) {
+            Some(ordering) => ordering as i32 as f64,
+            None => f64::NAN,
+        }
+    }
+}
+
+impl From<Decimal128> for WasmDecimal128 {
+    fn from(inner: Decimal128) -> Self {
+        WasmDecimal128 { inner }
+    }
+}
+
+impl From<WasmDecimal128> for Decimal128 {
+    fn from(value: WasmDecimal128) -> Self {
+        value.inner
+    }
+}
@@ -0,0 +1,179 @@
+//! Runs the BSON corpus decimal128 tests against Decimal128.
+//!
+//! Test files are read from `tests/bson_corpus/data`, or from the directory
+//! named by the `BSON_CORPUS_DIR` environment variable, so the official
+//! `decimal128-*.json` files from the MongoDB specifications repository
+//! (`source/bson-corpus/tests`) can be run without checking them in. Files for
+//! other BSON types are skipped.
+//!
+//! For every valid case, `canonical_bson` has to decode to the string in
+//! `canonical_extjson` and encode back to the same bytes, and `degenerate_bson`
+//! has to decode to that string too. The `canonical_extjson` string, and the
+//! `degenerate_extjson` string unless the case is lossy, have to parse to the
+//! `canonical_bson` bytes. Every `parseErrors` string has to fail to parse.
+use decimal128::*;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn it_passes_bson_corpus_files() {
+    let dir = env::var_os("BSON_CORPUS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/bson_corpus/data")
+        });
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    files.sort();
+
+    let mut passed = 0;
+    let mut failures = Vec::new();
+    for file in files {
+        let contents = fs::read_to_string(&file).unwrap();
+        let suite: Value =
+            serde_json::from_str(&contents).unwrap_or_else(|e| panic!("{}: {}", file.display(), e));
+        if suite["bson_type"] != "0x13" {
+            continue;
+        }
+        let test_key = suite["test_key"].as_str().unwrap();
+
+        for case in cases(&suite, "valid") {
+            let description = case["description"].as_str().unwrap();
+            match check_valid(case, test_key) {
+                Ok(()) => passed += 1,
+                Err(e) => failures.push(format!("{}: {}", description, e)),
+            }
+        }
+        for case in cases(&suite, "parseErrors") {
+            let description = case["description"].as_str().unwrap();
+            let string = case["string"].as_str().unwrap();
+            match string.parse::<Decimal128>() {
+                Err(_) => passed += 1,
+                Ok(dec128) => failures.push(format!(
+                    "{}: {:?} parsed to {}",
+                    description, string, dec128
+                )),
+            }
+        }
+    }
+
+    println!("bson corpus: {} passed, {} failed", passed, failures.len());
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    assert!(
+        passed > 0,
+        "no bson corpus cases were run from {}",
+        dir.display()
+    );
+}
+
+fn cases<'a>(suite: &'a Value, key: &str) -> &'a [Value] {
+    suite[key].as_array().map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn check_valid(case: &Value, test_key: &str) -> Result<(), String> {
+    let canonical_bson = case["canonical_bson"].as_str().unwrap();
+    let expected = number_decimal(case["canonical_extjson"].as_str().unwrap(), test_key)?;
+
+    let bytes = decode_document(canonical_bson, test_key)?;
+    let dec128 = Decimal128::from_raw_bytes(bytes);
+    let string = dec128.to_string();
+    if string != expected {
+        return Err(format!(
+            "canonical_bson decoded to {}, not {}",
+            string, expected
+        ));
+    }
+    if dec128.to_raw_bytes() != bytes {
+        return Err(format!(
+            "canonical_bson encoded back to {:02x?}, not {:02x?}",
+            dec128.to_raw_bytes(),
+            bytes
+        ));
+    }
+
+    if let Some(degenerate_bson) = case["degenerate_bson"].as_str() {
+        let bytes = decode_document(degenerate_bson, test_key)?;
+        let string = Decimal128::from_raw_bytes(bytes).to_string();
+        if string != expected {
+            return Err(format!(
+                "degenerate_bson decoded to {}, not {}",
+                string, expected
+            ));
+        }
+    }
+
+    check_parse(&expected, bytes)?;
+    if let Some(degenerate_extjson) = case["degenerate_extjson"].as_str() {
+        if case["lossy"] != true {
+            check_parse(&number_decimal(degenerate_extjson, test_key)?, bytes)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_parse(string: &str, bytes: [u8; 16]) -> Result<(), String> {
+    let dec128: Decimal128 = string
+        .parse()
+        .map_err(|e| format!("{:?} failed to parse: {}", string, e))?;
+    if dec128.to_raw_bytes() != bytes {
+        return Err(format!(
+            "{:?} parsed to {:02x?}, not {:02x?}",
+            string,
+            dec128.to_raw_bytes(),
+            bytes
+        ));
+    }
+    Ok(())
+}
+
+// The `$numberDecimal` string from an Extended JSON document like
+// `{"d" : {"$numberDecimal" : "1.0"}}`.
+fn number_decimal(extjson: &str, test_key: &str) -> Result<String, String> {
+    let document: Value = serde_json::from_str(extjson).map_err(|e| e.to_string())?;
+    document[test_key]["$numberDecimal"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("no $numberDecimal in {}", extjson))
+}
+
+// The decimal128 bytes, most significant first, from a hex encoded BSON
+// document holding a single decimal128 element named `test_key`. BSON stores
+// the value little endian.
+fn decode_document(hex: &str, test_key: &str) -> Result<[u8; 16], String> {
+    let document = (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| format!("{} is not valid hex", hex))?;
+
+    let key_end = 5 + test_key.len();
+    let expected_len = key_end + 1 + 16 + 1;
+    let declared_len = document
+        .get(..4)
+        .map(|len| u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize);
+    if document.len() != expected_len
+        || declared_len != Some(expected_len)
+        || document[4] != 0x13
+        || &document[5..key_end] != test_key.as_bytes()
+        || document[key_end] != 0
+        || document[expected_len - 1] != 0
+    {
+        return Err(format!(
+            "{} is not a document with a single decimal128 {:?}",
+            hex, test_key
+        ));
+    }
+
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&document[key_end + 1..key_end + 17]);
+    bytes.reverse();
+    Ok(bytes)
+}
@@ -0,0 +1,180 @@
+//! Runs decNumber `.decTest` files against Decimal128.
+//!
+//! Test files are read from `tests/dectest/data`, or from the directory named
+//! by the `DECTEST_DIR` environment variable, so the official suite from
+//! http://speleotrove.com/decimal/dectest.html can be run without checking it
+//! in. Only tests written for decimal128 (the `dq*` files: precision 34,
+//! exponents -6143 to 6144, clamping on) are run; decDouble (`dd*`) and
+//! arbitrary-precision files are skipped.
+//!
+//! Supported operations are tosci, toeng, apply, compare, comparetotal,
+//! comparetotmag, add, subtract, multiply, divide, divideint, remainder, and, or, xor and invert. The
+//! arithmetic ones only run under half_even rounding. Operations that
+//! Decimal128 doesn't implement yet are counted as skipped. Conditions
+//! (Inexact, Rounded, ...) are not checked.
+mod parser;
+
+use decimal128::*;
+use parser::{parse_file, TestCase};
+use std::cmp::Ordering;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+enum Outcome {
+    Pass,
+    Fail(String),
+    Skip,
+}
+
+#[test]
+fn it_passes_dectest_files() {
+    let dir = env::var_os("DECTEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/dectest/data"));
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "decTest"))
+        .collect();
+    files.sort();
+
+    let (mut passed, mut skipped) = (0, 0);
+    let mut failures = Vec::new();
+    for file in files {
+        for case in parse_file(&file).unwrap() {
+            match run(&case) {
+                Outcome::Pass => passed += 1,
+                Outcome::Skip => skipped += 1,
+                Outcome::Fail(actual) => failures.push(format!(
+                    "{} {} {} -> {} {}, got {}",
+                    case.id,
+                    case.operation,
+                    case.operands.join(" "),
+                    case.result,
+                    case.conditions.join(" "),
+                    actual
+                )),
+            }
+        }
+    }
+
+    println!(
+        "dectest: {} passed, {} failed, {} skipped",
+        passed,
+        failures.len(),
+        skipped
+    );
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    assert!(
+        passed > 0,
+        "no dectest cases were run from {}",
+        dir.display()
+    );
+}
+
+fn run(case: &TestCase) -> Outcome {
+    let directive = |key: &str| case.directives.get(key).map(String::as_str);
+    if directive("precision") != Some("34")
+        || directive("maxexponent") != Some("6144")
+        || directive("minexponent") != Some("-6143")
+        || directive("clamp") != Some("1")
+    {
+        return Outcome::Skip;
+    }
+
+    let mode = match directive("rounding").and_then(rounding_mode) {
+        Some(mode) => mode,
+        None => return Outcome::Skip,
+    };
+    let operands: Option<Vec<Decimal128>> =
+        case.operands.iter().map(|o| operand(o, mode)).collect();
+    let operands = match operands {
+        Some(operands) => operands,
+        None => return Outcome::Skip,
+    };
+
+    let actual = match (case.operation.as_str(), operands.as_slice()) {
+        ("tosci", [a]) | ("apply", [a]) if case.result.starts_with('#') => format!("#{:x}", a),
+        ("tosci", [a]) | ("apply", [a]) => a.to_string(),
+        ("toeng", [a]) if !case.result.starts_with('#') => a.to_engineering_string(),
+        ("compare", [a, b]) => result(a.compare_decimal(b)),
+        ("comparetotal", [a, b]) => ordering(a.compare_total(b)),
+        ("comparetotmag", [a, b]) => ordering(a.compare_total_mag(b)),
+        ("add", [a, b]) if mode == RoundingMode::HalfEven => result(a.overflowing_add(b).0),
+        ("subtract", [a, b]) if mode == RoundingMode::HalfEven => result(a.overflowing_sub(b).0),
+        ("multiply", [a, b]) if mode == RoundingMode::HalfEven => result(a.overflowing_mul(b).0),
+        ("divide", [a, b]) if mode == RoundingMode::HalfEven => result(a.overflowing_div(b).0),
+        ("divideint", [a, b]) => result(a.divide_integer(b)),
+        ("remainder", [a, b]) => result(a.div_rem(b).1),
+        ("and", [a, b]) => logical(a.logical_and(b)),
+        ("or", [a, b]) => logical(a.logical_or(b)),
+        ("xor", [a, b]) => logical(a.logical_xor(b)),
+        ("invert", [a]) => logical(a.logical_invert()),
+        _ => return Outcome::Skip,
+    };
+    if actual == case.result {
+        Outcome::Pass
+    } else {
+        Outcome::Fail(actual)
+    }
+}
+
+// The result of an operation as the spec writes it, which unlike
+// `to_string` keeps the sign and payload of a NaN, as in `-NaN7`.
+fn result(value: Decimal128) -> String {
+    if value.is_nan() {
+        value.to_canonical_string()
+    } else {
+        value.to_string()
+    }
+}
+
+// The -1, 0 or 1 the total orderings are written as.
+fn ordering(ordering: Ordering) -> String {
+    (ordering as i32).to_string()
+}
+
+// Invalid logical operands give NaN, with Invalid_operation.
+fn logical(result: Result<Decimal128, ConversionError>) -> String {
+    match result {
+        Ok(value) => value.to_string(),
+        Err(_) => "NaN".to_string(),
+    }
+}
+
+// The rounding mode for a `rounding` directive. `05up` has no equivalent.
+fn rounding_mode(name: &str) -> Option<RoundingMode> {
+    match name.to_lowercase().as_str() {
+        "half_even" => Some(RoundingMode::HalfEven),
+        "half_up" => Some(RoundingMode::HalfUp),
+        "half_down" => Some(RoundingMode::HalfDown),
+        "up" => Some(RoundingMode::Up),
+        "down" => Some(RoundingMode::Down),
+        "ceiling" => Some(RoundingMode::Ceiling),
+        "floor" => Some(RoundingMode::Floor),
+        _ => None,
+    }
+}
+
+// Operands written as `#` followed by 32 hex digits are decimal128
+// encodings, most significant byte first. Anything else is a numeric string,
+// rounded to fit with `mode`.
+fn operand(token: &str, mode: RoundingMode) -> Option<Decimal128> {
+    let hex = match token.strip_prefix('#') {
+        Some(hex) => hex,
+        None => {
+            return Decimal128::from_str_rounded(token, mode)
+                .ok()
+                .map(|(d, _)| d)
+        }
+    };
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(Decimal128::from_raw_bytes(bytes))
+}
@@ -0,0 +1,120 @@
+//! Parser for the decNumber `.decTest` file format described at
+//! http://speleotrove.com/decimal/dtfile.html
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// A single test line, e.g.
+/// `dqadd001 add 1 1 -> 2 Rounded`
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub id: String,
+    pub operation: String,
+    pub operands: Vec<String>,
+    pub result: String,
+    pub conditions: Vec<String>,
+    /// The directives (precision, rounding, ...) in effect for this test,
+    /// with lowercase keys.
+    pub directives: HashMap<String, String>,
+}
+
+/// Parse a `.decTest` file, following `dectest:` directives into other files
+/// in the same directory.
+pub fn parse_file(path: &Path) -> Result<Vec<TestCase>, String> {
+    let mut cases = Vec::new();
+    let mut directives = HashMap::new();
+    parse_into(path, &mut directives, &mut cases)?;
+    Ok(cases)
+}
+
+fn parse_into(
+    path: &Path,
+    directives: &mut HashMap<String, String>,
+    cases: &mut Vec<TestCase>,
+) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    for (number, line) in contents.lines().enumerate() {
+        let tokens =
+            tokenize(line).map_err(|e| format!("{}:{}: {}", path.display(), number + 1, e))?;
+        if tokens.is_empty() {
+            continue;
+        }
+
+        if tokens[0].ends_with(':') {
+            let keyword = tokens[0].trim_end_matches(':').to_lowercase();
+            let value = tokens.get(1).cloned().unwrap_or_default();
+            if keyword == "dectest" {
+                let included = path.with_file_name(format!("{}.decTest", value));
+                parse_into(&included, directives, cases)?;
+            } else {
+                directives.insert(keyword, value);
+            }
+            continue;
+        }
+
+        let arrow = tokens
+            .iter()
+            .position(|t| t == "->")
+            .ok_or_else(|| format!("{}:{}: missing '->'", path.display(), number + 1))?;
+        if arrow < 2 || arrow + 1 >= tokens.len() {
+            return Err(format!("{}:{}: malformed test", path.display(), number + 1));
+        }
+        cases.push(TestCase {
+            id: tokens[0].clone(),
+            operation: tokens[1].to_lowercase(),
+            operands: tokens[2..arrow].to_vec(),
+            result: tokens[arrow + 1].clone(),
+            conditions: tokens[arrow + 2..].to_vec(),
+            directives: directives.clone(),
+        });
+    }
+    Ok(())
+}
+
+// Split a line into tokens, dropping `--` comments. Tokens can be quoted with
+// ' or ", and a doubled quote inside a quoted token stands for itself.
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '-' && line_continues_with_comment(&mut chars.clone()) {
+            break;
+        } else if c == '\'' || c == '"' {
+            chars.next();
+            let mut token = String::new();
+            loop {
+                match chars.next() {
+                    Some(q) if q == c => {
+                        if chars.peek() == Some(&c) {
+                            chars.next();
+                            token.push(c);
+                        } else {
+                            break;
+                        }
+                    }
+                    Some(other) => token.push(other),
+                    None => return Err("unterminated quote".to_string()),
+                }
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+    Ok(tokens)
+}
+
+fn line_continues_with_comment(chars: &mut std::iter::Peekable<std::str::Chars>) -> bool {
+    chars.next() == Some('-') && chars.next() == Some('-')
+}
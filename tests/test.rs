@@ -32,6 +32,47 @@ fn it_returns_nan() {
     assert_eq!("NaN".to_string(), string);
 }
 
+#[test]
+fn it_returns_negative_zero() {
+    let vec: [u8; 16] = [
+        0xb0, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!("-0".to_string(), dec128.to_string());
+    assert_eq!("-0", format!("{:+}", dec128));
+    assert_eq!("-0000", format!("{:05}", dec128));
+    assert_eq!("-0.00", format!("{:.2}", dec128));
+    assert_eq!("-0e0", format!("{:e}", dec128));
+    assert_eq!("-0", dec128.to_plain_string());
+    assert_eq!("-0", dec128.to_engineering_string());
+    assert_eq!("-0E+0", dec128.to_scientific_string(None));
+    assert_eq!(vec, "-0".parse::<Decimal128>().unwrap().to_raw_bytes());
+
+    // zeros with a positive exponent have no integer digits to write out
+    let dec128: Decimal128 = "-0E+3".parse().unwrap();
+    assert_eq!("-0E+3", dec128.to_string());
+    assert_eq!("-0", dec128.to_plain_string());
+    assert_eq!("-0.0", format!("{:.1}", dec128));
+    let dec128: Decimal128 = "0E+3".parse().unwrap();
+    assert_eq!("0", dec128.to_plain_string());
+    assert_eq!("+0.00", format!("{:+.2}", dec128));
+}
+
+#[test]
+fn it_signs_infinities_like_floats() {
+    let infinity: Decimal128 = "-Infinity".parse().unwrap();
+    assert_eq!("-Infinity", format!("{:+}", infinity));
+    assert_eq!("-Infinity", format!("{:.2}", infinity));
+    assert_eq!("-Infinity", format!("{:+e}", infinity));
+    assert_eq!("-000Infinity", format!("{:012}", infinity));
+    assert_eq!("   -Infinity", format!("{:>12}", infinity));
+    let infinity: Decimal128 = "Infinity".parse().unwrap();
+    assert_eq!("+Infinity", format!("{:+}", infinity));
+    assert_eq!("+000Infinity", format!("{:+012.3}", infinity));
+    assert_eq!("+Infinity", format!("{:+E}", infinity));
+}
+
 #[test]
 fn it_returns_0_001234() {
     let vec: [u8; 16] = [
@@ -100,3 +141,3885 @@ fn it_returns_1_e_6176() {
     let decimal = dec128.to_string();
     assert_eq!("1E-6176".to_string(), decimal)
 }
+
+#[test]
+fn it_returns_longest_strings() {
+    let vec: [u8; 16] = [
+        0xaf, 0xf2, 0x3c, 0xde, 0x6f, 0xff, 0x97, 0x32, 0xde, 0x82, 0x5c, 0xd0, 0x7e, 0x96, 0xaf,
+        0xf2,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!(
+        "-0.000001234567890123456789012345678901234".to_string(),
+        dec128.to_string()
+    );
+
+    let vec: [u8; 16] = [
+        0x80, 0x00, 0x3c, 0xde, 0x6f, 0xff, 0x97, 0x32, 0xde, 0x82, 0x5c, 0xd0, 0x7e, 0x96, 0xaf,
+        0xf2,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!(
+        "-1.234567890123456789012345678901234E-6143".to_string(),
+        dec128.to_string()
+    );
+}
+
+#[test]
+fn it_pads_formatted_strings() {
+    let dec128 = Decimal128::from_mysql_decimal("-123.45", RoundingMode::HalfEven).unwrap();
+    assert_eq!("     -123.45", format!("{:>12}", dec128));
+    assert_eq!("-123.45     ", format!("{:<12}", dec128));
+    assert_eq!("**-123.45***", format!("{:*^12}", dec128));
+    assert_eq!("-00000123.45", format!("{:012}", dec128));
+    assert_eq!("-123.45", format!("{:+}", dec128));
+    assert_eq!("-123.45", format!("{:3}", dec128));
+
+    let dec128 = Decimal128::from(5);
+    assert_eq!("+5", format!("{:+}", dec128));
+    assert_eq!("    5     ", format!("{:^10}", dec128));
+    assert_eq!("+0005", format!("{:+05}", dec128));
+
+    let nan = Decimal128::from_raw_bytes([
+        0x7c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ]);
+    assert_eq!("NaN", format!("{:+}", nan));
+    assert_eq!("   NaN", format!("{:>6}", nan));
+    let infinity = Decimal128::from_raw_bytes([
+        0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ]);
+    assert_eq!("-Infinity  ", format!("{:<11}", infinity));
+}
+
+#[test]
+fn it_rounds_to_formatter_precision() {
+    let parse = |s| Decimal128::from_mysql_decimal(s, RoundingMode::HalfEven).unwrap();
+    assert_eq!("2.68", format!("{:.2}", parse("2.675")));
+    assert_eq!("2.66", format!("{:.2}", parse("2.665")));
+    assert_eq!("10.00", format!("{:.2}", parse("9.995")));
+    assert_eq!("-0.00", format!("{:.2}", parse("-0.001")));
+    assert_eq!("0.00123400", format!("{:.8}", parse("0.001234")));
+    assert_eq!("-1", format!("{:.0}", parse("-0.5000001")));
+    assert_eq!("      2.68", format!("{:>10.2}", parse("2.675")));
+    assert_eq!("-000001.50", format!("{:010.2}", parse("-1.5")));
+    assert_eq!("+12.0", format!("{:+.1}", Decimal128::from(12)));
+
+    let vec: [u8; 16] = [
+        0x30, 0x46, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01,
+    ];
+    assert_eq!("1000.00", format!("{:.2}", Decimal128::from_raw_bytes(vec)));
+    let vec: [u8; 16] = [
+        0x5f, 0xfe, 0x31, 0x4d, 0xc6, 0x44, 0x8d, 0x93, 0x38, 0xc1, 0x5b, 0x0a, 0x00, 0x00, 0x00,
+        0x00,
+    ];
+    assert_eq!(
+        6145,
+        format!("{:.0}", Decimal128::from_raw_bytes(vec)).len()
+    );
+
+    let nan = Decimal128::from_raw_bytes([
+        0x7c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ]);
+    assert_eq!("NaN", format!("{:.2}", nan));
+}
+
+#[test]
+fn it_formats_lower_exp() {
+    let parse = |s| Decimal128::from_mysql_decimal(s, RoundingMode::HalfEven).unwrap();
+    assert_eq!("1.2345e3", format!("{:e}", parse("1234.5")));
+    assert_eq!("5.00e0", format!("{:e}", parse("5.00")));
+    assert_eq!("-1e-3", format!("{:e}", parse("-0.001")));
+    assert_eq!("0e-2", format!("{:e}", parse("0.00")));
+    assert_eq!("1.23e3", format!("{:.2e}", parse("1234.5")));
+    assert_eq!("1.0e4", format!("{:.1e}", parse("9999")));
+    assert_eq!("7.000e0", format!("{:.3e}", Decimal128::from(7)));
+    assert_eq!("  +1.2e1", format!("{:+8.1e}", Decimal128::from(12)));
+
+    let vec: [u8; 16] = [
+        0x5f, 0xfe, 0x31, 0x4d, 0xc6, 0x44, 0x8d, 0x93, 0x38, 0xc1, 0x5b, 0x0a, 0x00, 0x00, 0x00,
+        0x00,
+    ];
+    assert_eq!("1e6144", format!("{:.0e}", Decimal128::from_raw_bytes(vec)));
+    let infinity = Decimal128::from_raw_bytes([
+        0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ]);
+    assert_eq!("-Infinity", format!("{:e}", infinity));
+}
+
+#[test]
+fn it_formats_upper_exp() {
+    let parse = |s| Decimal128::from_mysql_decimal(s, RoundingMode::HalfEven).unwrap();
+    assert_eq!("1.2345E3", format!("{:E}", parse("1234.5")));
+    assert_eq!("-1.0E-3", format!("{:.1E}", parse("-0.001")));
+    assert_eq!("0001.2E3", format!("{:08.1E}", parse("1234.5")));
+}
+
+#[test]
+fn it_returns_plain_strings() {
+    // 1.234E+10
+    let vec: [u8; 16] = [
+        0x30, 0x4e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04,
+        0xd2,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!("1.234E+10".to_string(), dec128.to_string());
+    assert_eq!("12340000000".to_string(), dec128.to_plain_string());
+
+    let dec128 = Decimal128::from_mysql_decimal("-0.00123400000", RoundingMode::HalfEven).unwrap();
+    assert_eq!("-0.00123400000".to_string(), dec128.to_plain_string());
+
+    let vec: [u8; 16] = [
+        0x80, 0x00, 0x3c, 0xde, 0x6f, 0xff, 0x97, 0x32, 0xde, 0x82, 0x5c, 0xd0, 0x7e, 0x96, 0xaf,
+        0xf2,
+    ];
+    let plain = Decimal128::from_raw_bytes(vec).to_plain_string();
+    assert_eq!(6179, plain.len());
+    assert!(plain.starts_with("-0.000"));
+    assert!(plain.ends_with("1234567890123456789012345678901234"));
+
+    let infinity = Decimal128::from_raw_bytes([
+        0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ]);
+    assert_eq!("-Infinity".to_string(), infinity.to_plain_string());
+}
+
+#[test]
+fn it_returns_scientific_strings() {
+    let parse = |s| Decimal128::from_mysql_decimal(s, RoundingMode::HalfEven).unwrap();
+    assert_eq!("-1.2345E+3", parse("-1234.5").to_scientific_string(None));
+    assert_eq!("5.00E+0", parse("5.00").to_scientific_string(None));
+    assert_eq!("0E+0", Decimal128::from(0).to_scientific_string(None));
+    assert_eq!("0.001", parse("0.001").to_scientific_string(Some(6)));
+    assert_eq!("1E-3", parse("0.001").to_scientific_string(Some(2)));
+    assert_eq!(
+        "0.0000000001234",
+        parse("0.0000000001234").to_scientific_string(Some(10))
+    );
+
+    let vec: [u8; 16] = [
+        0x30, 0x46, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!("1E+3", dec128.to_scientific_string(Some(100)));
+
+    // a threshold past the smallest exponent writes everything positionally
+    let vec: [u8; 16] = [
+        0x80, 0x00, 0x3c, 0xde, 0x6f, 0xff, 0x97, 0x32, 0xde, 0x82, 0x5c, 0xd0, 0x7e, 0x96, 0xaf,
+        0xf2,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!(
+        dec128.to_plain_string(),
+        dec128.to_scientific_string(Some(u32::MAX))
+    );
+}
+
+#[test]
+fn it_formats_raw_encodings() {
+    let vec: [u8; 16] = [
+        0x30, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0xbe, 0x99, 0x1a,
+        0x14,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!(
+        "30400000000000000000001cbe991a14".to_string(),
+        format!("{:x}", dec128)
+    );
+    assert_eq!(
+        "30400000000000000000001CBE991A14".to_string(),
+        format!("{:X}", dec128)
+    );
+    let binary = format!("{:b}", dec128);
+    assert_eq!(128, binary.len());
+    assert!(binary.starts_with("0011000001000000"));
+    assert!(binary.ends_with("0001101000010100"));
+
+    assert_eq!(
+        "0x30400000000000000000001cbe991a14".to_string(),
+        format!("{:#x}", dec128)
+    );
+    assert_eq!(
+        "0x30400000000000000000001CBE991A14".to_string(),
+        format!("{:#X}", dec128)
+    );
+    assert!(format!("{:#b}", dec128).starts_with("0b0011"));
+    assert_eq!(
+        "3040 0000 0000 0000 0000 001c be99 1a14".to_string(),
+        dec128.to_grouped_hex(2)
+    );
+    assert_eq!(
+        "30 40 00 00 00 00 00 00 00 00 00 1c be 99 1a 14".to_string(),
+        dec128.to_grouped_hex(1)
+    );
+    assert_eq!(format!("{:x}", dec128), dec128.to_grouped_hex(0));
+}
+
+#[test]
+fn it_groups_integer_digits() {
+    let parse = |s| Decimal128::from_mysql_decimal(s, RoundingMode::HalfEven).unwrap();
+    let dec128 = parse("1234567.891");
+    assert_eq!(
+        "1,234,567.891",
+        dec128.to_formatted_string(&Grouping::Comma)
+    );
+    assert_eq!(
+        "1 234 567.891",
+        dec128.to_formatted_string(&Grouping::Space)
+    );
+    assert_eq!(
+        "1_234_567.891",
+        dec128.to_formatted_string(&Grouping::Underscore)
+    );
+    assert_eq!(
+        "1'234'567.891",
+        dec128.to_formatted_string(&Grouping::Apostrophe)
+    );
+    assert_eq!("1234567.891", dec128.to_formatted_string(&Grouping::None));
+    assert_eq!(
+        "-123,456",
+        parse("-123456").to_formatted_string(&Grouping::Comma)
+    );
+    assert_eq!("-12", parse("-12").to_formatted_string(&Grouping::Comma));
+    assert_eq!(
+        "0.001",
+        parse("0.001").to_formatted_string(&Grouping::Comma)
+    );
+
+    let vec: [u8; 16] = [
+        0x30, 0x4e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04,
+        0xd2,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!("1.234E+10", dec128.to_formatted_string(&Grouping::Comma));
+}
+
+#[test]
+fn it_formats_with_options() {
+    let parse = |s| Decimal128::from_mysql_decimal(s, RoundingMode::HalfEven).unwrap();
+    let dec128 = parse("-1234567.89");
+    assert_eq!("-1234567.89", dec128.format_with(&FormatOptions::default()));
+
+    let european = FormatOptions {
+        decimal_separator: ',',
+        grouping_separator: Some('.'),
+        ..FormatOptions::default()
+    };
+    assert_eq!("-1.234.567,89", dec128.format_with(&european));
+    assert_eq!("999", parse("999").format_with(&european));
+
+    let myriads = FormatOptions {
+        grouping_separator: Some(' '),
+        group_size: 4,
+        ..FormatOptions::default()
+    };
+    assert_eq!("-123 4567.89", dec128.format_with(&myriads));
+
+    let no_groups = FormatOptions {
+        grouping_separator: Some(','),
+        group_size: 0,
+        ..FormatOptions::default()
+    };
+    assert_eq!("-1234567.89", dec128.format_with(&no_groups));
+
+    let lower_exp = FormatOptions {
+        decimal_separator: ',',
+        exponent_marker: 'e',
+        ..FormatOptions::default()
+    };
+    assert_eq!("1,23e-7", parse("0.000000123").format_with(&lower_exp));
+
+    let wide = FormatOptions {
+        grouping_separator: Some(','),
+        min_positional_exponent: -10,
+        max_positional_exponent: 12,
+        ..FormatOptions::default()
+    };
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!("0.000000123", d("1.23E-7").format_with(&wide));
+    assert_eq!("0.0000000001", d("1E-10").format_with(&wide));
+    assert_eq!("1E-11", d("1E-11").format_with(&wide));
+    assert_eq!("1,500", d("1.5E+3").format_with(&wide));
+    assert_eq!("-1,000,000,000,000", d("-1E+12").format_with(&wide));
+    assert_eq!("1E+13", d("1E+13").format_with(&wide));
+    assert_eq!("0E+5", d("0E+5").format_with(&wide));
+    assert_eq!("0E+13", d("0E+13").format_with(&wide));
+    // numbers with an exponent of 0 or less are positional however large
+    assert_eq!(
+        "12,345,678,901,234,567.5",
+        d("12345678901234567.5").format_with(&wide)
+    );
+
+    // only ever scientific notation
+    let scientific = FormatOptions {
+        min_positional_exponent: i32::MAX,
+        ..FormatOptions::default()
+    };
+    assert_eq!("1.23E+0", d("1.23").format_with(&scientific));
+    assert_eq!("0E+0", d("0").format_with(&scientific));
+
+    let nan = Decimal128::from_raw_bytes([0x7c, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!("NaN", nan.format_with(&european));
+    let infinity = Decimal128::from_raw_bytes([0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!("-Infinity", infinity.format_with(&european));
+}
+
+#[test]
+fn it_formats_and_parses_percentages() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!("12.5%", d("0.125").to_percent_string(1));
+    assert_eq!("12%", d("0.125").to_percent_string(0));
+    assert_eq!("14%", d("0.135").to_percent_string(0));
+    assert_eq!("-3.000%", d("-0.03").to_percent_string(3));
+    assert_eq!("0.00%", d("0.00005").to_percent_string(2));
+    assert_eq!("0.01%", d("0.00006").to_percent_string(2));
+    assert_eq!("0.00%", d("0.00004").to_percent_string(2));
+    assert_eq!("-0.00%", d("-0.00004").to_percent_string(2));
+    assert_eq!("150000%", d("1.5E+3").to_percent_string(0));
+    assert_eq!("0%", d("0E+3").to_percent_string(0));
+    assert_eq!("NaN%", d("NaN").to_percent_string(2));
+    assert_eq!("-Infinity%", d("-Infinity").to_percent_string(2));
+    // the largest value has 6147 digits as a percentage
+    let max = d("9.999999999999999999999999999999999E+6144");
+    assert_eq!(6148, max.to_percent_string(0).len());
+
+    let percent = |s: &str| Decimal128::from_percent_str(s).map(|d| d.to_string());
+    assert_eq!(Ok("0.125".to_string()), percent("12.5%"));
+    assert_eq!(Ok("0.125".to_string()), percent("12.5"));
+    assert_eq!(Ok("1.00".to_string()), percent("100%"));
+    assert_eq!(Ok("-0.0300".to_string()), percent("-3.00%"));
+    assert_eq!(Ok("1E+3".to_string()), percent("1E+5%"));
+    assert_eq!(Ok("0E-6176".to_string()), percent("0E-6176%"));
+    assert_eq!(Ok("1E-6176".to_string()), percent("100E-6176%"));
+    assert_eq!(Ok("Infinity".to_string()), percent("Infinity%"));
+    assert_eq!(
+        Err(ParseDecimal128Error::ExponentOverflow),
+        percent("1E-6176%")
+    );
+    assert_eq!(Err(ParseDecimal128Error::Empty), percent("%"));
+    assert_eq!(
+        Err(ParseDecimal128Error::InvalidCharacter { index: 2 }),
+        percent("12%%")
+    );
+
+    for s in ["0.125", "-0.0300", "1E+3", "0.0000001"].iter() {
+        let formatted = d(s).to_percent_string(10);
+        let parsed = Decimal128::from_percent_str(&formatted).unwrap();
+        assert_eq!(d(s), parsed, "{}", formatted);
+    }
+}
+
+#[test]
+fn it_parses_strings() {
+    let parse = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!("1", parse("1").to_string());
+    assert_eq!("-1.50", parse("-1.50").to_string());
+    assert_eq!("0.5", parse("+.5").to_string());
+    assert_eq!("12", parse("12.").to_string());
+    assert_eq!("1.23E+5", parse("1.23e5").to_string());
+    assert_eq!("0.00123", parse("123E-5").to_string());
+    assert_eq!("0E+6111", parse("0E+9999").to_string());
+    assert_eq!("1E-6176", parse("0.01E-6174").to_string());
+    assert_eq!(
+        "1.000000000000000000000000000000000E+6144",
+        parse("1E+6144").to_string()
+    );
+    assert_eq!(
+        "1.000000000000000000000000000000000E+37",
+        parse("10000000000000000000000000000000000000").to_string()
+    );
+    assert_eq!("NaN", parse("NaN").to_string());
+    assert_eq!("-Infinity", parse("-Inf").to_string());
+    assert_eq!(
+        [0x30, 0x3c, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xf4],
+        parse("5.00").to_raw_bytes()
+    );
+}
+
+#[test]
+fn it_parses_special_values() {
+    let parse = |s: &str| format!("{:x}", s.parse::<Decimal128>().unwrap());
+    for s in ["Infinity", "infinity", "+INF", "Inf", "iNf"] {
+        assert_eq!("78000000000000000000000000000000", parse(s));
+    }
+    assert_eq!("f8000000000000000000000000000000", parse("-INF"));
+    assert_eq!("f8000000000000000000000000000000", parse("-infinity"));
+    assert_eq!("7c000000000000000000000000000000", parse("NaN"));
+    assert_eq!("7c000000000000000000000000000000", parse("nan"));
+    assert_eq!("fc000000000000000000000000000000", parse("-NAN"));
+    assert_eq!("7e000000000000000000000000000000", parse("sNaN"));
+    assert_eq!("fe000000000000000000000000000000", parse("-snan"));
+    assert_eq!("7c00000000000000000000000000007b", parse("NaN123"));
+    assert_eq!("7c00000000000000000000000000007b", parse("NaN000123"));
+    assert_eq!("7e00000000000000000000000000000c", parse("sNaN12"));
+    assert_eq!(
+        "7c00314dc6448d9338c15b09ffffffff",
+        parse("NaN999999999999999999999999999999999")
+    );
+    assert!("sNaN".parse::<Decimal128>().unwrap().is_nan());
+    assert_eq!("NaN", "NaN123".parse::<Decimal128>().unwrap().to_string());
+
+    let parse_err = |s: &str| s.parse::<Decimal128>().unwrap_err();
+    assert_eq!(
+        ParseDecimal128Error::TooManyDigits,
+        parse_err("NaN1000000000000000000000000000000000")
+    );
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 4 },
+        parse_err("-NaNx")
+    );
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 0 },
+        parse_err("Infinit")
+    );
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 1 },
+        parse_err("-Inf1")
+    );
+}
+
+#[test]
+fn it_parses_ascii_bytes() {
+    let dec128 = Decimal128::parse_ascii(b"1.23E+5").unwrap();
+    assert_eq!("1.23E+5", dec128.to_string());
+    assert!(Decimal128::parse_ascii(b"-sNaN7").unwrap().is_nan());
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 2 },
+        Decimal128::parse_ascii(b"12\xc3\xa9").unwrap_err()
+    );
+    assert_eq!(
+        ParseDecimal128Error::Empty,
+        Decimal128::parse_ascii(b"").unwrap_err()
+    );
+    for s in ["0", "-0.00", "1E+6144", "NaN42", "-Inf", "1.2.3", "1e"] {
+        assert_eq!(
+            s.parse::<Decimal128>().map(|d| d.to_raw_bytes()),
+            Decimal128::parse_ascii(s.as_bytes()).map(|d| d.to_raw_bytes())
+        );
+    }
+}
+
+#[test]
+fn it_parses_lenient_literals() {
+    let parse = |s: &str| Decimal128::parse_lenient(s).map(|d| d.to_string());
+    assert_eq!(Ok("1000000.25".to_string()), parse("1_000_000.25"));
+    assert_eq!(Ok("3.14".to_string()), parse("+3.14"));
+    assert_eq!(Ok("0.000001".to_string()), parse("0.000_001"));
+    assert_eq!(Ok("1E+10".to_string()), parse("1e1_0"));
+    assert_eq!(Ok("100".to_string()), parse("1__00_"));
+    assert_eq!(Ok("-Infinity".to_string()), parse("-inf"));
+    assert_eq!(
+        Err(ParseDecimal128Error::InvalidCharacter { index: 0 }),
+        parse("_1")
+    );
+    assert_eq!(
+        Err(ParseDecimal128Error::InvalidCharacter { index: 2 }),
+        parse("1._5")
+    );
+    assert_eq!(
+        Err(ParseDecimal128Error::InvalidCharacter { index: 5 }),
+        parse("1_000x")
+    );
+    assert_eq!(
+        Err(ParseDecimal128Error::InvalidCharacter { index: 1 }),
+        "1_000".parse::<Decimal128>().map(|d| d.to_string())
+    );
+}
+
+#[test]
+fn it_converts_floats_to_shortest_decimals() {
+    let shortest = |v: f64| Decimal128::from_f64_shortest(v).to_string();
+    assert_eq!("0.1", shortest(0.1));
+    assert_eq!("-2.5", shortest(-2.5));
+    assert_eq!("1E+2", shortest(100.0));
+    assert_eq!("123456789", shortest(123456789.0));
+    assert_eq!("0.30000000000000004", shortest(0.1 + 0.2));
+    assert_eq!("1.7976931348623157E+308", shortest(f64::MAX));
+    assert_eq!("5E-324", shortest(f64::from_bits(1)));
+    assert_eq!("2.2250738585072014E-308", shortest(f64::MIN_POSITIVE));
+    assert_eq!("0", shortest(0.0));
+    assert_eq!("-0", shortest(-0.0));
+    assert_eq!("NaN", shortest(f64::NAN));
+    assert_eq!("Infinity", shortest(f64::INFINITY));
+    assert_eq!("-Infinity", shortest(f64::NEG_INFINITY));
+
+    // every result reads back as the same float
+    for v in [
+        0.1,
+        1.0 / 3.0,
+        2.0f64.sqrt(),
+        -1e-7,
+        6.02214076e23,
+        9007199254740993.0,
+    ] {
+        let decimal = Decimal128::from_f64_shortest(v);
+        assert_eq!(v, decimal.to_string().parse::<f64>().unwrap());
+    }
+}
+
+#[test]
+fn it_rounds_parsed_strings() {
+    let parse = |s: &str, mode| {
+        let (dec128, inexact) = Decimal128::from_str_rounded(s, mode).unwrap();
+        (dec128.to_string(), inexact)
+    };
+    let even = RoundingMode::HalfEven;
+    assert_eq!(("1.50".to_string(), false), parse("1.50", even));
+    assert_eq!(
+        ("1.000000000000000000000000000000000E+37".to_string(), false),
+        parse("10000000000000000000000000000000000000", even)
+    );
+    assert_eq!(
+        ("1234567890123456789012345678901234".to_string(), true),
+        parse("1234567890123456789012345678901234.5", even)
+    );
+    assert_eq!(
+        ("1234567890123456789012345678901235".to_string(), true),
+        parse("1234567890123456789012345678901234.5", RoundingMode::HalfUp)
+    );
+    assert_eq!(("1.0E+6112".to_string(), false), parse("1E+6112", even));
+    assert_eq!(("0E+6111".to_string(), false), parse("0E+9999", even));
+    assert_eq!(("1E-6176".to_string(), true), parse("6E-6177", even));
+    assert_eq!(("0E-6176".to_string(), true), parse("5E-6177", even));
+    assert_eq!(("-Infinity".to_string(), true), parse("-1E+6145", even));
+    assert_eq!(
+        (
+            "9.999999999999999999999999999999999E+6144".to_string(),
+            true
+        ),
+        parse("1E+6145", RoundingMode::Down)
+    );
+    assert_eq!(("NaN".to_string(), false), parse("nan", even));
+    assert_eq!(
+        Err(ParseDecimal128Error::MalformedExponent),
+        Decimal128::from_str_rounded("1E", even).map(|(d, _)| d.to_string())
+    );
+
+    // the flags tell overflow and underflow apart from other rounding
+    let flags = |s: &str, mode| Decimal128::from_str_rounded_with_flags(s, mode).unwrap().1;
+    assert_eq!(Flags::default(), flags("1.50", even));
+    assert_eq!(
+        Flags {
+            inexact: true,
+            ..Flags::default()
+        },
+        flags("1234567890123456789012345678901234.5", even)
+    );
+    let overflow = Flags {
+        inexact: true,
+        overflow: true,
+        ..Flags::default()
+    };
+    assert_eq!(overflow, flags("-1E+6145", even));
+    assert_eq!(overflow, flags("1E+6145", RoundingMode::Down));
+    let underflow = Flags {
+        inexact: true,
+        underflow: true,
+        ..Flags::default()
+    };
+    assert_eq!(underflow, flags("5E-6177", even));
+    assert_eq!(underflow, flags("1.2345E-6174", even));
+    assert_eq!(Flags::default(), flags("1E-6176", even));
+    assert_eq!(Flags::default(), flags("Infinity", even));
+}
+
+#[test]
+fn it_reports_parse_errors() {
+    let parse = |s: &str| s.parse::<Decimal128>().unwrap_err();
+    assert_eq!(ParseDecimal128Error::Empty, parse(""));
+    assert_eq!(ParseDecimal128Error::Empty, parse("-"));
+    assert_eq!(ParseDecimal128Error::Empty, parse("."));
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 1 },
+        parse("1a")
+    );
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 4 },
+        parse("-1.2.3")
+    );
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 3 },
+        parse("1E+x")
+    );
+    assert_eq!(
+        ParseDecimal128Error::InvalidCharacter { index: 0 },
+        parse(" 1")
+    );
+    assert_eq!(ParseDecimal128Error::MalformedExponent, parse("1E"));
+    assert_eq!(ParseDecimal128Error::MalformedExponent, parse("1e-"));
+    assert_eq!(ParseDecimal128Error::ExponentOverflow, parse("1E+6145"));
+    assert_eq!(ParseDecimal128Error::ExponentOverflow, parse("1E-6177"));
+    assert_eq!(
+        ParseDecimal128Error::ExponentOverflow,
+        parse("1E+99999999999999999999")
+    );
+    assert_eq!(
+        ParseDecimal128Error::TooManyDigits,
+        parse("1.2345678901234567890123456789012345")
+    );
+    assert_eq!(
+        "invalid character at index 1",
+        ParseDecimal128Error::InvalidCharacter { index: 1 }.to_string()
+    );
+}
+
+#[test]
+fn it_parses_literals_in_constants() {
+    const FEE: Decimal128 = Decimal128::parse_const("0.0025");
+    assert_eq!("0.0025", FEE.to_string());
+
+    // the same encodings `parse` gives
+    for s in [
+        "0",
+        "-0",
+        "+1",
+        "-12.50",
+        ".5",
+        "5.",
+        "1e+5",
+        "1E-3",
+        "0e-9999",
+        "0E+9999",
+        "-0.000",
+        "9.999999999999999999999999999999999E+6144",
+        "1E+6144",
+        "1E-6176",
+        "1.0000000000000000000000000000000000000",
+        "00000000000000000000000000000000000001.5",
+        "1234567890123456789012345678901234",
+    ]
+    .iter()
+    {
+        let parsed: Decimal128 = s.parse().unwrap();
+        assert_eq!(
+            parsed.to_raw_bytes(),
+            Decimal128::parse_const(s).to_raw_bytes(),
+            "{}",
+            s
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "more than 34 significant digits")]
+fn it_panics_parsing_long_literals() {
+    Decimal128::parse_const("1.2345678901234567890123456789012345");
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn it_panics_parsing_literals_out_of_range() {
+    Decimal128::parse_const("1E-6177");
+}
+
+#[test]
+#[should_panic(expected = "invalid character")]
+fn it_panics_parsing_special_literals() {
+    Decimal128::parse_const("NaN");
+}
+
+#[test]
+fn it_decodes_and_encodes_slices() {
+    let strings = ["1", "-5.00", "1E+3", "NaN", "-Infinity", "0E-6176"];
+    let values: Vec<Decimal128> = strings.iter().map(|s| s.parse().unwrap()).collect();
+    let mut bytes = vec![0u8; 16 * values.len()];
+    assert_eq!(
+        Ok(bytes.len()),
+        Decimal128::encode_slice(&values, &mut bytes)
+    );
+    assert_eq!(values[1].to_raw_bytes(), bytes[16..32]);
+
+    let decoded = Decimal128::decode_slice(&bytes).unwrap();
+    let decoded: Vec<String> = decoded.iter().map(|d| d.to_string()).collect();
+    assert_eq!(strings.to_vec(), decoded);
+
+    let iter = Decimal128::decode_iter(&bytes).unwrap();
+    assert_eq!(strings.len(), iter.len());
+    assert_eq!(
+        "1E+3",
+        Decimal128::decode_iter(&bytes)
+            .unwrap()
+            .nth(2)
+            .unwrap()
+            .to_string()
+    );
+    assert!(Decimal128::decode_slice(&[]).unwrap().is_empty());
+
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        Decimal128::decode_slice(&bytes[1..]).map(|v| v.len())
+    );
+    assert!(Decimal128::decode_iter(&bytes[..17]).is_err());
+    let mut short = [0xaau8; 31];
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        Decimal128::encode_slice(&values[..2], &mut short)
+    );
+    assert!(short.iter().all(|b| *b == 0xaa));
+    let mut long = [0xaau8; 20];
+    assert_eq!(Ok(16), Decimal128::encode_slice(&values[..1], &mut long));
+    assert_eq!([0xaa; 4], long[16..]);
+}
+
+#[test]
+fn it_sums_values() {
+    let sum = |values: &[&str]| {
+        let values: Vec<Decimal128> = values.iter().map(|v| v.parse().unwrap()).collect();
+        values.iter().sum::<Decimal128>().to_string()
+    };
+    assert_eq!("3.25", sum(&["1.50", "2", "-0.25"]));
+    assert_eq!("1001", sum(&["1E+3", "1"]));
+    assert_eq!("1E+3", sum(&["1E+3"]));
+    assert_eq!("0", sum(&[]));
+    assert_eq!("-0", sum(&["-0", "-0"]));
+    assert_eq!("0", sum(&["0", "-0"]));
+    assert_eq!("0.00", sum(&["1.5", "-1.50"]));
+    assert_eq!("0E-6176", sum(&["1E-6176", "-1E-6176"]));
+    assert_eq!(
+        "1.000000000000000000000000000000000E+34",
+        sum(&["9999999999999999999999999999999999", "1"])
+    );
+    assert_eq!(
+        "1234567890123456789012345678901236",
+        sum(&["1234567890123456789012345678901235", "0.5"])
+    );
+    assert_eq!("1.000000000000000000000000000000000", sum(&["1", "1E-40"]));
+    assert_eq!(
+        "1.000000000000000000000000000000000E+6144",
+        sum(&["1E+6144", "-1E-6176"])
+    );
+    assert_eq!(
+        "Infinity",
+        sum(&["9.999999999999999999999999999999999E+6144", "1E+6111"])
+    );
+    assert_eq!("-Infinity", sum(&["1", "-Infinity", "-Infinity"]));
+    assert_eq!("NaN", sum(&["Infinity", "1", "-Infinity"]));
+
+    let values: Vec<Decimal128> = ["1", "sNaN12", "NaN34"]
+        .iter()
+        .map(|v| v.parse().unwrap())
+        .collect();
+    let nan = values.into_iter().sum::<Decimal128>();
+    assert_eq!("7c00000000000000000000000000000c", format!("{:x}", nan));
+}
+
+#[test]
+fn it_sums_slices_exactly() {
+    let sum = |values: &[&str]| {
+        let values: Vec<Decimal128> = values.iter().map(|v| v.parse().unwrap()).collect();
+        let (total, flags) = Decimal128::sum_exact(&values);
+        (total.to_string(), flags)
+    };
+    let exact = Flags::default();
+    let inexact = Flags {
+        inexact: true,
+        ..Flags::default()
+    };
+    assert_eq!(("0".to_string(), exact), sum(&[]));
+    assert_eq!(("3.25".to_string(), exact), sum(&["1.50", "2", "-0.25"]));
+    assert_eq!(
+        ("1000000000000000000000000000000002".to_string(), exact),
+        sum(&["1E+33", "0.5", "0.5", "0.5", "0.5"])
+    );
+    assert_eq!(
+        ("1000000000000000000000000000000002".to_string(), inexact),
+        sum(&["1E+33", "0.5", "0.5", "0.5", "0.5", "0.1"])
+    );
+    assert_eq!(
+        ("1E-6176".to_string(), exact),
+        sum(&["1E+6144", "1E-6176", "-1E+6144"])
+    );
+    assert_eq!(("0.00".to_string(), exact), sum(&["1", "-1.00"]));
+    assert_eq!(("-0".to_string(), exact), sum(&["-0", "-0"]));
+    assert_eq!(
+        (
+            "Infinity".to_string(),
+            Flags {
+                inexact: true,
+                overflow: true,
+                ..Flags::default()
+            }
+        ),
+        sum(&[
+            "9.999999999999999999999999999999999E+6144",
+            "9.999999999999999999999999999999999E+6144"
+        ])
+    );
+    assert_eq!(("-Infinity".to_string(), exact), sum(&["1", "-Inf"]));
+    assert_eq!(
+        (
+            "NaN".to_string(),
+            Flags {
+                invalid_operation: true,
+                ..Flags::default()
+            }
+        ),
+        sum(&["Inf", "-Inf"])
+    );
+    assert_eq!(("NaN".to_string(), exact), sum(&["Inf", "NaN"]));
+
+    let values: Vec<Decimal128> = (0..1000).map(|_| "0.01".parse().unwrap()).collect();
+    assert_eq!("10.00", Decimal128::sum_exact(&values).0.to_string());
+}
+
+#[test]
+fn it_multiplies_values() {
+    let product = |values: &[&str]| {
+        let values: Vec<Decimal128> = values.iter().map(|v| v.parse().unwrap()).collect();
+        values.iter().product::<Decimal128>().to_string()
+    };
+    assert_eq!("-0.300", product(&["1.5", "-2", "0.10"]));
+    assert_eq!("1", product(&[]));
+    assert_eq!("-0", product(&["-0", "5"]));
+    assert_eq!("0E+6111", product(&["0E+5000", "0E+5000"]));
+    assert_eq!(
+        "1.524157875323883675049535156256667E+66",
+        product(&[
+            "1234567890123456789012345678901234",
+            "1234567890123456789012345678901234"
+        ])
+    );
+    assert_eq!(
+        "9.999999999999999999999999999999998E+67",
+        product(&[
+            "9999999999999999999999999999999999",
+            "9999999999999999999999999999999999"
+        ])
+    );
+    assert_eq!("Infinity", product(&["1E+6144", "10"]));
+    assert_eq!("0E-6176", product(&["1E-6176", "0.1"]));
+    assert_eq!("1E-6176", product(&["1E-6176", "0.6"]));
+    assert_eq!("-Infinity", product(&["-Infinity", "2"]));
+    assert_eq!("NaN", product(&["Infinity", "0"]));
+    assert_eq!("NaN", product(&["2", "NaN"]));
+}
+
+#[test]
+fn it_saturates_on_overflow() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let max = "9.999999999999999999999999999999999E+6144";
+    assert_eq!("3.50", d("1.50").saturating_add(&d("2")).to_string());
+    assert_eq!("-0.50", d("1.50").saturating_sub(&d("2")).to_string());
+    assert_eq!("3.00", d("1.50").saturating_mul(&d("2")).to_string());
+
+    assert_eq!(max, d(max).saturating_add(&d(max)).to_string());
+    assert_eq!(
+        format!("-{}", max),
+        d(&format!("-{}", max)).saturating_sub(&d(max)).to_string()
+    );
+    assert_eq!(
+        max,
+        d("-1E+5000").saturating_mul(&d("-1E+5000")).to_string()
+    );
+    assert_eq!(
+        format!("-{}", max),
+        d("1E+5000").saturating_mul(&d("-1E+5000")).to_string()
+    );
+    assert_eq!(max, d(max).saturating_sub(&d("-1")).to_string());
+
+    assert_eq!(
+        "Infinity",
+        d("Infinity").saturating_add(&d("1")).to_string()
+    );
+    assert_eq!(
+        "-Infinity",
+        d("2").saturating_mul(&d("-Infinity")).to_string()
+    );
+    assert_eq!(
+        "NaN",
+        d("Infinity").saturating_sub(&d("Infinity")).to_string()
+    );
+    assert!(d("NaN").saturating_add(&d("1")).is_nan());
+    assert!(d("1").saturating_sub(&d("-NaN")).is_negative());
+}
+
+#[test]
+fn it_reports_overflow() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let result = |(v, overflow): (Decimal128, bool)| (v.to_string(), overflow);
+    let max = d("9.999999999999999999999999999999999E+6144");
+    assert_eq!(
+        ("3.50".to_string(), false),
+        result(d("1.50").overflowing_add(&d("2")))
+    );
+    assert_eq!(
+        ("Infinity".to_string(), true),
+        result(max.overflowing_add(&max))
+    );
+    assert_eq!(
+        ("-Infinity".to_string(), true),
+        result(max.with_sign(true).overflowing_sub(&max))
+    );
+    assert_eq!(
+        ("Infinity".to_string(), true),
+        result(d("-1E+5000").overflowing_mul(&d("-1E+5000")))
+    );
+    assert_eq!(
+        ("0.25".to_string(), false),
+        result(d("1.00").overflowing_div(&d("4")))
+    );
+    assert_eq!(
+        ("-Infinity".to_string(), true),
+        result(max.overflowing_div(&d("-0.1")))
+    );
+    assert_eq!(
+        ("Infinity".to_string(), false),
+        result(d("1").overflowing_div(&d("0")))
+    );
+    assert_eq!(
+        ("Infinity".to_string(), false),
+        result(d("Infinity").overflowing_mul(&d("2")))
+    );
+    let (nan, overflow) = d("NaN").overflowing_div(&d("1"));
+    assert!(nan.is_nan() && !overflow);
+}
+
+#[test]
+fn it_reports_conditions_raised() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let (sum, flags) = d("1.50").add_with_flags(&d("2"));
+    assert_eq!("3.50", sum.to_string());
+    assert!(flags.is_empty());
+
+    let (quotient, flags) = d("2").div_with_flags(&d("3"));
+    assert_eq!("0.6666666666666666666666666666666667", quotient.to_string());
+    assert_eq!(
+        Flags {
+            inexact: true,
+            ..Flags::default()
+        },
+        flags
+    );
+
+    let (product, flags) = d("1E+5000").mul_with_flags(&d("-1E+5000"));
+    assert_eq!("-Infinity", product.to_string());
+    assert!(flags.overflow && flags.inexact && !flags.underflow);
+
+    let (quotient, flags) = d("1E-6176").div_with_flags(&d("3"));
+    assert_eq!("0E-6176", quotient.to_string());
+    assert!(flags.underflow && flags.inexact);
+    let (difference, flags) = d("1E-6170").sub_with_flags(&d("1E-6176"));
+    assert_eq!("9.99999E-6171", difference.to_string());
+    assert!(flags.is_empty());
+
+    let (quotient, flags) = d("-5").div_with_flags(&d("0"));
+    assert_eq!("-Infinity", quotient.to_string());
+    assert!(flags.division_by_zero && !flags.overflow);
+    let (quotient, flags) = d("0").div_with_flags(&d("0"));
+    assert!(quotient.is_nan() && flags.invalid_operation && !flags.division_by_zero);
+    let (difference, flags) = d("Infinity").sub_with_flags(&d("Infinity"));
+    assert!(difference.is_nan() && flags.invalid_operation);
+    let (sum, flags) = d("NaN").add_with_flags(&d("1"));
+    assert!(sum.is_nan() && flags.is_empty());
+}
+
+#[test]
+fn it_returns_errors_from_checked_operations() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let ok = |result: Result<Decimal128, ArithmeticError>| result.unwrap().to_string();
+    assert_eq!("3.50", ok(d("1.50").checked_add(&d("2"))));
+    assert_eq!("-0.50", ok(d("1.50").checked_sub(&d("2"))));
+    assert_eq!("3.00", ok(d("1.50").checked_mul(&d("2"))));
+    assert_eq!("0.75", ok(d("1.50").checked_div(&d("2"))));
+    assert_eq!("1.50", ok(d("7.50").checked_rem(&d("2"))));
+    // rounding, overflow and NaN operands aren't errors
+    assert_eq!(
+        "0.6666666666666666666666666666666667",
+        ok(d("2").checked_div(&d("3")))
+    );
+    assert_eq!("Infinity", ok(d("9E+6144").checked_add(&d("9E+6144"))));
+    assert_eq!("NaN", ok(d("NaN").checked_mul(&d("0"))));
+
+    let invalid = Err(ArithmeticError::InvalidOperation);
+    assert_eq!(invalid, d("Infinity").checked_add(&d("-Infinity")));
+    assert_eq!(invalid, d("Infinity").checked_sub(&d("Infinity")));
+    assert_eq!(invalid, d("0").checked_mul(&d("-Infinity")));
+    assert_eq!(invalid, d("0").checked_div(&d("0")));
+    assert_eq!(invalid, d("Infinity").checked_div(&d("Infinity")));
+    assert_eq!(invalid, d("5").checked_rem(&d("0")));
+    assert_eq!(invalid, d("Infinity").checked_rem(&d("2")));
+    assert_eq!(invalid, d("1E+34").checked_rem(&d("1")));
+    assert_eq!(
+        Err(ArithmeticError::DivisionByZero),
+        d("-5").checked_div(&d("0"))
+    );
+    assert_eq!(
+        "division by zero",
+        ArithmeticError::DivisionByZero.to_string()
+    );
+}
+
+#[test]
+fn it_reports_conditions_of_other_operations() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let result = |(v, flags): (Decimal128, Flags)| (v.to_string(), flags);
+    let exact = Flags::default();
+    let inexact = Flags {
+        inexact: true,
+        ..Flags::default()
+    };
+    let invalid = Flags {
+        invalid_operation: true,
+        ..Flags::default()
+    };
+    let division_by_zero = Flags {
+        division_by_zero: true,
+        ..Flags::default()
+    };
+    let overflow = Flags {
+        inexact: true,
+        overflow: true,
+        ..Flags::default()
+    };
+    let underflow = Flags {
+        inexact: true,
+        underflow: true,
+        ..Flags::default()
+    };
+
+    assert_eq!(
+        ("13".to_string(), exact),
+        result(d("100.00").divide_integer_with_flags(&d("7.50")))
+    );
+    assert_eq!(
+        ("NaN".to_string(), invalid),
+        result(d("1E+34").divide_integer_with_flags(&d("1")))
+    );
+    assert_eq!(
+        ("-Infinity".to_string(), division_by_zero),
+        result(d("-1").divide_integer_with_flags(&d("0")))
+    );
+    assert_eq!(
+        ("NaN".to_string(), invalid),
+        result(d("0").divide_integer_with_flags(&d("0")))
+    );
+    assert_eq!(
+        ("Infinity".to_string(), exact),
+        result(d("Infinity").divide_integer_with_flags(&d("2")))
+    );
+
+    let div_rem = |a: &str, b: &str| {
+        let (quotient, remainder, flags) = d(a).div_rem_with_flags(&d(b));
+        (quotient.to_string(), remainder.to_string(), flags)
+    };
+    assert_eq!(
+        ("13".to_string(), "2.50".to_string(), exact),
+        div_rem("100.00", "7.50")
+    );
+    assert_eq!(
+        (
+            "Infinity".to_string(),
+            "NaN".to_string(),
+            Flags {
+                division_by_zero: true,
+                invalid_operation: true,
+                ..Flags::default()
+            }
+        ),
+        div_rem("3", "0")
+    );
+    assert_eq!(
+        ("Infinity".to_string(), "NaN".to_string(), invalid),
+        div_rem("Infinity", "3")
+    );
+    assert_eq!(
+        ("NaN".to_string(), "NaN".to_string(), invalid),
+        div_rem("1E+34", "1")
+    );
+    assert_eq!(
+        ("NaN".to_string(), "NaN".to_string(), exact),
+        div_rem("NaN", "0")
+    );
+
+    assert_eq!(
+        ("0.25".to_string(), exact),
+        result(d("4").recip_with_flags())
+    );
+    assert_eq!(
+        ("0.3333333333333333333333333333333333".to_string(), inexact),
+        result(d("3").recip_with_flags())
+    );
+    assert_eq!(
+        ("-Infinity".to_string(), division_by_zero),
+        result(d("-0").recip_with_flags())
+    );
+    assert_eq!(
+        ("Infinity".to_string(), overflow),
+        result(d("1E-6176").recip_with_flags())
+    );
+    assert_eq!(
+        ("0E-6176".to_string(), exact),
+        result(d("Infinity").recip_with_flags())
+    );
+
+    assert_eq!(
+        ("2".to_string(), exact),
+        result(d("1").midpoint_with_flags(&d("3")))
+    );
+    assert_eq!(
+        ("0E-6176".to_string(), underflow),
+        result(d("1E-6176").midpoint_with_flags(&d("0")))
+    );
+    assert_eq!(
+        ("NaN".to_string(), invalid),
+        result(d("Infinity").midpoint_with_flags(&d("-Infinity")))
+    );
+
+    let rescale =
+        |s: &str, scale: i32| result(d(s).rescale_with_flags(scale, RoundingMode::HalfEven));
+    assert_eq!(("12.3450".to_string(), exact), rescale("12.345", 4));
+    assert_eq!(("12.34".to_string(), inexact), rescale("12.345", 2));
+    assert_eq!(("12.30".to_string(), exact), rescale("12.300", 2));
+    assert_eq!(("NaN".to_string(), invalid), rescale("12.345", 40));
+    assert_eq!(("NaN".to_string(), invalid), rescale("1", 6177));
+    assert_eq!(("Infinity".to_string(), exact), rescale("Infinity", 2));
+
+    let round_sf = |s: &str, n: u32, mode: RoundingMode| result(d(s).round_sf_with_flags(n, mode));
+    let even = RoundingMode::HalfEven;
+    assert_eq!(
+        ("1.50E+8".to_string(), inexact),
+        round_sf("149597870.7", 3, even)
+    );
+    assert_eq!(("1.5".to_string(), exact), round_sf("1.5", 3, even));
+    assert_eq!(("NaN".to_string(), invalid), round_sf("1.5", 0, even));
+    let max = "9.999999999999999999999999999999999E+6144";
+    assert_eq!(("Infinity".to_string(), overflow), round_sf(max, 2, even));
+    assert_eq!(
+        (
+            "9.900000000000000000000000000000000E+6144".to_string(),
+            inexact
+        ),
+        round_sf(max, 2, RoundingMode::Floor)
+    );
+    assert_eq!(
+        ("-Infinity".to_string(), overflow),
+        round_sf(&format!("-{}", max), 2, RoundingMode::Floor)
+    );
+    assert_eq!(
+        ("1.2E-6174".to_string(), underflow),
+        round_sf("1.23E-6174", 2, even)
+    );
+}
+
+#[test]
+fn it_uses_arithmetic_operators() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!("3.50", (d("1.50") + d("2")).to_string());
+    assert_eq!("-0.50", (&d("1.50") - &d("2")).to_string());
+    assert_eq!("3.00", (d("1.50") * d("2")).to_string());
+    assert_eq!("0.75", (&d("1.50") / &d("2")).to_string());
+    assert_eq!("2.50", (d("100.00") % d("7.50")).to_string());
+    assert_eq!("-2.50", (d("-100.00") % d("7.50")).to_string());
+    assert_eq!("-1.50", (-d("1.50")).to_string());
+    assert_eq!("0", (-&d("-0")).to_string());
+
+    assert_eq!(
+        "1.000000000000000000000000000000000E+34",
+        (d("1E+34") + d("1")).to_string()
+    );
+    assert_eq!(
+        "0.3333333333333333333333333333333333",
+        (d("1") / d("3")).to_string()
+    );
+    assert_eq!("Infinity", (d("1E+6144") * d("10")).to_string());
+    assert_eq!("-Infinity", (d("-1") / d("0")).to_string());
+    assert!((d("0") / d("0")).is_nan());
+    assert!((d("1") % d("0")).is_nan());
+    assert!((d("Infinity") - d("Infinity")).is_nan());
+    assert!((-d("NaN")).is_negative());
+}
+
+#[test]
+fn it_finds_midpoints() {
+    let midpoint = |a: &str, b: &str| {
+        let a: Decimal128 = a.parse().unwrap();
+        a.midpoint(&b.parse().unwrap()).to_string()
+    };
+    let max = "9.999999999999999999999999999999999E+6144";
+    assert_eq!("1.5", midpoint("1", "2"));
+    assert_eq!("2", midpoint("1", "3"));
+    assert_eq!("2.50", midpoint("2.00", "3"));
+    assert_eq!("0", midpoint("-3", "3"));
+    assert_eq!("-0", midpoint("-0", "-0"));
+    assert_eq!("0.005", midpoint("0", "0.01"));
+    assert_eq!(max, midpoint(max, max));
+    assert_eq!(
+        "9.999999999999999999999999999999998E+6144",
+        midpoint(max, "9.999999999999999999999999999999998E+6144")
+    );
+    assert_eq!("0E-6176", midpoint("1E-6176", "0"));
+    assert_eq!("2E-6176", midpoint("1E-6176", "3E-6176"));
+    assert_eq!(
+        "1.000000000000000000000000000000000",
+        midpoint("1", "1.000000000000000000000000000000001")
+    );
+    assert_eq!("Infinity", midpoint("Infinity", max));
+    assert_eq!("NaN", midpoint("Infinity", "-Infinity"));
+    assert_eq!("NaN", midpoint("1", "NaN"));
+}
+
+#[test]
+fn it_rounds_results_both_ways_for_bounds() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let pair = |(low, high): (Decimal128, Decimal128)| (low.to_string(), high.to_string());
+    let both = |low: &str, high: &str| (low.to_string(), high.to_string());
+    let max = "9.999999999999999999999999999999999E+6144";
+
+    assert_eq!(both("3.50", "3.50"), pair(d("1.50").add_bounds(&d("2"))));
+    assert_eq!(
+        both(
+            "1.000000000000000000000000000000000E+34",
+            "1.000000000000000000000000000000001E+34"
+        ),
+        pair(d("1E+34").add_bounds(&d("1")))
+    );
+    assert_eq!(
+        both(
+            "-1.000000000000000000000000000000001E+34",
+            "-1.000000000000000000000000000000000E+34"
+        ),
+        pair(d("-1E+34").sub_bounds(&d("1")))
+    );
+    assert_eq!(both("-0", "0"), pair(d("1").sub_bounds(&d("1"))));
+    assert_eq!(both("-0", "0"), pair(d("0").add_bounds(&d("-0"))));
+    assert_eq!(both("0", "0"), pair(d("0").add_bounds(&d("0"))));
+    assert_eq!(both("-0", "-0"), pair(d("-0").add_bounds(&d("-0"))));
+    assert_eq!(both(max, "Infinity"), pair(d(max).add_bounds(&d(max))));
+    assert_eq!(
+        both("-Infinity", &format!("-{}", max)),
+        pair(d(max).mul_bounds(&d("-2")))
+    );
+
+    assert_eq!(
+        both("-0.300", "-0.300"),
+        pair(d("1.5").mul_bounds(&d("-0.20")))
+    );
+    assert_eq!(
+        both(
+            "1524157875323883675019051998750190",
+            "1524157875323883675019051998750191"
+        ),
+        pair(d("12345678901234567.89").mul_bounds(&d("123456789012345678.9")))
+    );
+    assert_eq!(
+        both("0E-6176", "1E-6176"),
+        pair(d("1E-6176").mul_bounds(&d("0.5")))
+    );
+
+    assert_eq!(
+        both(
+            "0.3333333333333333333333333333333333",
+            "0.3333333333333333333333333333333334"
+        ),
+        pair(d("1").div_bounds(&d("3")))
+    );
+    assert_eq!(both("0.25", "0.25"), pair(d("1.00").div_bounds(&d("4"))));
+    assert_eq!(
+        both("0E-6176", "0E-6176"),
+        pair(d("1").div_bounds(&d("Infinity")))
+    );
+    assert_eq!(
+        both("Infinity", "Infinity"),
+        pair(d("1").div_bounds(&d("0")))
+    );
+    assert_eq!(both("NaN", "NaN"), pair(d("0").div_bounds(&d("0"))));
+    assert_eq!(
+        both("NaN", "NaN"),
+        pair(d("Infinity").sub_bounds(&d("Infinity")))
+    );
+    assert_eq!(both("NaN", "NaN"), pair(d("NaN").mul_bounds(&d("1"))));
+
+    // each bound is the result of rounding the other way from the nearest
+    for (a, b) in [("1", "7"), ("-2", "3"), ("1E+34", "-0.6")].iter() {
+        let (low, high) = d(a).div_bounds(&d(b));
+        let nearest = d(a).overflowing_div(&d(b)).0;
+        assert!(low < high);
+        assert!(low == nearest || high == nearest);
+    }
+}
+
+#[test]
+fn it_divides_to_integers() {
+    let div_rem = |a: &str, b: &str| {
+        let a: Decimal128 = a.parse().unwrap();
+        let (quotient, remainder) = a.div_rem(&b.parse().unwrap());
+        (quotient.to_string(), remainder.to_string())
+    };
+    let pair = |q: &str, r: &str| (q.to_string(), r.to_string());
+    assert_eq!(pair("3", "1"), div_rem("10", "3"));
+    assert_eq!(pair("-3", "-1"), div_rem("-10", "3"));
+    assert_eq!(pair("-3", "1"), div_rem("10", "-3"));
+    assert_eq!(pair("13", "2.50"), div_rem("100.00", "7.50"));
+    assert_eq!(pair("333", "0.1"), div_rem("1E+2", "0.3"));
+    assert_eq!(pair("0", "0.001"), div_rem("0.001", "1E+10"));
+    assert_eq!(
+        pair("9999999999999999999999999999999999", "0"),
+        div_rem("9.999999999999999999999999999999999E+33", "1")
+    );
+    assert_eq!(pair("NaN", "NaN"), div_rem("1E+34", "1"));
+    assert_eq!(pair("NaN", "NaN"), div_rem("1E+6144", "1E-6176"));
+    assert_eq!(pair("-Infinity", "NaN"), div_rem("5", "-0"));
+    assert_eq!(pair("NaN", "NaN"), div_rem("0", "0"));
+    assert_eq!(pair("-0", "-5"), div_rem("-5", "Infinity"));
+    assert_eq!(pair("NaN", "NaN"), div_rem("NaN", "1"));
+
+    let total: Decimal128 = "12".parse().unwrap();
+    assert_eq!(
+        "4",
+        total.divide_integer(&"2.5".parse().unwrap()).to_string()
+    );
+}
+
+#[test]
+fn it_builds_values_from_fields() {
+    let value = Decimal128Builder::new()
+        .coefficient(5)
+        .exponent(-3)
+        .build()
+        .unwrap();
+    assert_eq!("0.005", value.to_string());
+
+    let zero = Decimal128Builder::new().sign(true).build().unwrap();
+    assert_eq!("-0", zero.to_string());
+
+    let max = Decimal128Builder::new()
+        .coefficient(9_999_999_999_999_999_999_999_999_999_999_999)
+        .exponent(6111)
+        .build()
+        .unwrap();
+    assert_eq!("9.999999999999999999999999999999999E+6144", max.to_string());
+    let tiny = Decimal128Builder::new().coefficient(1).exponent(-6176);
+    assert_eq!("1E-6176", tiny.build().unwrap().to_string());
+
+    assert_eq!(
+        Err(BuildError::TooManyDigits { digits: 35 }),
+        Decimal128Builder::new()
+            .coefficient(10_000_000_000_000_000_000_000_000_000_000_000)
+            .build()
+    );
+    assert_eq!(
+        Err(BuildError::ExponentOutOfRange { exponent: -6177 }),
+        Decimal128Builder::new().exponent(-6177).build()
+    );
+}
+
+#[test]
+fn it_replaces_single_fields() {
+    let value: Decimal128 = "12.50".parse().unwrap();
+    assert_eq!("-12.50", value.with_sign(true).to_string());
+    assert_eq!("12.50", value.with_sign(true).with_sign(false).to_string());
+    assert_eq!("0.01250", value.with_exponent(-5).unwrap().to_string());
+    assert_eq!("0.07", value.with_coefficient(7).unwrap().to_string());
+    assert_eq!(
+        Err(BuildError::ExponentOutOfRange { exponent: 6112 }),
+        value.with_exponent(6112)
+    );
+    assert_eq!(
+        Err(BuildError::TooManyDigits { digits: 39 }),
+        value.with_coefficient(u128::MAX)
+    );
+
+    let nan: Decimal128 = "NaN".parse().unwrap();
+    assert!(nan.with_sign(true).is_negative());
+    assert!(nan.with_exponent(2).unwrap().is_nan());
+    let inf: Decimal128 = "-Infinity".parse().unwrap();
+    assert_eq!("Infinity", inf.with_sign(false).to_string());
+    assert_eq!("-Infinity", inf.with_coefficient(1).unwrap().to_string());
+}
+
+#[test]
+fn it_raises_ten_to_powers() {
+    let exp10 = |s: &str| s.parse::<Decimal128>().unwrap().exp10().to_string();
+    assert_eq!("1", exp10("0"));
+    assert_eq!("1", exp10("-0.00"));
+    assert_eq!("1E+3", exp10("3"));
+    assert_eq!("1E+3", exp10("3.000"));
+    assert_eq!("1E-7", exp10("-7"));
+    assert_eq!("3.162277660168379331998893544432719", exp10("0.5"));
+    assert_eq!("0.3162277660168379331998893544432719", exp10("-0.5"));
+    assert_eq!("31.62277660168379331998893544432719", exp10("1.5"));
+    assert_eq!("1.258925411794167210423954106395801", exp10("0.1"));
+    assert_eq!("0.005623413251903490803949510397764812", exp10("-2.25"));
+    assert_eq!("5011.872336272722850015541868849458", exp10("3.7"));
+    assert_eq!("1.000000000000000000000000000002303", exp10("1E-30"));
+    assert_eq!("3.162277660168379331998893544432719E+6144", exp10("6144.5"));
+    assert_eq!("5.01187E-6171", exp10("-6170.3"));
+
+    assert_eq!("Infinity", exp10("6145"));
+    assert_eq!("Infinity", exp10("1E+100"));
+    assert_eq!("1E-6176", exp10("-6176"));
+    assert_eq!("0E-6176", exp10("-6177"));
+    assert_eq!("0E-6176", exp10("-1E+100"));
+    assert_eq!("NaN", exp10("NaN"));
+    assert_eq!("Infinity", exp10("Infinity"));
+    assert_eq!("0", exp10("-Infinity"));
+}
+
+#[test]
+fn it_raises_two_to_powers() {
+    let exp2 = |s: &str| s.parse::<Decimal128>().unwrap().exp2().to_string();
+    assert_eq!("1", exp2("0"));
+    assert_eq!("1024", exp2("10"));
+    assert_eq!("0.125", exp2("-3"));
+    assert_eq!("5192296858534827628530496329220096", exp2("112"));
+    assert_eq!("1.038459371706965525706099265844019E+34", exp2("113"));
+    assert_eq!("1.606938044258990275541962092341163E+60", exp2("200"));
+    assert_eq!("6.223015277861141707144064053780124E-61", exp2("-200"));
+    // 5^49 has 35 digits and ends in 5, so this rounds half-even
+    assert_eq!("1.776356839400250464677810668945312E-15", exp2("-49"));
+    assert_eq!("1.414213562373095048801688724209698", exp2("0.5"));
+    assert_eq!("0.7071067811865475244008443621048490", exp2("-0.5"));
+    assert_eq!("2.828427124746190097603377448419396", exp2("1.5"));
+    assert_eq!("1.071773462536293164213006325023342", exp2("0.1"));
+    assert_eq!("0.2102241038134286357577813690583037", exp2("-2.25"));
+    assert_eq!("1.000000000000000000000000000000693", exp2("1E-30"));
+    assert_eq!("5.628961489605482600400511804238708E+6020", exp2("20000.5"));
+
+    assert_eq!("Infinity", exp2("20500"));
+    assert_eq!("0E-6176", exp2("-21000"));
+    assert_eq!("NaN", exp2("-NaN"));
+    assert_eq!("0", exp2("-Infinity"));
+}
+
+#[test]
+fn it_takes_natural_logarithms() {
+    let ln = |s: &str| s.parse::<Decimal128>().unwrap().ln().to_string();
+    assert_eq!("0", ln("1"));
+    assert_eq!("0", ln("1.000"));
+    assert_eq!("0.6931471805599453094172321214581766", ln("2"));
+    assert_eq!("-0.6931471805599453094172321214581766", ln("0.5"));
+    assert_eq!("4.815884817283263883109232105166526", ln("123.456"));
+    assert_eq!("-14220.76553433122614449511522413063", ln("1E-6176"));
+    assert_eq!(
+        "14149.38539644841072829055748903542",
+        ln("9.999999999999999999999999999999999E+6144")
+    );
+    // no digits are lost next to 1
+    assert_eq!(
+        "9.999999999999999999999999999999995E-34",
+        ln("1.000000000000000000000000000000001")
+    );
+    assert_eq!(
+        "-1.000000000000000000000000000000000E-34",
+        ln("0.9999999999999999999999999999999999")
+    );
+
+    assert_eq!("-Infinity", ln("0"));
+    assert_eq!("-Infinity", ln("-0E+3"));
+    assert_eq!("Infinity", ln("Infinity"));
+    assert_eq!("NaN", ln("-1"));
+    assert_eq!("NaN", ln("-Infinity"));
+    assert_eq!("NaN", ln("NaN"));
+}
+
+#[test]
+fn it_takes_logarithms_to_any_base() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let log = |x: &str, base: &str| d(x).log(&d(base)).to_string();
+    let log10 = |x: &str| d(x).log10().to_string();
+    assert_eq!("3", log10("1000"));
+    assert_eq!("3", log10("1.000E+3"));
+    assert_eq!("-3", log10("0.001"));
+    assert_eq!("-6176", log10("1E-6176"));
+    assert_eq!("0", log10("1"));
+    assert_eq!("0.3010299956639811952137388947244930", log10("2"));
+    assert_eq!("0.6989700043360188047862611052755070", log10("5"));
+
+    assert_eq!("3", log("8", "2"));
+    assert_eq!("-3", log("8", "0.5"));
+    assert_eq!("-1", log("0.5", "2"));
+    assert_eq!("1", log("0.5", "0.5"));
+    assert_eq!("14.20669908289047413032023363185645", log("2", "1.05"));
+    assert_eq!("4.191806548578769208593135044042803", log("100", "3"));
+    assert_eq!("20409.92621498795426531524257478281", log("1E+6144", "2"));
+    assert_eq!("0.7480703635874077555355402094929134", log("0.3", "0.2"));
+    assert_eq!("0.6666666666666666666666666666666667", log("9", "27"));
+
+    assert_eq!("Infinity", log("2", "1"));
+    assert_eq!("-Infinity", log("0.5", "1"));
+    assert_eq!("NaN", log("1", "1"));
+    assert_eq!("0", log("2", "Infinity"));
+    assert_eq!("-0", log("2", "0"));
+    assert_eq!("-Infinity", log("0", "2"));
+    assert_eq!("Infinity", log("0", "0.5"));
+    assert_eq!("NaN", log("0", "0"));
+    assert_eq!("NaN", log("Infinity", "0"));
+    assert_eq!("NaN", log("2", "-2"));
+    assert_eq!("NaN", log("-8", "2"));
+    assert_eq!("NaN", log("NaN", "2"));
+    assert_eq!("NaN", log("2", "NaN"));
+}
+
+#[test]
+fn it_divides_integers_into_decimals() {
+    let ratio = |n: i128, d: i128, mode: RoundingMode| {
+        Decimal128::from_ratio(n, d, mode).map(|r| r.to_string())
+    };
+    let even = RoundingMode::HalfEven;
+    let ok = |s: &str| Ok(s.to_string());
+    assert_eq!(ok("0.25"), ratio(1, 4, even));
+    assert_eq!(ok("2"), ratio(6, 3, even));
+    assert_eq!(ok("100"), ratio(100, 1, even));
+    assert_eq!(ok("-0.375"), ratio(3, -8, even));
+    assert_eq!(ok("0.375"), ratio(-3, -8, even));
+    assert_eq!(ok("0"), ratio(0, -5, even));
+    assert_eq!(
+        ok("0.3333333333333333333333333333333333"),
+        ratio(1, 3, even)
+    );
+    assert_eq!(
+        ok("-0.6666666666666666666666666666666667"),
+        ratio(-2, 3, even)
+    );
+    assert_eq!(
+        ok("-0.6666666666666666666666666666666666"),
+        ratio(-2, 3, RoundingMode::Down)
+    );
+    assert_eq!(
+        ok("-0.6666666666666666666666666666666667"),
+        ratio(-2, 3, RoundingMode::Floor)
+    );
+    assert_eq!(Err(ConversionError::Invalid), ratio(1, 0, even));
+
+    // integers wider than a coefficient are only rounded once, after dividing
+    assert_eq!(
+        ok("1.701411834604692317316873037158841E+38"),
+        ratio(i128::MAX, 1, even)
+    );
+    assert_eq!(
+        ok("-1.701411834604692317316873037158841E+38"),
+        ratio(i128::MIN, 1, even)
+    );
+    assert_eq!(ok("1"), ratio(i128::MAX, i128::MAX, even));
+    assert_eq!(
+        ok("1.000000000000000000000000000000000"),
+        ratio(i128::MAX, i128::MAX - 1, even)
+    );
+    assert_eq!(
+        ok("1.000000000000000000000000000000001"),
+        ratio(i128::MAX, i128::MAX - 1, RoundingMode::Up)
+    );
+    assert_eq!(
+        ok("0.9999999999999999999999999999999999"),
+        ratio(i128::MAX - 1, i128::MAX, RoundingMode::Down)
+    );
+    assert_eq!(
+        ok("5.877471754111437539843682686111228E-39"),
+        ratio(1, i128::MAX, even)
+    );
+    // the 35th digit is a 5 followed by a remainder, which makes it more
+    // than half, whether or not the quotient has digits after it
+    let half_down = RoundingMode::HalfDown;
+    assert_eq!(
+        ok("2.000000000000000000000000000000001E+34"),
+        ratio(
+            200_000_000_000_000_000_000_000_000_000_000_051,
+            10,
+            half_down
+        )
+    );
+    assert_eq!(
+        ok("2.000000000000000000000000000000001E+36"),
+        ratio(
+            20_000_000_000_000_000_000_000_000_000_000_005_001,
+            10,
+            half_down
+        )
+    );
+    assert_eq!(
+        ok("2.000000000000000000000000000000000E+36"),
+        ratio(
+            20_000_000_000_000_000_000_000_000_000_000_005_000,
+            10,
+            half_down
+        )
+    );
+}
+
+#[test]
+fn it_writes_decimals_as_exact_fractions() {
+    let ratio = |s: &str| s.parse::<Decimal128>().unwrap().to_ratio();
+    assert_eq!(Ok((3, 2)), ratio("1.50"));
+    assert_eq!(Ok((-1, 80)), ratio("-0.0125"));
+    assert_eq!(Ok((1200, 1)), ratio("1.2E+3"));
+    assert_eq!(Ok((-7, 1)), ratio("-7"));
+    assert_eq!(Ok((3, 10)), ratio("0.3"));
+    assert_eq!(Ok((0, 1)), ratio("0E-20"));
+    assert_eq!(Ok((0, 1)), ratio("-0E+20"));
+    assert_eq!(Ok((0, 1)), ratio("0E+6000"));
+
+    // denominators past 10^38 still fit once they're reduced
+    assert_eq!(Ok((1, 1 << 40)), ratio("9.094947017729282379150390625E-13"));
+    assert_eq!(Ok((1, 5u128.pow(50))), ratio("1.125899906842624E-35"));
+    assert_eq!(Ok((1, 10u128.pow(38))), ratio("1E-38"));
+    assert_eq!(Err(ConversionError::Overflow), ratio("1E-39"));
+    assert_eq!(Err(ConversionError::Overflow), ratio("1E-6176"));
+
+    assert_eq!(
+        Ok((9_999_999_999_999_999_999_999_999_999_999_999 * 10_000, 1)),
+        ratio("9.999999999999999999999999999999999E+37")
+    );
+    assert_eq!(Err(ConversionError::Overflow), ratio("1E+39"));
+    assert_eq!(Err(ConversionError::Overflow), ratio("-1E+6144"));
+    assert_eq!(Err(ConversionError::NotFinite), ratio("NaN"));
+    assert_eq!(Err(ConversionError::NotFinite), ratio("-Infinity"));
+}
+
+#[test]
+fn it_takes_reciprocals() {
+    let recip = |s: &str| s.parse::<Decimal128>().unwrap().recip().to_string();
+    assert_eq!("2", recip("0.5"));
+    assert_eq!("0.25", recip("4"));
+    assert_eq!("-0.125", recip("-8"));
+    assert_eq!("0.8", recip("1.25"));
+    assert_eq!("1E+3", recip("0.001"));
+    assert_eq!("1", recip("1.00"));
+    assert_eq!("0.3333333333333333333333333333333333", recip("3"));
+    assert_eq!("1E-6144", recip("1E+6144"));
+    assert_eq!(
+        "1.428571428571428571428571428571429E+6143",
+        recip("7E-6144")
+    );
+    assert_eq!("Infinity", recip("1E-6176"));
+
+    assert_eq!("Infinity", recip("0"));
+    assert_eq!("-Infinity", recip("-0"));
+    assert_eq!("0E-6176", recip("Infinity"));
+    assert_eq!("-0E-6176", recip("-Infinity"));
+    assert_eq!("NaN", recip("NaN"));
+
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    for s in ["3", "7E-10", "-1.234567890123456789012345678901234"].iter() {
+        let (quotient, _) = d("1").overflowing_div(&d(s));
+        assert_eq!(quotient.to_raw_bytes(), d(s).recip().to_raw_bytes());
+    }
+}
+
+#[test]
+fn it_allocates_by_weight() {
+    let allocate = |s: &str, weights: &[u32]| -> Vec<String> {
+        let parts = s.parse::<Decimal128>().unwrap().allocate(weights);
+        parts.iter().map(|p| p.to_string()).collect()
+    };
+    assert_eq!(
+        vec!["33.34", "33.33", "33.33"],
+        allocate("100.00", &[1, 1, 1])
+    );
+    assert_eq!(
+        vec!["-33.34", "-33.33", "-33.33"],
+        allocate("-100.00", &[1, 1, 1])
+    );
+    assert_eq!(vec!["0.03", "0.02"], allocate("0.05", &[1, 1]));
+    assert_eq!(vec!["2", "1", "0"], allocate("3", &[1, 1, 0]));
+    assert_eq!(vec!["0", "6", "4"], allocate("10", &[0, 3, 2]));
+    // 5 cents split 1:2:3 is 0.83, 1.67 and 2.5 cents: the 2 cents left
+    // over after rounding down go to the two largest fractions
+    assert_eq!(vec!["0.01", "0.02", "0.02"], allocate("0.05", &[1, 2, 3]));
+    assert_eq!(vec!["0E+3", "0E+3"], allocate("0E+3", &[1, 2]));
+    assert_eq!(vec!["1E+3"], allocate("1E+3", &[7]));
+    assert!(allocate("1", &[]).is_empty());
+
+    assert_eq!(vec!["NaN", "NaN"], allocate("1", &[0, 0]));
+    assert_eq!(vec!["NaN"], allocate("Infinity", &[1]));
+    assert_eq!(vec!["NaN"], allocate("sNaN", &[1]));
+
+    // the parts add up to the original exactly, even with large weights
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let amount = d("9999999999999999999999999999999.999");
+    let parts = amount.allocate(&[u32::MAX, u32::MAX - 1, 7, 1]);
+    let (sum, flags) = Decimal128::sum_exact(&parts);
+    assert!(flags.is_empty());
+    assert_eq!(amount.to_raw_bytes(), sum.to_raw_bytes());
+}
+
+#[test]
+fn it_takes_cube_roots() {
+    let cbrt = |s: &str| s.parse::<Decimal128>().unwrap().cbrt().to_string();
+    assert_eq!("3", cbrt("27"));
+    assert_eq!("-3", cbrt("-27"));
+    assert_eq!("10", cbrt("1000"));
+    assert_eq!("2E+1", cbrt("8E+3"));
+    assert_eq!("2.0", cbrt("8.000"));
+    assert_eq!("0.1", cbrt("0.001"));
+    assert_eq!("1.23", cbrt("1.860867"));
+    assert_eq!("1E+2037", cbrt("1E+6111"));
+    assert_eq!("1.259921049894873164767210607278228", cbrt("2"));
+    assert_eq!("4.641588833612778892410076350919447", cbrt("100"));
+    assert_eq!("0.7937005259840997373758528196361541", cbrt("0.5"));
+    assert_eq!(
+        "4979338592.347722697109915038832025",
+        cbrt("123456789012345678901234567890")
+    );
+    assert_eq!("2.154434690031883721759293566519350E-2059", cbrt("1E-6176"));
+    assert_eq!(
+        "2.154434690031883721759293566519350E+2048",
+        cbrt("9.999999999999999999999999999999999E+6144")
+    );
+
+    assert_eq!("0", cbrt("0"));
+    assert_eq!("-0.000", cbrt("-0E-7"));
+    assert_eq!("Infinity", cbrt("Infinity"));
+    assert_eq!("-Infinity", cbrt("-Infinity"));
+    assert_eq!("NaN", cbrt("sNaN"));
+}
+
+#[test]
+fn it_provides_mathematical_constants() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let constants = [
+        (consts::PI, "3.141592653589793238462643383279503"),
+        (consts::E, "2.718281828459045235360287471352662"),
+        (consts::LN_10, "2.302585092994045684017991454684364"),
+        (consts::LN_2, "0.6931471805599453094172321214581766"),
+        (consts::SQRT_2, "1.414213562373095048801688724209698"),
+    ];
+    for (constant, digits) in constants.iter() {
+        assert_eq!(*digits, constant.to_string());
+        assert_eq!(d(digits).to_raw_bytes(), constant.to_raw_bytes());
+        assert_eq!(Some(34), constant.precision());
+    }
+    assert_eq!(d("10").ln().to_string(), consts::LN_10.to_string());
+    assert_eq!(d("2").ln().to_string(), consts::LN_2.to_string());
+    assert_eq!(d("0.5").exp2().to_string(), consts::SQRT_2.to_string());
+}
+
+#[test]
+fn it_decodes_into_integer_parts() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!(10, d("1.5").radix());
+    assert_eq!(10, d("NaN").radix());
+    assert_eq!((1250, -2, 1), d("12.50").integer_decode());
+    assert_eq!((1250, -2, -1), d("-12.50").integer_decode());
+    assert_eq!((0, 3, -1), d("-0E+3").integer_decode());
+    assert_eq!((1, -6176, 1), d("1E-6176").integer_decode());
+    assert_eq!(
+        (9999999999999999999999999999999999, 6111, 1),
+        d("9.999999999999999999999999999999999E+6144").integer_decode()
+    );
+    assert_eq!((0, 0, -1), d("-Infinity").integer_decode());
+    assert_eq!((12, 0, 1), d("NaN12").integer_decode());
+
+    // the parts put back together give the same value
+    for s in ["-12.50", "7E+20", "0.000123"].iter() {
+        let (coefficient, exponent, sign) = d(s).integer_decode();
+        let sign = if sign < 0 { "-" } else { "" };
+        assert_eq!(
+            d(s).to_raw_bytes(),
+            d(&format!("{}{}E{}", sign, coefficient, exponent)).to_raw_bytes()
+        );
+    }
+}
+
+#[test]
+fn it_rounds_to_significant_figures() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let round = |s: &str, n: u32, mode: RoundingMode| d(s).round_sf(n, mode).unwrap().to_string();
+    assert_eq!("0.00123", round("0.0012345", 3, RoundingMode::HalfEven));
+    assert_eq!("1.50E+8", round("149597870.7", 3, RoundingMode::HalfEven));
+    assert_eq!("-1.2E+3", round("-1250", 2, RoundingMode::HalfEven));
+    assert_eq!("-1.3E+3", round("-1250", 2, RoundingMode::HalfUp));
+    assert_eq!("-1.3E+3", round("-1201", 2, RoundingMode::Floor));
+    assert_eq!("10", round("9.96", 2, RoundingMode::HalfEven));
+    assert_eq!("1E+1", round("9.6", 1, RoundingMode::Ceiling));
+    // already short enough, so nothing changes
+    assert_eq!("12.50", round("12.50", 4, RoundingMode::HalfEven));
+    assert_eq!("0E-9", round("0E-9", 1, RoundingMode::HalfEven));
+    assert_eq!("-Infinity", round("-Infinity", 1, RoundingMode::HalfEven));
+    assert_eq!("NaN", round("NaN", 1, RoundingMode::HalfEven));
+
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        d("1.5").round_sf(0, RoundingMode::HalfEven)
+    );
+    let max = d("9.999999999999999999999999999999999E+6144");
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        max.round_sf(2, RoundingMode::HalfEven)
+    );
+    // the exponent can't go past 6111, so zeros make up the difference
+    assert_eq!(
+        "9.900000000000000000000000000000000E+6144",
+        max.round_sf(2, RoundingMode::Down).unwrap().to_string()
+    );
+    assert_eq!(
+        "1.000000000000000000000000000000000E+6144",
+        d("9.96E+6143")
+            .round_sf(2, RoundingMode::HalfEven)
+            .unwrap()
+            .to_string()
+    );
+}
+
+#[test]
+fn it_writes_significant_figures() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!("1.500", d("1.5").to_precision_string(4));
+    assert_eq!("0.00123", d("0.0012345").to_precision_string(3));
+    assert_eq!("1.2E+3", d("1234.5").to_precision_string(2));
+    assert_eq!("1234", d("1234.5").to_precision_string(4));
+    assert_eq!("1234.50", d("1234.5").to_precision_string(6));
+    assert_eq!("-0.00", d("-0").to_precision_string(3));
+    assert_eq!("10", d("9.96").to_precision_string(2));
+    assert_eq!(
+        "1.000000000000000000000000000000000",
+        d("1").to_precision_string(50)
+    );
+    // there's no room for zeros below the smallest exponent
+    assert_eq!("1E-6176", d("1E-6176").to_precision_string(3));
+    assert_eq!("1.5E-6175", d("1.5E-6175").to_precision_string(5));
+    assert_eq!("1.2E-6171", d("1.23456E-6171").to_precision_string(2));
+    // nor is there room to drop digits above the largest one
+    assert_eq!(
+        "1.200000000000000000000000000000000E+6144",
+        d("1234567890123456789012345678901234E+6111").to_precision_string(2)
+    );
+    assert_eq!(
+        "Infinity",
+        d("9.999999999999999999999999999999999E+6144").to_precision_string(2)
+    );
+    assert_eq!("NaN", d("NaN").to_precision_string(2));
+}
+
+#[test]
+fn it_encodes_sortable_bytes() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let values: Vec<Decimal128> = [
+        "-NaN5",
+        "-NaN",
+        "-sNaN",
+        "-Infinity",
+        "-9.999999999999999999999999999999999E+6144",
+        "-1E+6144",
+        "-12.3",
+        "-12.30",
+        "-1",
+        "-1E-6176",
+        "-0E+3",
+        "-0",
+        "-0E-5",
+        "0E-6176",
+        "0",
+        "0E+6111",
+        "1E-6176",
+        "1E-6175",
+        "1.00",
+        "1.0",
+        "1",
+        "1.000000000000000000000000000000001",
+        "9.99",
+        "10",
+        "1E+1",
+        "1.2E+6144",
+        "9.999999999999999999999999999999999E+6144",
+        "Infinity",
+        "sNaN",
+        "sNaN3",
+        "NaN",
+        "NaN7",
+    ]
+    .iter()
+    .map(|s| d(s))
+    .collect();
+    for a in values.iter() {
+        let key = a.to_sortable_bytes();
+        let decoded = Decimal128::from_sortable_bytes(key).unwrap();
+        assert_eq!(a.to_raw_bytes(), decoded.to_raw_bytes(), "{}", a);
+        for b in values.iter() {
+            assert_eq!(
+                a.compare_total(b),
+                key.cmp(&b.to_sortable_bytes()),
+                "{} and {}",
+                a,
+                b
+            );
+        }
+    }
+    // the list above is in order
+    for pair in values.windows(2) {
+        assert!(pair[0].to_sortable_bytes() < pair[1].to_sortable_bytes());
+    }
+
+    // tags past NaN, a finite coefficient without 34 digits, and digits
+    // past the end of a zero aren't valid
+    let mut key = d("1.5").to_sortable_bytes();
+    key[0] = 10;
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        Decimal128::from_sortable_bytes(key)
+    );
+    let mut key = d("1.5").to_sortable_bytes();
+    for b in &mut key[3..17] {
+        *b = 0;
+    }
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        Decimal128::from_sortable_bytes(key)
+    );
+    let mut key = d("0").to_sortable_bytes();
+    key[17] = 1;
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        Decimal128::from_sortable_bytes(key)
+    );
+}
+
+#[test]
+fn it_converts_to_and_from_raw_bytes_wrappers() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    for s in ["-12.50", "NaN7", "-Infinity", "0E-6176"].iter() {
+        let raw = RawDecimal128::from(d(s));
+        assert_eq!(d(s).to_raw_bytes(), raw.bytes);
+        assert_eq!(raw, RawDecimal128::from(&d(s)));
+        assert_eq!(d(s).to_raw_bytes(), Decimal128::from(raw).to_raw_bytes());
+    }
+    // the bytes are compared, not the values
+    assert_ne!(
+        RawDecimal128::from(d("1.0")),
+        RawDecimal128::from(d("1.00"))
+    );
+    assert_eq!(16, std::mem::size_of::<RawDecimal128>());
+    assert_eq!(1, std::mem::align_of::<RawDecimal128>());
+}
+
+#[test]
+fn it_orders_values_that_are_not_nan() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
+    use std::hash::{Hash, Hasher};
+
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let n = |s: &str| NotNan::new(d(s)).unwrap();
+    assert_eq!(Err(ConversionError::Invalid), NotNan::new(d("NaN")));
+    assert_eq!(Err(ConversionError::Invalid), NotNan::try_from(d("-sNaN")));
+    assert_eq!(
+        "-Infinity",
+        NotNan::try_from(d("-Infinity")).unwrap().to_string()
+    );
+
+    let mut values: Vec<NotNan> = ["3", "-Infinity", "1.00", "-0", "Infinity", "0", "1"]
+        .iter()
+        .map(|s| n(s))
+        .collect();
+    values.sort();
+    let strings: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    assert_eq!(
+        vec!["-Infinity", "-0", "0", "1.00", "1", "3", "Infinity"],
+        strings
+    );
+    assert_eq!(n("1.0"), n("1.00"));
+    assert_eq!(std::cmp::Ordering::Equal, n("-0").cmp(&n("0")));
+    assert!(n("-1E+6144") < n("-1"));
+
+    let hash = |v: &NotNan| {
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&n("1.0")), hash(&n("1.00")));
+    let set: HashSet<NotNan> = ["2.5", "2.50", "-0", "0E+3"].iter().map(|s| n(s)).collect();
+    assert_eq!(2, set.len());
+
+    // the value is still there to use
+    let price = n("12.50");
+    assert_eq!(Some(2), price.scale());
+    assert_eq!(Some(2), price.as_ref().scale());
+    assert_eq!("12.50", Decimal128::from(price.clone()).to_string());
+    assert_eq!("12.50", price.into_inner().to_string());
+}
+
+#[test]
+fn it_divides_by_values_that_are_not_zero() {
+    use std::convert::TryFrom;
+
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let n = |s: &str| NonZeroDecimal128::new(d(s)).unwrap();
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        NonZeroDecimal128::new(d("-0E+3"))
+    );
+    assert_eq!(
+        Err(ConversionError::NotFinite),
+        NonZeroDecimal128::new(d("NaN"))
+    );
+    assert_eq!(
+        Err(ConversionError::NotFinite),
+        NonZeroDecimal128::try_from(d("-Infinity"))
+    );
+    assert_eq!("1E-6176", n("1E-6176").to_string());
+
+    assert_eq!("80", (d("100.00") / n("1.25")).to_string());
+    assert_eq!("1.20", (&d("2.40") / &n("2")).to_string());
+    assert_eq!(
+        "3.333333333333333333333333333333333",
+        (d("10") / n("3")).to_string()
+    );
+    assert_eq!("-0", (d("0") / n("-7")).to_string());
+    assert_eq!("-Infinity", (d("-Infinity") / n("2")).to_string());
+    assert_eq!("Infinity", (d("1E+6144") / n("1E-6176")).to_string());
+    assert!((d("NaN") / n("2")).is_nan());
+
+    assert_eq!(n("1.0"), n("1.00"));
+    assert!(n("-2") < n("0.5"));
+    assert_eq!(Some(2), n("12.50").scale());
+    assert_eq!("12.50", n("12.50").into_inner().to_string());
+}
+
+#[test]
+fn it_implements_std_errors() {
+    use std::convert::TryFrom;
+
+    fn parse(s: &str) -> Result<i128, Box<dyn std::error::Error>> {
+        let dec: Decimal128 = s.parse()?;
+        Ok(i128::try_from(dec)?)
+    }
+    assert_eq!(12, parse("12").unwrap());
+    assert_eq!(
+        "invalid character at index 1",
+        parse("1x").unwrap_err().to_string()
+    );
+    let err = parse("1.5").unwrap_err();
+    assert_eq!(
+        Some(&error::ConversionError::Inexact),
+        err.downcast_ref::<ConversionError>()
+    );
+    let err: Box<dyn std::error::Error + Send + Sync> =
+        Box::new(Decimal128Builder::new().exponent(7000).build().unwrap_err());
+    assert_eq!("exponent 7000 is outside -6176..=6111", err.to_string());
+}
+
+#[test]
+fn it_round_trips_canonical_strings() {
+    let from_bits = |bits: u128| Decimal128::from_raw_bytes(bits.to_be_bytes());
+    let values = [
+        ("0", "0".parse().unwrap()),
+        ("-0E-6176", "-0E-6176".parse().unwrap()),
+        ("1.50E+400", "1.50E+400".parse().unwrap()),
+        ("-Infinity", "-Infinity".parse().unwrap()),
+        ("NaN", "NaN".parse().unwrap()),
+        ("-sNaN42", "-sNaN42".parse().unwrap()),
+        // Infinity with a stray coefficient bit
+        (
+            "0x78000000000000000000000000000001",
+            from_bits(0x78 << 120 | 1),
+        ),
+        // a coefficient of 10^34
+        (
+            "0x3041ed09bead87c0378d8e6400000000",
+            from_bits(0x3040 << 112 | 10u128.pow(34)),
+        ),
+    ];
+    for (string, dec128) in values.iter() {
+        assert_eq!(*string, dec128.to_canonical_string());
+        let parsed = Decimal128::from_canonical_string(string).unwrap();
+        assert_eq!(dec128.to_raw_bytes(), parsed.to_raw_bytes());
+    }
+
+    for string in [
+        "+1",
+        "1e3",
+        "nan",
+        "Inf",
+        "0x30400000000000000000000000000001",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            Err(ParseDecimal128Error::NotCanonical),
+            Decimal128::from_canonical_string(string)
+        );
+    }
+    assert_eq!(
+        Err(ParseDecimal128Error::InvalidCharacter { index: 2 }),
+        Decimal128::from_canonical_string("0xG8000000000000000000000000000000")
+    );
+}
+
+#[test]
+fn it_formats_into_a_buffer() {
+    let mut buffer = Buffer::new();
+    for string in [
+        "0",
+        "-12.50",
+        "-0.000001234567890123456789012345678901234",
+        "-1.234567890123456789012345678901234E-6143",
+        "1E+6111",
+        "-Infinity",
+        "NaN",
+    ]
+    .iter()
+    {
+        let dec128: Decimal128 = string.parse().unwrap();
+        assert_eq!(dec128.to_string(), buffer.format(&dec128));
+        assert_eq!(*string, buffer.format(&dec128));
+    }
+}
+
+#[test]
+fn it_formats_into_a_byte_slice() {
+    let longest: Decimal128 = "-0.000001234567890123456789012345678901234"
+        .parse()
+        .unwrap();
+    let mut buf = [0u8; MAX_STRING_LEN];
+    assert_eq!(
+        "-0.000001234567890123456789012345678901234",
+        longest.format_into(&mut buf).unwrap()
+    );
+    assert!(longest.to_string().len() <= MAX_STRING_LEN);
+
+    let mut buf = [b'x'; 8];
+    let err = longest.format_into(&mut buf).unwrap_err();
+    assert_eq!(42, err.needed());
+    assert_eq!("buffer is too small, 42 bytes are needed", err.to_string());
+    assert_eq!([b'x'; 8], buf);
+
+    let nan: Decimal128 = "NaN".parse().unwrap();
+    assert_eq!("NaN", nan.format_into(&mut buf[..3]).unwrap());
+    assert_eq!(b"NaNxxxxx", &buf);
+}
+
+#[test]
+fn it_decodes_non_canonical_significands_as_zero() {
+    // `11` after the sign bit: exponent 6176 (unbiased 0), implicit
+    // significand of at least 2^113
+    let vec: [u8; 16] = [
+        0x6c, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x01,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!("0".to_string(), dec128.to_string());
+    assert_eq!(vec, dec128.to_raw_bytes());
+
+    // a 113-bit significand larger than 34 digits, exponent -2
+    let vec: [u8; 16] = [
+        0x30, 0x3d, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff,
+    ];
+    let dec128 = Decimal128::from_raw_bytes(vec);
+    assert_eq!("0.00".to_string(), dec128.to_string());
+}
+
+#[test]
+fn it_decodes_parquet_bytes() {
+    let dec128 = Decimal128::from_parquet_bytes(&[0xcf, 0xc7], 2).unwrap();
+    assert_eq!("-123.45".to_string(), dec128.to_string());
+    let dec128 = Decimal128::from_parquet_bytes(&[0x00, 0x00, 0x04, 0xd2], 6).unwrap();
+    assert_eq!("0.001234".to_string(), dec128.to_string());
+}
+
+#[test]
+fn it_round_trips_parquet_bytes() {
+    let bytes = [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0xbe, 0x99, 0x1a, 0x14,
+    ];
+    let dec128 = Decimal128::from_parquet_bytes(&bytes, 0).unwrap();
+    assert_eq!("123456789012".to_string(), dec128.to_string());
+    assert_eq!(bytes.to_vec(), dec128.to_parquet_bytes(0, 12).unwrap());
+}
+
+#[test]
+fn it_rejects_lossy_parquet_conversions() {
+    let dec128 = Decimal128::from_parquet_bytes(&[0x30, 0x39], 2).unwrap();
+    assert_eq!(
+        Err(ConversionError::Inexact),
+        dec128.to_parquet_bytes(1, 16)
+    );
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        dec128.to_parquet_bytes(2, 1)
+    );
+    let nan = Decimal128::from_raw_bytes([
+        0x7c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ]);
+    assert_eq!(Err(ConversionError::NotFinite), nan.to_parquet_bytes(2, 16));
+    // 10^38 - 1 has more digits than a Decimal128 coefficient can hold
+    let too_precise = 99_999_999_999_999_999_999_999_999_999_999_999_999i128.to_be_bytes();
+    assert_eq!(
+        Err(ConversionError::Inexact),
+        Decimal128::from_parquet_bytes(&too_precise, 0).map(|d| d.to_raw_bytes())
+    );
+}
+
+#[test]
+fn it_formats_mysql_decimals() {
+    let dec128 = Decimal128::from_mysql_decimal("-0.125", RoundingMode::HalfEven).unwrap();
+    assert_eq!(
+        "-0.12".to_string(),
+        dec128
+            .to_mysql_decimal(5, 2, RoundingMode::HalfEven)
+            .unwrap()
+    );
+    assert_eq!(
+        "-0.13".to_string(),
+        dec128.to_mysql_decimal(5, 2, RoundingMode::Floor).unwrap()
+    );
+    assert_eq!(
+        "-0.12500".to_string(),
+        dec128
+            .to_mysql_decimal(6, 5, RoundingMode::HalfEven)
+            .unwrap()
+    );
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        dec128.to_mysql_decimal(66, 2, RoundingMode::HalfEven)
+    );
+}
+
+#[test]
+fn it_checks_mysql_decimal_precision() {
+    let dec128 = Decimal128::from_mysql_decimal("123456.7", RoundingMode::HalfEven).unwrap();
+    assert_eq!(Ok((7, 1)), dec128.mysql_precision_scale());
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        dec128.to_mysql_decimal(7, 2, RoundingMode::HalfEven)
+    );
+    assert_eq!(
+        "123457".to_string(),
+        dec128
+            .to_mysql_decimal(6, 0, RoundingMode::HalfEven)
+            .unwrap()
+    );
+}
+
+#[test]
+fn it_parses_mysql_decimals() {
+    let dec128 = Decimal128::from_mysql_decimal(
+        "00012345678901234567890123456789012345.5",
+        RoundingMode::HalfEven,
+    )
+    .unwrap();
+    assert_eq!(
+        "1.234567890123456789012345678901235E+34".to_string(),
+        dec128.to_string()
+    );
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        Decimal128::from_mysql_decimal("1.2.3", RoundingMode::HalfEven).map(|d| d.to_raw_bytes())
+    );
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        Decimal128::from_mysql_decimal("-", RoundingMode::HalfEven).map(|d| d.to_raw_bytes())
+    );
+}
+
+#[test]
+fn it_converts_from_i32_and_u32() {
+    assert_eq!(
+        "-2147483648".to_string(),
+        Decimal128::from(i32::MIN).to_string()
+    );
+    assert_eq!(
+        "4294967295".to_string(),
+        Decimal128::from(u32::MAX).to_string()
+    );
+}
+
+#[test]
+fn it_converts_exactly_to_128_bit_integers() {
+    use std::convert::TryFrom;
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!(Ok(120), i128::try_from(d("1.20E+2")));
+    assert_eq!(Ok(120), i128::try_from(d("120.00")));
+    assert_eq!(Ok(-7), i128::try_from(d("-7")));
+    assert_eq!(Ok(0), i128::try_from(d("-0E-6176")));
+    assert_eq!(
+        Ok(-170_000_000_000_000_000_000_000_000_000_000_000_000),
+        i128::try_from(d("-1.7E+38"))
+    );
+    assert_eq!(Err(ConversionError::Overflow), i128::try_from(d("1.8E+38")));
+    assert_eq!(Err(ConversionError::Overflow), i128::try_from(d("1E+6111")));
+    assert_eq!(Err(ConversionError::Inexact), i128::try_from(d("0.5")));
+    assert_eq!(Err(ConversionError::Inexact), i128::try_from(d("1E-6176")));
+    assert_eq!(Err(ConversionError::NotFinite), i128::try_from(d("NaN")));
+    assert_eq!(
+        Err(ConversionError::NotFinite),
+        i128::try_from(d("-Infinity"))
+    );
+
+    assert_eq!(Ok(120), u128::try_from(d("1.20E+2")));
+    assert_eq!(
+        Ok(340_000_000_000_000_000_000_000_000_000_000_000_000),
+        u128::try_from(d("3.4E+38"))
+    );
+    assert_eq!(Ok(0), u128::try_from(d("-0.00")));
+    assert_eq!(Err(ConversionError::Overflow), u128::try_from(d("3.5E+38")));
+    assert_eq!(Err(ConversionError::Overflow), u128::try_from(d("-1")));
+    assert_eq!(Err(ConversionError::Inexact), u128::try_from(d("2.5")));
+    assert_eq!(
+        Err(ConversionError::NotFinite),
+        u128::try_from(d("Infinity"))
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn it_compares_across_exponents() {
+    let five = Decimal128::from_mysql_decimal("5.00", RoundingMode::HalfEven).unwrap();
+    let two = Decimal128::from(2);
+    let minus_five = Decimal128::from_mysql_decimal("-5.00", RoundingMode::HalfEven).unwrap();
+    let minus_two = Decimal128::from(-2);
+    assert_eq!(1, five.compare(&two));
+    assert_eq!(-1, two.compare(&five));
+    assert_eq!(-1, minus_five.compare(&minus_two));
+    assert_eq!(0, five.compare(&Decimal128::from(5)));
+    assert_eq!(
+        -1,
+        "-0".parse::<Decimal128>()
+            .unwrap()
+            .compare(&Decimal128::from(0))
+    );
+    assert!(minus_five < two);
+}
+
+#[test]
+fn it_compares_like_floats() {
+    use std::cmp::Ordering;
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!(d("0"), d("-0"));
+    assert_eq!(d("0E+5"), d("-0.00"));
+    assert_eq!(d("1.0"), d("1.00"));
+    assert_eq!(Some(Ordering::Equal), d("-0").partial_cmp(&d("0")));
+    assert!(d("-0") <= d("0") && d("-0") >= d("0"));
+    assert!(d("-1") < d("-0"));
+
+    let nan = d("NaN");
+    assert_ne!(nan, nan);
+    assert_ne!(d("-NaN"), d("-NaN"));
+    assert_eq!(None, nan.partial_cmp(&d("1")));
+    assert_eq!(None, d("-Infinity").partial_cmp(&d("-NaN")));
+    assert_ne!(nan, d("1"));
+    assert!(d("Infinity") > d("9.999999999999999999999999999999999E+6144"));
+
+    assert_eq!(Ordering::Less, d("-0").total_cmp(&d("0")));
+    assert_eq!(Ordering::Equal, d("1.0").total_cmp(&d("1.00")));
+    assert_eq!(Ordering::Equal, nan.total_cmp(&nan));
+    assert_eq!(Ordering::Less, d("-NaN").total_cmp(&d("-Infinity")));
+    assert_eq!(Ordering::Greater, nan.total_cmp(&d("Infinity")));
+}
+
+#[test]
+fn it_compares_total_orderings() {
+    use std::cmp::Ordering;
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let total = |a: &str, b: &str| d(a).compare_total(&d(b));
+    assert_eq!(Ordering::Less, total("-2", "1"));
+    assert_eq!(Ordering::Less, total("12.30", "12.3"));
+    assert_eq!(Ordering::Equal, total("12.30", "12.30"));
+    assert_eq!(Ordering::Greater, total("12.3", "12.30"));
+    assert_eq!(Ordering::Greater, total("-12.30", "-12.3"));
+    assert_eq!(Ordering::Greater, total("0", "-0"));
+    assert_eq!(Ordering::Less, total("0E-5", "0"));
+    assert_eq!(Ordering::Greater, total("-0E-5", "-0"));
+    assert_eq!(Ordering::Equal, total("Infinity", "Infinity"));
+    assert_eq!(
+        Ordering::Greater,
+        total("Infinity", "9.999999999999999999999999999999999E+6144")
+    );
+    assert_eq!(Ordering::Greater, total("sNaN", "Infinity"));
+    assert_eq!(Ordering::Greater, total("NaN", "sNaN"));
+    assert_eq!(Ordering::Less, total("-NaN", "-sNaN"));
+    assert_eq!(Ordering::Less, total("-NaN", "NaN"));
+    assert_eq!(Ordering::Less, total("NaN1", "NaN2"));
+    assert_eq!(Ordering::Greater, total("-NaN1", "-NaN2"));
+    assert_eq!(Ordering::Less, total("-sNaN", "-Infinity"));
+
+    let mag = |a: &str, b: &str| d(a).compare_total_mag(&d(b));
+    assert_eq!(Ordering::Greater, mag("-2", "1"));
+    assert_eq!(Ordering::Less, mag("-12.30", "12.3"));
+    assert_eq!(Ordering::Equal, mag("-0", "0"));
+    assert_eq!(Ordering::Equal, mag("-NaN", "NaN"));
+    assert_eq!(Ordering::Greater, mag("-Infinity", "1"));
+    assert_eq!(Ordering::Less, mag("sNaN9", "-NaN1"));
+}
+
+#[test]
+fn it_compares_to_a_decimal_result() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let compare = |a: &str, b: &str| d(a).compare_decimal(&d(b)).to_string();
+    assert_eq!("-1", compare("2.1", "3"));
+    assert_eq!("0", compare("2.1", "2.10"));
+    assert_eq!("1", compare("3", "2.1"));
+    assert_eq!("0", compare("-0", "0E+5"));
+    assert_eq!("-1", compare("-Infinity", "-1E+6144"));
+    assert_eq!(
+        "1",
+        compare("Infinity", "9.999999999999999999999999999999999E+6144")
+    );
+    assert_eq!("0", compare("-Infinity", "-Infinity"));
+    assert_eq!("NaN", compare("NaN", "NaN"));
+    assert_eq!("NaN", compare("1", "NaN"));
+    // the first NaN operand's sign and payload come through
+    assert_eq!(
+        d("-NaN7").to_raw_bytes(),
+        d("-NaN7").compare_decimal(&d("NaN8")).to_raw_bytes()
+    );
+    assert_eq!(
+        d("NaN8").to_raw_bytes(),
+        d("1").compare_decimal(&d("NaN8")).to_raw_bytes()
+    );
+}
+
+#[test]
+fn it_hashes_equal_values_alike() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let hash = |s: &str| {
+        let mut hasher = DefaultHasher::new();
+        s.parse::<Decimal128>().unwrap().hash(&mut hasher);
+        hasher.finish()
+    };
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let groups: &[&[&str]] = &[
+        &["1", "1.0", "1.000000000000000000000000000000000", "0.1E+1"],
+        &["-0", "0", "0.00", "0E+6111", "-0E-6176"],
+        &["1200", "1.2E+3", "12E+2", "1200.000"],
+        &["-1.5", "-1.50", "-15E-1"],
+        &["Infinity"],
+        &["-Infinity"],
+    ];
+    for group in groups {
+        for a in group.iter() {
+            for b in group.iter() {
+                assert_eq!(d(a), d(b), "{} == {}", a, b);
+                assert_eq!(hash(a), hash(b), "hash({}) == hash({})", a, b);
+            }
+        }
+    }
+    let firsts: std::collections::HashSet<u64> = groups.iter().map(|g| hash(g[0])).collect();
+    assert_eq!(groups.len(), firsts.len());
+    assert_ne!(hash("1"), hash("-1"));
+    assert_ne!(hash("1"), hash("1E+1"));
+    assert_ne!(hash("1E-6176"), hash("0"));
+}
+
+#[test]
+fn it_clamps_values() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let clamp = |v: &str, min: &str, max: &str| d(v).clamp(d(min), d(max)).to_string();
+    assert_eq!("-1", clamp("-5", "-1", "1"));
+    assert_eq!("1.00", clamp("5", "-1", "1.00"));
+    assert_eq!("0.5", clamp("0.5", "-1", "1"));
+    assert_eq!("1.0", clamp("1.0", "1", "2"));
+    assert_eq!("-0", clamp("-0", "0", "1"));
+    assert_eq!("0.00", clamp("0.00", "-0", "-0"));
+    assert_eq!("-Infinity", clamp("-Infinity", "-Infinity", "0"));
+    assert_eq!("1E+6000", clamp("Infinity", "0", "1E+6000"));
+    assert!(d("NaN").clamp(d("0"), d("1")).is_nan());
+}
+
+#[test]
+#[should_panic]
+fn it_panics_clamping_to_an_empty_range() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    d("1").clamp(d("2"), d("1"));
+}
+
+#[test]
+#[should_panic]
+fn it_panics_clamping_to_nan() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    d("1").clamp(d("0"), d("NaN"));
+}
+
+#[test]
+fn it_detects_zeros_with_any_exponent() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    for zero in ["0", "0E+5", "0.000", "0E-6176", "0E+6111"] {
+        assert!(d(zero).is_zero(), "{}", zero);
+        assert!(d(zero).is_positive_zero(), "{}", zero);
+        assert!(!d(zero).is_negative_zero(), "{}", zero);
+    }
+    for zero in ["-0", "-0E+5", "-0.000"] {
+        assert!(d(zero).is_zero(), "{}", zero);
+        assert!(d(zero).is_negative_zero(), "{}", zero);
+        assert!(!d(zero).is_positive_zero(), "{}", zero);
+    }
+    assert!(Decimal128::zero().is_positive_zero());
+    for value in ["1", "-1E-6176", "NaN", "-NaN", "Infinity", "-Infinity"] {
+        assert!(!d(value).is_zero(), "{}", value);
+        assert!(!d(value).is_positive_zero(), "{}", value);
+        assert!(!d(value).is_negative_zero(), "{}", value);
+    }
+}
+
+#[test]
+fn it_keeps_the_exponent_of_formatted_zeros() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    // to_string and to_engineering_string
+    let zeros = [
+        ("0", "0", "0"),
+        ("0.00", "0.00", "0.00"),
+        ("-0.00", "-0.00", "-0.00"),
+        ("0.000000", "0.000000", "0.000000"),
+        ("0E-7", "0E-7", "0.0E-6"),
+        ("0E-8", "0E-8", "0.00E-6"),
+        ("0E+1", "0E+1", "0.00E+3"),
+        ("0E+2", "0E+2", "0.0E+3"),
+        ("0E+3", "0E+3", "0E+3"),
+        ("-0E+5", "-0E+5", "-0.0E+6"),
+        ("0E+6111", "0E+6111", "0E+6111"),
+        ("0E-6176", "0E-6176", "0.00E-6174"),
+    ];
+    let wide = FormatOptions {
+        min_positional_exponent: -20,
+        max_positional_exponent: 20,
+        ..FormatOptions::default()
+    };
+    let mut buffer = Buffer::new();
+    for (s, string, engineering) in zeros.iter() {
+        let zero = d(s);
+        assert_eq!(*string, zero.to_string());
+        assert_eq!(*engineering, zero.to_engineering_string());
+        for formatted in [
+            zero.to_string(),
+            zero.to_engineering_string(),
+            zero.to_scientific_string(None),
+            zero.format_with(&wide),
+            buffer.format(&zero).to_string(),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                zero.to_raw_bytes(),
+                d(formatted).to_raw_bytes(),
+                "{}",
+                formatted
+            );
+        }
+    }
+}
+
+#[test]
+fn it_counts_coefficient_digits() {
+    assert_eq!(1, Decimal128::zero().digit_count());
+    assert_eq!(1, Decimal128::from(7).digit_count());
+    assert_eq!(10, Decimal128::from(i32::MIN).digit_count());
+    let five = Decimal128::from_mysql_decimal("5.00", RoundingMode::HalfEven).unwrap();
+    assert_eq!(3, five.digit_count());
+    let vec: [u8; 16] = [
+        0x2f, 0xfc, 0x3c, 0xde, 0x6f, 0xff, 0x97, 0x32, 0xde, 0x82, 0x5c, 0xd0, 0x7e, 0x96, 0xaf,
+        0xf2,
+    ];
+    assert_eq!(34, Decimal128::from_raw_bytes(vec).digit_count());
+}
+
+#[test]
+fn it_iterates_over_coefficient_digits() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let digits = |v: Decimal128| v.digits().collect::<Vec<u8>>();
+    assert_eq!(vec![1, 2, 5, 0], digits(d("-12.50")));
+    assert_eq!(vec![0], digits(d("0E+7")));
+    assert_eq!(vec![0], digits(d("-Infinity")));
+    assert_eq!(vec![4, 2], digits(d("NaN42")));
+    assert_eq!(vec![1, 0, 0], digits(d("1.00E+10")));
+    let max = d("9.999999999999999999999999999999999E+6144");
+    assert_eq!(vec![9; 34], digits(max.clone()));
+
+    // the count is known up front, and matches digit_count
+    for s in ["0", "7", "-2147483648", "5.00", "1E-6176"].iter() {
+        let v = d(s);
+        let mut digits = v.digits();
+        assert_eq!(v.digit_count() as usize, digits.len());
+        digits.next();
+        assert_eq!(v.digit_count() as usize - 1, digits.len());
+    }
+    assert_eq!(max.digit_count() as usize, max.digits().len());
+}
+
+#[test]
+fn it_reports_scale_and_precision() {
+    let value: Decimal128 = "-123.4500".parse().unwrap();
+    assert_eq!(Some(4), value.scale());
+    assert_eq!(Some(7), value.precision());
+    let value: Decimal128 = "5E+3".parse().unwrap();
+    assert_eq!(Some(-3), value.scale());
+    assert_eq!(Some(1), value.precision());
+    assert_eq!(Some(6176), Decimal128::zero().scale());
+    assert_eq!(Some(1), Decimal128::zero().precision());
+
+    let nan: Decimal128 = "NaN".parse().unwrap();
+    assert_eq!(None, nan.scale());
+    assert_eq!(None, nan.precision());
+    let inf: Decimal128 = "Infinity".parse().unwrap();
+    assert_eq!(None, inf.scale());
+    assert_eq!(None, inf.precision());
+}
+
+#[test]
+fn it_rescales_values() {
+    let value: Decimal128 = "-2.5".parse().unwrap();
+    let rescale = |mode| value.rescale(0, mode).unwrap().to_string();
+    assert_eq!("-2", rescale(RoundingMode::HalfEven));
+    assert_eq!("-3", rescale(RoundingMode::HalfUp));
+    assert_eq!("-2", rescale(RoundingMode::Ceiling));
+    assert_eq!("-3", rescale(RoundingMode::Floor));
+    assert_eq!(
+        "-2.5000",
+        value
+            .rescale(4, RoundingMode::HalfEven)
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(
+        "-0E+2",
+        value
+            .rescale(-2, RoundingMode::HalfEven)
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(
+        "0E-6176",
+        Decimal128::zero()
+            .rescale(6176, RoundingMode::HalfEven)
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(
+        Some(-40),
+        Decimal128::zero()
+            .rescale(-40, RoundingMode::HalfEven)
+            .unwrap()
+            .scale()
+    );
+
+    let nines: Decimal128 = "999999999999999999999999999999999.9".parse().unwrap();
+    assert_eq!(
+        "1000000000000000000000000000000000",
+        nines
+            .rescale(0, RoundingMode::HalfEven)
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        nines.rescale(2, RoundingMode::HalfEven)
+    );
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        value.rescale(34, RoundingMode::HalfEven)
+    );
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        value.rescale(6177, RoundingMode::HalfEven)
+    );
+    assert_eq!(
+        Err(ConversionError::Invalid),
+        value.rescale(i32::MIN, RoundingMode::HalfEven)
+    );
+    let inf: Decimal128 = "Infinity".parse().unwrap();
+    assert_eq!(
+        "Infinity",
+        inf.rescale(2, RoundingMode::HalfEven).unwrap().to_string()
+    );
+}
+
+#[test]
+fn it_creates_values_from_minor_units() {
+    let from = |units, scale| Decimal128::from_minor_units(units, scale).map(|v| v.to_string());
+    assert_eq!(Ok("123.45".to_string()), from(12345, 2));
+    assert_eq!(Ok("1.00".to_string()), from(100, 2));
+    assert_eq!(Ok("-0.005".to_string()), from(-5, 3));
+    assert_eq!(Ok("0.00".to_string()), from(0, 2));
+    assert_eq!(Ok("1.2E+3".to_string()), from(12, -2));
+    assert_eq!(
+        Ok("-92233720368547758.08".to_string()),
+        from(i64::MIN as i128, 2)
+    );
+    assert_eq!(
+        Ok("9999999999999999999999999999999.999".to_string()),
+        from(9_999_999_999_999_999_999_999_999_999_999_999, 3)
+    );
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        from(10_000_000_000_000_000_000_000_000_000_000_000, 3)
+    );
+    assert_eq!(Err(ConversionError::Overflow), from(i128::MIN, 0));
+    assert_eq!(Err(ConversionError::Invalid), from(1, 6177));
+    assert_eq!(Err(ConversionError::Invalid), from(1, -6112));
+    assert_eq!(Err(ConversionError::Invalid), from(1, i32::MIN));
+}
+
+#[test]
+fn it_converts_values_to_minor_units() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    assert_eq!(Ok(12345), d("123.45").to_minor_units_exact(2));
+    assert_eq!(Ok(123450), d("123.45").to_minor_units_exact(3));
+    assert_eq!(Ok(-500), d("-5").to_minor_units_exact(2));
+    assert_eq!(Ok(12), d("1.2E+3").to_minor_units_exact(-2));
+    assert_eq!(Ok(0), d("-0.000").to_minor_units_exact(0));
+    assert_eq!(Ok(0), d("0E+6000").to_minor_units_exact(2));
+    assert_eq!(
+        Err(ConversionError::Inexact),
+        d("0.125").to_minor_units_exact(2)
+    );
+    assert_eq!(
+        Err(ConversionError::Inexact),
+        d("1E-6176").to_minor_units_exact(2)
+    );
+
+    let value = d("-0.125");
+    assert_eq!(Ok(-12), value.to_minor_units(2, RoundingMode::HalfEven));
+    assert_eq!(Ok(-13), value.to_minor_units(2, RoundingMode::HalfUp));
+    assert_eq!(Ok(-12), value.to_minor_units(2, RoundingMode::Ceiling));
+    assert_eq!(Ok(-13), value.to_minor_units(2, RoundingMode::Floor));
+    assert_eq!(
+        Ok(0),
+        d("1E-6176").to_minor_units(2, RoundingMode::HalfEven)
+    );
+    assert_eq!(Ok(1), d("1E-6176").to_minor_units(2, RoundingMode::Up));
+
+    assert_eq!(
+        Ok(-170_000_000_000_000_000_000_000_000_000_000_000_000),
+        d("-1.7E+38").to_minor_units_exact(0)
+    );
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        d("1.8E+38").to_minor_units(0, RoundingMode::HalfEven)
+    );
+    assert_eq!(
+        Err(ConversionError::Overflow),
+        d("1").to_minor_units(39, RoundingMode::HalfEven)
+    );
+    assert_eq!(
+        Err(ConversionError::NotFinite),
+        d("NaN").to_minor_units(2, RoundingMode::HalfEven)
+    );
+    assert_eq!(
+        Err(ConversionError::NotFinite),
+        d("-Infinity").to_minor_units_exact(2)
+    );
+}
+
+#[test]
+fn it_splits_integer_and_fractional_parts() {
+    let split = |s: &str| {
+        let (integer, fraction) = s.parse::<Decimal128>().unwrap().trunc_fract();
+        (integer.to_string(), fraction.to_string())
+    };
+    assert_eq!(("12".to_string(), "0.50".to_string()), split("12.50"));
+    assert_eq!(("-5".to_string(), "-0.00".to_string()), split("-5.00"));
+    assert_eq!(("-0".to_string(), "-0.25".to_string()), split("-0.25"));
+    assert_eq!(("1.2E+3".to_string(), "0".to_string()), split("1.2E+3"));
+    assert_eq!(("7".to_string(), "0".to_string()), split("7"));
+    assert_eq!(("0".to_string(), "1E-6176".to_string()), split("1E-6176"));
+    assert_eq!(
+        (
+            "1".to_string(),
+            "0.234567890123456789012345678901234".to_string()
+        ),
+        split("1.234567890123456789012345678901234")
+    );
+    assert_eq!(("Infinity".to_string(), "0".to_string()), split("Infinity"));
+    let (integer, fraction) = "NaN".parse::<Decimal128>().unwrap().trunc_fract();
+    assert!(integer.is_nan() && fraction.is_nan());
+}
+
+#[test]
+fn it_returns_the_unit_in_the_last_place() {
+    let ulp = |s: &str| s.parse::<Decimal128>().unwrap().ulp().to_string();
+    assert_eq!("0.001", ulp("3.141"));
+    assert_eq!("1", ulp("-42"));
+    assert_eq!("1E+3", ulp("7E+3"));
+    assert_eq!("0.01", ulp("0.00"));
+    assert_eq!("1E-6176", ulp("-0E-6176"));
+    assert_eq!("1E+6111", ulp("9.999999999999999999999999999999999E+6144"));
+    assert_eq!("Infinity", ulp("-Infinity"));
+    assert_eq!("NaN", ulp("NaN"));
+}
+
+#[test]
+fn it_returns_the_quantum() {
+    let quantum = |s: &str| s.parse::<Decimal128>().unwrap().quantum().to_string();
+    assert_eq!("0.0001", quantum("-1.2500"));
+    assert_eq!("1E+2", quantum("0E+2"));
+    assert_eq!("Infinity", quantum("-Infinity"));
+    assert_eq!("NaN", quantum("NaN"));
+
+    let price: Decimal128 = "19.9".parse().unwrap();
+    let scale = price.quantum().scale().unwrap();
+    let total: Decimal128 = "39.795".parse().unwrap();
+    let total = total.rescale(scale, RoundingMode::HalfEven).unwrap();
+    assert_eq!("39.8", total.to_string());
+    assert_eq!(price.quantum().to_string(), total.quantum().to_string());
+}
+
+#[test]
+fn it_compares_quanta() {
+    let same = |a: &str, b: &str| {
+        let a: Decimal128 = a.parse().unwrap();
+        a.same_quantum(&b.parse().unwrap())
+    };
+    assert!(same("2.17", "-0.01"));
+    assert!(same("0E+3", "7E+3"));
+    assert!(!same("2.17", "2.170"));
+    assert!(!same("1", "1E+1"));
+    assert!(same("NaN", "sNaN"));
+    assert!(same("Infinity", "-Infinity"));
+    assert!(!same("NaN", "Infinity"));
+    assert!(!same("Infinity", "1"));
+    assert!(!same("0", "NaN"));
+}
+
+#[test]
+fn it_shifts_coefficient_digits() {
+    let shift = |s: &str, n| {
+        s.parse::<Decimal128>()
+            .unwrap()
+            .shift(n)
+            .map(|v| v.to_string())
+    };
+    assert_eq!(Ok("3400000000".to_string()), shift("34", 8));
+    assert_eq!(Ok("0".to_string()), shift("12", -9));
+    assert_eq!(Ok("1234567".to_string()), shift("123456789", -2));
+    assert_eq!(Ok("-1.23".to_string()), shift("-1.23", 0));
+    assert_eq!(
+        Ok("4567890123456789012345678901234000".to_string()),
+        shift("1234567890123456789012345678901234", 3)
+    );
+    assert_eq!(
+        Ok("0".to_string()),
+        shift("1234567890123456789012345678901234", 34)
+    );
+    assert_eq!(
+        Ok("0".to_string()),
+        shift("1234567890123456789012345678901234", -34)
+    );
+    assert_eq!(Ok("-Infinity".to_string()), shift("-Infinity", 5));
+    assert_eq!(Err(ConversionError::Invalid), shift("1", 35));
+    assert_eq!(Err(ConversionError::Invalid), shift("1", -35));
+}
+
+#[test]
+fn it_rotates_coefficient_digits() {
+    let rotate = |s: &str, n| {
+        s.parse::<Decimal128>()
+            .unwrap()
+            .rotate(n)
+            .map(|v| v.to_string())
+    };
+    assert_eq!(Ok("3400000000".to_string()), rotate("34", 8));
+    assert_eq!(
+        Ok("1200000000000000000000000000000000".to_string()),
+        rotate("12", -2)
+    );
+    assert_eq!(
+        Ok("-0.008900000000000000000000000000000067".to_string()),
+        rotate("-6.789E-33", -2)
+    );
+    assert_eq!(Ok("1.23".to_string()), rotate("1.23", 34));
+    assert_eq!(Ok("1.23".to_string()), rotate("1.23", -34));
+    assert_eq!(Ok("NaN".to_string()), rotate("NaN", 3));
+    assert_eq!(Err(ConversionError::Invalid), rotate("1", 35));
+}
+
+#[test]
+fn it_applies_logical_operations_to_digits() {
+    let d = |s: &str| s.parse::<Decimal128>().unwrap();
+    let (a, b) = (d("1010"), d("110"));
+    assert_eq!("10", a.logical_and(&b).unwrap().to_string());
+    assert_eq!("1110", a.logical_or(&b).unwrap().to_string());
+    assert_eq!("1100", a.logical_xor(&b).unwrap().to_string());
+    assert_eq!(
+        "1111111111111111111111111111110101",
+        a.logical_invert().unwrap().to_string()
+    );
+    assert_eq!("0", d("0").logical_and(&a).unwrap().to_string());
+
+    for invalid in &["2", "-1", "1.0", "1E+1", "NaN", "Infinity"] {
+        assert_eq!(Err(ConversionError::Invalid), d(invalid).logical_or(&a));
+        assert_eq!(Err(ConversionError::Invalid), a.logical_xor(&d(invalid)));
+        assert_eq!(Err(ConversionError::Invalid), d(invalid).logical_invert());
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+    use arbitrary::{Arbitrary, Unstructured};
+    use decimal128::*;
+
+    #[test]
+    fn it_builds_arbitrary_values_from_bytes() {
+        let data = [
+            0x30, 0x34, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x04, 0xd2, 0x7c,
+        ];
+        let mut u = Unstructured::new(&data);
+        let dec128 = Decimal128::arbitrary(&mut u).unwrap();
+        assert_eq!("0.001234".to_string(), dec128.to_string());
+        assert_eq!(1, u.len());
+        assert_eq!((16, Some(16)), Decimal128::size_hint(0));
+    }
+}
+
+#[cfg(feature = "borsh")]
+mod borsh {
+    use decimal128::*;
+
+    #[test]
+    fn it_serializes_with_borsh() {
+        let price: Decimal128 = "-12.50".parse().unwrap();
+        let bytes = borsh::to_vec(&price).unwrap();
+        let mut expected = price.to_raw_bytes();
+        expected.reverse();
+        assert_eq!(expected.to_vec(), bytes);
+        let decoded: Decimal128 = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(price.to_raw_bytes(), decoded.to_raw_bytes());
+
+        // inside other borsh types, like a Vec with its length in front
+        let values: Vec<Decimal128> = ["NaN", "-Infinity", "1E-6176", "0.00"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let bytes = borsh::to_vec(&values).unwrap();
+        assert_eq!(4 + 16 * values.len(), bytes.len());
+        let decoded: Vec<Decimal128> = borsh::from_slice(&bytes).unwrap();
+        for (value, decoded) in values.iter().zip(decoded.iter()) {
+            assert_eq!(value.to_raw_bytes(), decoded.to_raw_bytes());
+        }
+
+        assert!(borsh::from_slice::<Decimal128>(&[0x30; 15]).is_err());
+        assert!(borsh::from_slice::<Decimal128>(&[0x30; 17]).is_err());
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck {
+    use decimal128::*;
+
+    #[test]
+    fn it_casts_buffers_with_bytemuck() {
+        let values: Vec<Decimal128> = ["1.5", "-NaN", "Infinity"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        // one byte in front, so the values aren't aligned to anything
+        let mut buffer = vec![0xff];
+        for value in values.iter() {
+            buffer.extend_from_slice(&value.to_raw_bytes());
+        }
+        let raw: &[RawDecimal128] = bytemuck::cast_slice(&buffer[1..]);
+        let decoded: Vec<String> = raw
+            .iter()
+            .map(|r| Decimal128::from(*r).to_string())
+            .collect();
+        assert_eq!(vec!["1.5", "NaN", "Infinity"], decoded);
+        assert_eq!(&buffer[1..], bytemuck::cast_slice::<RawDecimal128, u8>(raw));
+
+        let zero: RawDecimal128 = bytemuck::Zeroable::zeroed();
+        assert_eq!("0E-6176", Decimal128::from(zero).to_string());
+        assert!(bytemuck::try_cast_slice::<u8, RawDecimal128>(&buffer[..20]).is_err());
+    }
+}
+
+#[cfg(feature = "ffi")]
+mod ffi {
+    use decimal128::ffi::*;
+    use std::ffi::{CStr, CString};
+    use std::os::raw::c_char;
+
+    fn from_string(s: &str, raw: &mut RawDecimal128) -> i32 {
+        let s = CString::new(s).unwrap();
+        unsafe { decimal128_from_string(s.as_ptr(), raw) }
+    }
+
+    fn parse(s: &str) -> RawDecimal128 {
+        let mut raw = RawDecimal128 { bytes: [0; 16] };
+        assert_eq!(0, from_string(s, &mut raw));
+        raw
+    }
+
+    fn to_string(value: RawDecimal128) -> String {
+        let mut buf = [0 as c_char; DECIMAL128_STRING_SIZE];
+        let len = unsafe { decimal128_to_string(value, buf.as_mut_ptr(), buf.len()) };
+        let string = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        assert_eq!(len, string.to_bytes().len());
+        string.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn it_round_trips_through_c_functions() {
+        let mut bytes = [0u8; 16];
+        bytes[0] = 0x30;
+        bytes[1] = 0x3c;
+        bytes[15] = 0xf4;
+        bytes[14] = 0x01;
+        let raw = unsafe { decimal128_from_bytes(bytes.as_ptr()) };
+        assert_eq!("5.00", to_string(raw));
+        let mut out = [0u8; 16];
+        unsafe { decimal128_to_bytes(raw, out.as_mut_ptr()) };
+        assert_eq!(bytes, out);
+
+        assert_eq!(raw, parse("5.00"));
+        assert_eq!(
+            "-1.000000000000000000000000000000000E-6143",
+            to_string(parse("-1.000000000000000000000000000000000E-6143"))
+        );
+    }
+
+    #[test]
+    fn it_reports_errors_and_short_buffers() {
+        let mut raw = RawDecimal128 { bytes: [0xaa; 16] };
+        assert_eq!(1, from_string("", &mut raw));
+        assert_eq!(2, from_string("1x", &mut raw));
+        assert_eq!(3, from_string("1E+9999", &mut raw));
+        assert_eq!(5, from_string("1E", &mut raw));
+        assert_eq!([0xaa; 16], raw.bytes);
+
+        let value = parse("-12.5");
+        let mut buf = [0x7f as c_char; 5];
+        assert_eq!(5, unsafe {
+            decimal128_to_string(value, buf.as_mut_ptr(), buf.len())
+        });
+        assert_eq!([0x7f; 5], buf);
+        let mut buf = [0x7f as c_char; 6];
+        assert_eq!(5, unsafe {
+            decimal128_to_string(value, buf.as_mut_ptr(), buf.len())
+        });
+        assert_eq!(0, buf[5]);
+    }
+
+    #[test]
+    fn it_compares_and_classifies() {
+        assert_eq!(-1, decimal128_compare(parse("1"), parse("2")));
+        assert_eq!(0, decimal128_compare(parse("5.00"), parse("5")));
+        assert_eq!(1, decimal128_compare(parse("Infinity"), parse("1E+6144")));
+        assert_eq!(0, decimal128_compare(parse("-0"), parse("0.00")));
+        assert_eq!(
+            DECIMAL128_UNORDERED,
+            decimal128_compare(parse("NaN"), parse("1"))
+        );
+        assert_eq!(
+            DECIMAL128_UNORDERED,
+            decimal128_compare(parse("-NaN"), parse("-NaN"))
+        );
+        assert!(decimal128_is_nan(parse("NaN")));
+        assert!(!decimal128_is_nan(parse("Inf")));
+        assert!(decimal128_is_negative(parse("-0")));
+        assert!(decimal128_is_zero(parse("0E-6176")));
+        assert!(decimal128_is_zero(parse("-0.00")));
+        assert!(!decimal128_is_zero(parse("1E-6176")));
+    }
+}
+
+#[cfg(feature = "async-graphql")]
+mod async_graphql {
+    use async_graphql::{Number, ScalarType, Value};
+    use decimal128::*;
+
+    #[test]
+    fn it_is_an_async_graphql_scalar() {
+        let parse = |value: Value| {
+            <Decimal128 as ScalarType>::parse(value)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!("12.50", parse(Value::String("12.50".to_string())));
+        assert_eq!("-1E+400", parse(Value::String("-1E+400".to_string())));
+        assert_eq!("-42", parse(Value::Number(Number::from(-42))));
+        assert_eq!(
+            "18446744073709551615",
+            parse(Value::Number(Number::from(u64::MAX)))
+        );
+        assert_eq!("0.1", parse(Value::Number(Number::from_f64(0.1).unwrap())));
+
+        assert!(<Decimal128 as ScalarType>::parse(Value::String("1.2.3".to_string())).is_err());
+        assert!(<Decimal128 as ScalarType>::parse(Value::Boolean(true)).is_err());
+        assert!(!<Decimal128 as ScalarType>::is_valid(&Value::Null));
+
+        let price: Decimal128 = "12.50".parse().unwrap();
+        assert_eq!(Value::String("12.50".to_string()), price.to_value());
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+mod bigint {
+    use decimal128::*;
+    use num_bigint::BigInt;
+    use std::str::FromStr;
+
+    #[test]
+    fn it_returns_the_coefficient_as_a_bigint() {
+        let vec: [u8; 16] = [
+            0xb0, 0x34, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x04, 0xd2,
+        ];
+        let dec128 = Decimal128::from_raw_bytes(vec);
+        assert_eq!(BigInt::from(-1234), dec128.coefficient_bigint().unwrap());
+        let nan = Decimal128::from_raw_bytes([
+            0x7c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ]);
+        assert_eq!(Err(ConversionError::NotFinite), nan.coefficient_bigint());
+    }
+
+    #[test]
+    fn it_rounds_long_bigint_coefficients() {
+        // 40 digits
+        let coefficient = BigInt::from_str("1234567890123456789012345678901234567890").unwrap();
+        let dec128 = Decimal128::from_bigint(&coefficient, -40, RoundingMode::HalfEven).unwrap();
+        assert_eq!(
+            "0.1234567890123456789012345678901235".to_string(),
+            dec128.to_string()
+        );
+        let dec128 = Decimal128::from_bigint(&coefficient, 6110, RoundingMode::HalfEven);
+        assert_eq!(
+            Err(ConversionError::Overflow),
+            dec128.map(|d| d.to_raw_bytes())
+        );
+    }
+}
+
+#[cfg(feature = "math-extra")]
+mod math_extra {
+    use decimal128::*;
+
+    fn d(s: &str) -> Decimal128 {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn it_takes_sines_cosines_and_tangents() {
+        let sin = |s: &str| d(s).sin().to_string();
+        let cos = |s: &str| d(s).cos().to_string();
+        let tan = |s: &str| d(s).tan().to_string();
+        assert_eq!("0.8414709848078965066525023216302990", sin("1"));
+        assert_eq!("-0.8414709848078965066525023216302990", sin("-1"));
+        assert_eq!("0.4794255386042030002732879352155714", sin("0.5"));
+        assert_eq!("0.5403023058681397174009366074429766", cos("1"));
+        assert_eq!("0.5403023058681397174009366074429766", cos("-1"));
+        assert_eq!("1.557407724654902230506974807458360", tan("1"));
+        assert_eq!("0.7470222972386602793553526878252746", tan("-2.5"));
+        assert_eq!("1.000000000000000000000000000000000E-20", sin("1E-20"));
+        assert_eq!("1.000000000000000000000000000000000", cos("1E-20"));
+
+        // the nearest Decimal128 values to π and π/2
+        assert_eq!(
+            "1.000000000000000000000000000000000",
+            sin("1.570796326794896619231321691639751")
+        );
+        assert_eq!(
+            "4.420985846996875529104874722961539E-34",
+            cos("1.570796326794896619231321691639751")
+        );
+        assert_eq!(
+            "2261938930836633226244288822199802",
+            tan("1.570796326794896619231321691639751")
+        );
+        assert_eq!(
+            "-1.158028306006248941790250554076922E-34",
+            sin("3.141592653589793238462643383279503")
+        );
+    }
+
+    #[test]
+    fn it_reduces_large_arguments_exactly() {
+        assert_eq!(
+            "-0.8522008497671888017727058937530294",
+            d("1E+22").sin().to_string()
+        );
+        assert_eq!(
+            "0.5232147853951389454975944733847095",
+            d("1E+22").cos().to_string()
+        );
+        let max = d("9.999999999999999999999999999999999E+6144");
+        assert_eq!(
+            "0.5582907749092521238056875912416942",
+            max.sin().to_string()
+        );
+        assert_eq!(
+            "-0.8296453523350967216289114223081673",
+            max.cos().to_string()
+        );
+    }
+
+    #[test]
+    fn it_takes_arctangents() {
+        let atan = |s: &str| d(s).atan().to_string();
+        assert_eq!("0.7853981633974483096156608458198757", atan("1"));
+        assert_eq!("-0.7853981633974483096156608458198757", atan("-1"));
+        assert_eq!("0.4636476090008061162142562314612144", atan("0.5"));
+        assert_eq!("1.570796326794896619231221691639751", atan("1E+22"));
+        assert_eq!("1.000000000000000000000000000000000E-20", atan("1E-20"));
+        assert_eq!("1.570796326794896619231321691639751", atan("Infinity"));
+        assert_eq!("-1.570796326794896619231321691639751", atan("-Infinity"));
+    }
+
+    #[test]
+    fn it_handles_special_values_in_trigonometry() {
+        for f in [Decimal128::sin, Decimal128::cos, Decimal128::tan].iter() {
+            assert_eq!("NaN", f(&d("Infinity")).to_string());
+            assert_eq!("NaN", f(&d("-Infinity")).to_string());
+            assert_eq!("NaN", f(&d("sNaN")).to_string());
+        }
+        assert_eq!("-0.00", d("-0.00").sin().to_string());
+        assert_eq!("-0.00", d("-0.00").tan().to_string());
+        assert_eq!("-0.00", d("-0.00").atan().to_string());
+        assert_eq!("1", d("-0.00").cos().to_string());
+        assert_eq!("NaN", d("NaN").atan().to_string());
+    }
+}
+
+#[cfg(feature = "juniper")]
+mod juniper {
+    use decimal128::*;
+    use juniper::{
+        graphql_object, graphql_value, EmptyMutation, EmptySubscription, RootNode, Variables,
+    };
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn double(value: Decimal128) -> Decimal128 {
+            value.add_with_flags(&value).0
+        }
+    }
+
+    #[test]
+    fn it_is_a_juniper_scalar() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let double = |input: &str| {
+            let query = format!("{{ double(value: {}) }}", input);
+            juniper::execute_sync(&query, None, &schema, &Variables::new(), &())
+                .map(|(value, _)| value)
+        };
+        assert_eq!(Ok(graphql_value!({ "double": "2.50" })), double("\"1.25\""));
+        assert_eq!(
+            Ok(graphql_value!({ "double": "-2E+400" })),
+            double("\"-1E+400\"")
+        );
+        assert_eq!(Ok(graphql_value!({ "double": "84" })), double("42"));
+        assert_eq!(Ok(graphql_value!({ "double": "0.2" })), double("0.1"));
+        assert!(double("\"1.2.3\"").is_err());
+        assert!(double("true").is_err());
+    }
+}
+
+#[cfg(feature = "num-traits")]
+mod num_traits {
+    use decimal128::*;
+    use num_traits::{FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+
+    #[test]
+    fn it_converts_from_primitives() {
+        let dec128 = Decimal128::from_i64(-9_223_372_036_854_775_808).unwrap();
+        assert_eq!("-9223372036854775808".to_string(), dec128.to_string());
+        let dec128 = Decimal128::from_u128(u128::MAX).unwrap();
+        assert_eq!(
+            "3.402823669209384634633746074317682E+38".to_string(),
+            dec128.to_string()
+        );
+        let dec128 = Decimal128::from_f64(0.1).unwrap();
+        assert_eq!(
+            "0.1000000000000000055511151231257827".to_string(),
+            dec128.to_string()
+        );
+        assert!(Decimal128::from_f64(f64::NAN).unwrap().is_nan());
+    }
+
+    #[test]
+    fn it_converts_to_primitives() {
+        let dec128 = Decimal128::from_mysql_decimal("-1234.99", RoundingMode::HalfEven).unwrap();
+        assert_eq!(Some(-1234), dec128.to_i64());
+        assert_eq!(None, dec128.to_u64());
+        assert_eq!(Some(-1234.99), dec128.to_f64());
+        let vec: [u8; 16] = [
+            0x5f, 0xfe, 0x31, 0x4d, 0xc6, 0x44, 0x8d, 0x93, 0x38, 0xc1, 0x5b, 0x0a, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+        assert_eq!(None, Decimal128::from_raw_bytes(vec).to_i128());
+
+        let d = |s: &str| s.parse::<Decimal128>().unwrap();
+        assert_eq!(
+            Some(300_000_000_000_000_000_000_000_000_000_000_000_000),
+            d("3E+38").to_u128()
+        );
+        assert_eq!(
+            Some(340_000_000_000_000_000_000_000_000_000_000_000_000),
+            d("3.4E+38").to_u128()
+        );
+        assert_eq!(None, d("3.5E+38").to_u128());
+        assert_eq!(None, d("3E+38").to_i128());
+        assert_eq!(Some(0), d("-0.5").to_u128());
+        assert_eq!(None, d("-1").to_u128());
+        assert_eq!(Some(12), d("12.99").to_u64());
+    }
+
+    #[test]
+    fn it_is_a_signed_number() {
+        let d = |s: &str| s.parse::<Decimal128>().unwrap();
+        assert_eq!("0", <Decimal128 as Zero>::zero().to_string());
+        assert!(Zero::is_zero(&d("-0.00")));
+        assert_eq!("1", Decimal128::one().to_string());
+        assert_eq!(
+            "12.5",
+            Decimal128::from_str_radix("12.5", 10).unwrap().to_string()
+        );
+        assert_eq!(
+            Err(ParseDecimal128Error::InvalidRadix),
+            Decimal128::from_str_radix("ff", 16)
+        );
+
+        assert_eq!("1.50", d("-1.50").abs().to_string());
+        assert_eq!("0.50", d("2.00").abs_sub(&d("1.50")).to_string());
+        assert_eq!("0", d("1.50").abs_sub(&d("2")).to_string());
+        assert!(d("NaN").abs_sub(&d("1")).is_nan());
+        assert_eq!("-1", d("-0.01").signum().to_string());
+        assert_eq!("1", d("Infinity").signum().to_string());
+        assert_eq!("0", d("-0").signum().to_string());
+        assert!(d("NaN").signum().is_nan());
+        assert!(Signed::is_positive(&d("1E-6176")));
+        assert!(!Signed::is_positive(&d("0")));
+        assert!(!Signed::is_negative(&d("-0")));
+        assert!(Signed::is_negative(&d("-Infinity")));
+        assert!(!Signed::is_negative(&d("-NaN")));
+    }
+
+    // Generic code written against `Num` works with Decimal128.
+    fn mean<T: Num + Clone>(values: &[T]) -> T {
+        let mut total = T::zero();
+        let mut count = T::zero();
+        for v in values {
+            total = total + v.clone();
+            count = count + T::one();
+        }
+        total / count
+    }
+
+    #[test]
+    fn it_works_in_generic_numeric_code() {
+        let values: Vec<Decimal128> = ["1.50", "2.25", "3"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        assert_eq!("2.25", mean(&values).to_string());
+        assert_eq!(2.25, mean(&[1.5, 2.25, 3.0]));
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use bytes::BytesMut;
+    use decimal128::*;
+    use postgres_types::{FromSql, ToSql, Type};
+
+    fn to_numeric(dec128: &Decimal128) -> Vec<u8> {
+        let mut out = BytesMut::new();
+        dec128.to_sql(&Type::NUMERIC, &mut out).unwrap();
+        out.to_vec()
+    }
+
+    #[test]
+    fn it_encodes_postgres_numeric() {
+        let dec128 = Decimal128::from_parquet_bytes(&[0xcf, 0xc7], 2).unwrap();
+        // -123.45: two base 10000 digits, 123 and 4500, display scale 2
+        let expected = vec![0, 2, 0, 0, 0x40, 0, 0, 2, 0, 123, 0x11, 0x94];
+        assert_eq!(expected, to_numeric(&dec128));
+    }
+
+    #[test]
+    fn it_round_trips_postgres_numeric() {
+        let vec: [u8; 16] = [
+            0x30, 0x2a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x5a,
+            0xef, 0x40,
+        ];
+        let dec128 = Decimal128::from_raw_bytes(vec);
+        let numeric = to_numeric(&dec128);
+        let decoded = Decimal128::from_sql(&Type::NUMERIC, &numeric).unwrap();
+        assert_eq!("0.00123400000".to_string(), decoded.to_string());
+        assert_eq!(vec, decoded.to_raw_bytes());
+    }
+
+    #[test]
+    fn it_decodes_postgres_special_values() {
+        let nan = [0, 0, 0, 0, 0xc0, 0, 0, 0];
+        let decoded = Decimal128::from_sql(&Type::NUMERIC, &nan).unwrap();
+        assert!(decoded.is_nan());
+        let negative_infinity = [0, 0, 0, 0, 0xf0, 0, 0, 0];
+        let decoded = Decimal128::from_sql(&Type::NUMERIC, &negative_infinity).unwrap();
+        assert_eq!("-Infinity".to_string(), decoded.to_string());
+    }
+
+    #[test]
+    fn it_rejects_postgres_numerics_too_large_for_a_coefficient() {
+        // 340282366920938463463374607431768219999, just above u128::MAX
+        let mut numeric = vec![0, 10, 0, 9, 0, 0, 0, 0];
+        for group in [340u16, 2823, 6692, 938, 4634, 6337, 4607, 4317, 6821, 9999].iter() {
+            numeric.extend_from_slice(&group.to_be_bytes());
+        }
+        let err = Decimal128::from_sql(&Type::NUMERIC, &numeric).unwrap_err();
+        assert_eq!(
+            Some(&ConversionError::Inexact),
+            err.downcast_ref::<ConversionError>()
+        );
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod strategies {
+    use ::proptest::prelude::*;
+    use decimal128::proptest::{boundary_exponent, finite, non_canonical, special};
+    use decimal128::Decimal128;
+
+    proptest! {
+        #[test]
+        fn it_generates_finite_values(dec128 in finite()) {
+            prop_assert!(!dec128.is_nan());
+            prop_assert!(!dec128.to_string().contains("Infinity"));
+            prop_assert!((-6176..=6111).contains(&dec128.exponent.to_adjusted()));
+        }
+
+        #[test]
+        fn it_generates_special_values(dec128 in special()) {
+            prop_assert!(dec128.is_nan() || dec128.to_string().ends_with("Infinity"));
+        }
+
+        #[test]
+        fn it_generates_non_canonical_values(dec128 in non_canonical()) {
+            let bits = u128::from_be_bytes(dec128.to_raw_bytes());
+            let combination_11 = (bits >> 125) & 0b11 == 0b11;
+            let coefficient = bits & ((1 << 113) - 1);
+            prop_assert!(combination_11 || coefficient > 9_999_999_999_999_999_999_999_999_999_999_999);
+        }
+
+        #[test]
+        fn it_generates_boundary_exponents(dec128 in boundary_exponent()) {
+            let exponent = dec128.exponent.to_adjusted();
+            prop_assert!([-6176, -6175, -1, 0, 1, 6110, 6111].contains(&exponent));
+        }
+
+        #[test]
+        fn it_generates_arbitrary_values(dec128 in any::<Decimal128>()) {
+            prop_assert_eq!(dec128.to_raw_bytes(), Decimal128::from_raw_bytes(dec128.to_raw_bytes()).to_raw_bytes());
+        }
+
+        #[test]
+        fn it_round_trips_canonical_strings(dec128 in any::<Decimal128>()) {
+            let parsed = Decimal128::from_canonical_string(&dec128.to_canonical_string()).unwrap();
+            prop_assert_eq!(dec128.to_raw_bytes(), parsed.to_raw_bytes());
+        }
+
+        #[test]
+        fn it_round_trips_canonical_strings_of_any_bytes(bytes in any::<[u8; 16]>()) {
+            let dec128 = Decimal128::from_raw_bytes(bytes);
+            let parsed = Decimal128::from_canonical_string(&dec128.to_canonical_string()).unwrap();
+            prop_assert_eq!(bytes, parsed.to_raw_bytes());
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+mod random {
+    use decimal128::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn it_samples_standard_values_between_0_and_1() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let dec128: Decimal128 = rng.gen();
+            let string = dec128.to_string();
+            assert!(
+                string.starts_with("0.") || string.ends_with("E-34"),
+                "{}",
+                string
+            );
+        }
+    }
+
+    #[test]
+    fn it_samples_values_in_range() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let low = Decimal128::from_mysql_decimal("-0.05", RoundingMode::HalfEven).unwrap();
+        let high = Decimal128::from_mysql_decimal("0.05", RoundingMode::HalfEven).unwrap();
+        for _ in 0..100 {
+            let dec128 = rng.gen_range(low.clone()..high.clone());
+            let cents = dec128.to_mysql_decimal(3, 2, RoundingMode::Down).unwrap();
+            assert!([
+                "-0.05", "-0.04", "-0.03", "-0.02", "-0.01", "0.00", "0.01", "0.02", "0.03", "0.04"
+            ]
+            .contains(&cents.as_str()));
+            assert_eq!(
+                cents,
+                dec128
+                    .to_mysql_decimal(3, 2, RoundingMode::HalfEven)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn it_samples_values_at_a_fixed_scale() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let d = |s: &str| s.parse::<Decimal128>().unwrap();
+        let (low, high) = (d("-1"), d("1.005"));
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..2000 {
+            let value = Decimal128::random_range(&low, &high, 2, &mut rng);
+            assert_eq!(Some(2), value.scale());
+            assert!(value >= low && value <= high, "{}", value);
+            seen.insert(value.to_string());
+        }
+        // -1.00 through 1.00 in steps of 0.01
+        assert_eq!(201, seen.len());
+        assert!(seen.contains("-1.00") && seen.contains("1.00"));
+
+        let value = Decimal128::random_range(&d("5E+3"), &d("5000"), -3, &mut rng);
+        assert_eq!("5E+3", value.to_string());
+        let value = Decimal128::random_range(&d("0.001"), &d("0.009"), 3, &mut rng);
+        assert_eq!(Some(3), value.scale());
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_without_values_at_the_scale() {
+        let d = |s: &str| s.parse::<Decimal128>().unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        Decimal128::random_range(&d("0.001"), &d("0.009"), 2, &mut rng);
+    }
+
+    #[test]
+    fn it_rounds_stochastically() {
+        let d = |s: &str| s.parse::<Decimal128>().unwrap();
+        let mut ups = 0;
+        for _ in 0..10000 {
+            let rounded = d("1.27").rescale(1, RoundingMode::Stochastic).unwrap();
+            match rounded.to_string().as_str() {
+                "1.3" => ups += 1,
+                "1.2" => {}
+                other => panic!("{}", other),
+            }
+        }
+        // 7000 expected, with a standard deviation of about 46
+        assert!((6600..7400).contains(&ups), "{}", ups);
+
+        let mut ups = 0;
+        for _ in 0..10000 {
+            let rounded = d("-0.0430").round_sf(1, RoundingMode::Stochastic).unwrap();
+            match rounded.to_string().as_str() {
+                "-0.05" => ups += 1,
+                "-0.04" => {}
+                other => panic!("{}", other),
+            }
+        }
+        // 3000 expected, with a standard deviation of about 46
+        assert!((2600..3400).contains(&ups), "{}", ups);
+
+        // the 35th digit and past it are rounded away when parsing
+        let rounds_up = |s: &str, up: &str| {
+            (0..10000)
+                .filter(|_| {
+                    let (parsed, _) =
+                        Decimal128::from_str_rounded(s, RoundingMode::Stochastic).unwrap();
+                    parsed.to_string() == up
+                })
+                .count()
+        };
+        let ups = rounds_up(
+            "1.0000000000000000000000000000000005000000001",
+            "1.000000000000000000000000000000001",
+        );
+        assert!((4600..5400).contains(&ups), "{}", ups);
+        // 0.05 of the smallest subnormal value
+        let ups = rounds_up("5E-6178", "1E-6176");
+        assert!((300..700).contains(&ups), "{}", ups);
+
+        // exact values are never moved
+        for _ in 0..100 {
+            let rounded = d("1.20").rescale(1, RoundingMode::Stochastic).unwrap();
+            assert_eq!("1.2", rounded.to_string());
+        }
+    }
+
+    #[test]
+    fn it_samples_single_value_ranges() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let value = Decimal128::from_mysql_decimal("12.5", RoundingMode::HalfEven).unwrap();
+        let dec128 = rng.gen_range(value.clone()..=value.clone());
+        assert_eq!(value.to_raw_bytes(), dec128.to_raw_bytes());
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal {
+    use decimal128::*;
+    use rust_decimal::Decimal;
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    #[test]
+    fn it_converts_from_rust_decimal() {
+        let decimal = Decimal::from_str("-79228162514264337593543950335").unwrap();
+        let dec128 = Decimal128::from(decimal);
+        assert_eq!(
+            "-79228162514264337593543950335".to_string(),
+            dec128.to_string()
+        );
+        let decimal = Decimal::from_str("0.0000000000000000000000000001").unwrap();
+        assert_eq!("1E-28".to_string(), Decimal128::from(decimal).to_string());
+    }
+
+    #[test]
+    fn it_converts_to_rust_decimal() {
+        let dec128 = Decimal128::from_mysql_decimal("-1234.50", RoundingMode::HalfEven).unwrap();
+        let decimal = Decimal::try_from(dec128).unwrap();
+        assert_eq!("-1234.50".to_string(), decimal.to_string());
+    }
+
+    #[test]
+    fn it_reports_lossy_rust_decimal_conversions() {
+        let vec: [u8; 16] = [
+            0x2f, 0xfc, 0x3c, 0xde, 0x6f, 0xff, 0x97, 0x32, 0xde, 0x82, 0x5c, 0xd0, 0x7e, 0x96,
+            0xaf, 0xf2,
+        ];
+        let dec128 = Decimal128::from_raw_bytes(vec);
+        assert_eq!(
+            Err(ConversionError::Inexact),
+            Decimal::try_from(dec128.clone())
+        );
+        assert_eq!(
+            "0.1234567890123456789012345679".to_string(),
+            dec128
+                .to_rust_decimal(RoundingMode::HalfEven)
+                .unwrap()
+                .to_string()
+        );
+
+        let vec: [u8; 16] = [
+            0x5f, 0xfe, 0x31, 0x4d, 0xc6, 0x44, 0x8d, 0x93, 0x38, 0xc1, 0x5b, 0x0a, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+        let dec128 = Decimal128::from_raw_bytes(vec);
+        assert_eq!(
+            Err(ConversionError::Overflow),
+            dec128.to_rust_decimal(RoundingMode::HalfEven)
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use decimal128::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Order {
+        price: Decimal128,
+        #[serde(with = "decimal128::serde_strict")]
+        total: Decimal128,
+    }
+
+    fn from_json(json: &str) -> Result<String, String> {
+        serde_json::from_str::<Decimal128>(json)
+            .map(|v| v.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn it_deserializes_leniently() {
+        assert_eq!(Ok("12.50".to_string()), from_json(r#""12.50""#));
+        assert_eq!(Ok("Infinity".to_string()), from_json(r#""Infinity""#));
+        assert_eq!(Ok("-42".to_string()), from_json("-42"));
+        assert_eq!(
+            Ok("18446744073709551615".to_string()),
+            from_json("18446744073709551615")
+        );
+        assert_eq!(Ok("0.1".to_string()), from_json("0.1"));
+        assert_eq!(Ok("1.5E+300".to_string()), from_json("1.5e300"));
+        assert_eq!(
+            Ok("1E+400".to_string()),
+            from_json(r#"{"$numberDecimal": "1E+400"}"#)
+        );
+
+        assert!(from_json(r#""1.2.3""#).is_err());
+        assert!(from_json("true").is_err());
+        assert!(from_json("null").is_err());
+        assert!(from_json(r#"{"$numberDecimal": 12}"#).is_err());
+        assert!(from_json(r#"{"$numberDouble": "12"}"#).is_err());
+        assert!(from_json(r#"{"$numberDecimal": "12", "extra": 1}"#).is_err());
+        assert!(from_json("{}").is_err());
+    }
+
+    #[test]
+    fn it_serializes_as_strings() {
+        let price: Decimal128 = "-1.20E+400".parse().unwrap();
+        assert_eq!(r#""-1.20E+400""#, serde_json::to_string(&price).unwrap());
+
+        let order: Order = serde_json::from_str(r#"{"price": 9.99, "total": "19.98"}"#).unwrap();
+        assert_eq!("9.99", order.price.to_string());
+        assert_eq!("19.98", order.total.to_string());
+        assert_eq!(
+            r#"{"price":"9.99","total":"19.98"}"#,
+            serde_json::to_string(&order).unwrap()
+        );
+        // strict fields only take strings
+        assert!(serde_json::from_str::<Order>(r#"{"price": 9.99, "total": 19.98}"#).is_err());
+        assert!(serde_json::from_str::<Order>(
+            r#"{"price": 9.99, "total": {"$numberDecimal": "19.98"}}"#
+        )
+        .is_err());
+    }
+}
+
+#[cfg(feature = "utoipa")]
+mod utoipa {
+    use decimal128::*;
+    use utoipa::{PartialSchema, ToSchema};
+
+    #[derive(ToSchema)]
+    #[allow(dead_code)]
+    struct Order {
+        price: Decimal128,
+    }
+
+    #[test]
+    fn it_describes_an_openapi_schema() {
+        assert_eq!("Decimal128", Decimal128::name());
+        let schema = serde_json::to_value(Decimal128::schema()).unwrap();
+        assert_eq!("string", schema["type"]);
+        assert_eq!("decimal128", schema["format"]);
+        assert_eq!(serde_json::json!(["12.50"]), schema["examples"]);
+
+        // other schemas refer to it by name
+        let order = serde_json::to_value(Order::schema()).unwrap();
+        assert_eq!(
+            "#/components/schemas/Decimal128",
+            order["properties"]["price"]["$ref"]
+        );
+    }
+}
+
+#[cfg(feature = "wasm")]
+mod wasm {
+    use decimal128::wasm::WasmDecimal128;
+    use decimal128::*;
+
+    #[test]
+    fn it_exports_a_javascript_class() {
+        let value = WasmDecimal128::from_string("-12.50").unwrap();
+        assert_eq!("-12.50", value.to_js_string());
+
+        let bytes = value.to_bytes();
+        assert_eq!(16, bytes.len());
+        let decoded = WasmDecimal128::from_bytes(&bytes).unwrap();
+        assert_eq!("-12.50", decoded.to_js_string());
+        assert_eq!(0.0, value.compare(&decoded));
+
+        let one = WasmDecimal128::from(Decimal128::from(1));
+        assert_eq!(-1.0, value.compare(&one));
+        assert_eq!(1.0, one.compare(&value));
+        let zero = WasmDecimal128::from_string("0").unwrap();
+        assert_eq!(
+            0.0,
+            zero.compare(&WasmDecimal128::from_string("-0").unwrap())
+        );
+        assert!(zero
+            .compare(&WasmDecimal128::from_string("NaN").unwrap())
+            .is_nan());
+        assert_eq!("1", Decimal128::from(one).to_string());
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+mod zerocopy {
+    use decimal128::*;
+    use zerocopy::{FromBytes, IntoBytes};
+
+    #[test]
+    fn it_reads_buffers_with_zerocopy() {
+        let values: Vec<Decimal128> = ["-0.001", "9.999999999999999999999999999999999E+6144"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let mut buffer = vec![0xff];
+        for value in values.iter() {
+            buffer.extend_from_slice(&value.to_raw_bytes());
+        }
+        let raw = <[RawDecimal128]>::ref_from_bytes(&buffer[1..]).unwrap();
+        assert_eq!(2, raw.len());
+        for (value, raw) in values.iter().zip(raw.iter()) {
+            assert_eq!(value.to_raw_bytes(), Decimal128::from(*raw).to_raw_bytes());
+        }
+        assert_eq!(&buffer[1..], raw.as_bytes());
+
+        let (first, rest) = RawDecimal128::read_from_prefix(&buffer[1..]).unwrap();
+        assert_eq!("-0.001", Decimal128::from(first).to_string());
+        assert_eq!(16, rest.len());
+        assert!(<[RawDecimal128]>::ref_from_bytes(&buffer[..20]).is_err());
+    }
+}
+
+#[cfg(feature = "cli")]
+mod cli {
+    use std::process::Command;
+
+    fn run(args: &[&str]) -> (bool, String, String) {
+        let output = Command::new(env!("CARGO_BIN_EXE_decimal128-cli"))
+            .args(args)
+            .output()
+            .unwrap();
+        (
+            output.status.success(),
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    }
+
+    #[test]
+    fn it_breaks_down_decimal_strings() {
+        let (success, stdout, _) = run(&["-12.50"]);
+        assert!(success);
+        assert_eq!(
+            "decimal:     -12.50\n\
+             hex:         b03c 0000 0000 0000 0000 0000 0000 04e2\n\
+             bson hex:    e204 0000 0000 0000 0000 0000 0000 3cb0\n\
+             kind:        finite\n\
+             sign:        -\n\
+             exponent:    -2\n\
+             coefficient: 1250\n\
+             canonical:   yes\n",
+            stdout
+        );
+    }
+
+    #[test]
+    fn it_reads_hex_in_either_byte_order() {
+        let (_, big_endian, _) = run(&["0x7e00_0000_0000_0000_0000_0000_0000_000c"]);
+        let (_, little_endian, _) = run(&["--le", "0x0c00 0000 0000 0000 0000 0000 0000 007e"]);
+        assert_eq!(big_endian, little_endian);
+        assert!(big_endian.starts_with("decimal:     sNaN12\n"));
+        assert!(
+            big_endian.contains("kind:        signaling NaN\nsign:        +\npayload:     12\n")
+        );
+    }
+
+    #[test]
+    fn it_flags_non_canonical_encodings() {
+        let (success, stdout, _) = run(&["0x6fffffffffffffffffffffffffffffff"]);
+        assert!(success);
+        assert!(stdout.starts_with("decimal:     0E+2015\n"));
+        assert!(stdout.ends_with("canonical:   no\n"));
+    }
+
+    #[test]
+    fn it_fails_on_invalid_values() {
+        let (success, _, stderr) = run(&["1x"]);
+        assert!(!success);
+        assert_eq!("1x: invalid character at index 1\n", stderr);
+        let (success, _, stderr) = run(&["0x7c"]);
+        assert!(!success);
+        assert_eq!("0x7c: expected 32 hex digits, found 2\n", stderr);
+    }
+}